@@ -15,6 +15,13 @@ pub const TOPDOWN_LOOKAT_Z: f32 = 8.7;
 pub const TOPDOWN_WALL_ALPHA: f32 = 1.0;
 pub const TOPDOWN_ROOF_ALPHA: f32 = 1.0;
 
+// Spectator cameras
+pub const SPECTATOR_FLY_SPEED: f32 = 12.0; // meters per second
+pub const SPECTATOR_FLY_FAST_MULTIPLIER: f32 = 3.0; // While Shift is held
+pub const SPECTATOR_FOLLOW_DISTANCE: f32 = 6.0; // meters behind the followed player
+pub const SPECTATOR_FOLLOW_HEIGHT: f32 = 3.0; // meters above the followed player's eyes
+pub const SPECTATOR_START_HEIGHT: f32 = 20.0; // meters above ground for the free-fly camera
+
 // Rearview mirror
 pub const REARVIEW_WIDTH_RATIO: f32 = 0.25; // Width as ratio of screen width
 pub const REARVIEW_HEIGHT_RATIO: f32 = 0.25; // Height as ratio of screen height
@@ -26,6 +33,7 @@ pub const REARVIEW_FOV_DEGREES: f32 = 90.0;
 // ============================================================================
 
 pub const MOUSE_SENSITIVITY: f32 = 0.002; // radians per pixel
+pub const MAX_PITCH: f32 = std::f32::consts::FRAC_PI_2 - 0.05; // radians up or down
 
 // ============================================================================
 // Player Model
@@ -49,6 +57,9 @@ pub const WALL_LIGHT_RANGE: f32 = 10.0; // meters
 pub const WALL_LIGHT_INWARD_OFFSET: f32 = 0.2; // meters
 pub const WALL_LIGHT_RADIUS: f32 = 0.1; // Larger radius = softer specular highlights, less shimmer
 pub const WALL_LIGHT_EMISSIVE_LUMINANCE: f32 = 2.0;
+pub const WALL_LIGHT_FLICKER_RATE: f32 = 9.0; // radians per second
+pub const WALL_LIGHT_FLICKER_PHASE_OFFSET: f32 = 1.7; // radians between neighboring light indices
+pub const WALL_LIGHT_FLICKER_DIM: f32 = 0.1; // Brightness while flickered off
 
 // Scene lighting
 pub const LIGHT_AMBIENT_BRIGHTNESS: f32 = 100.0;
@@ -69,6 +80,19 @@ pub const FACE_CHANGE_THRESHOLD: f32 = 2.0; // degrees
 // Round-trip time
 pub const ECHO_INTERVAL: f32 = 10.0; // seconds
 
+// Reconnecting after the connection drops
+pub const RECONNECT_INITIAL_BACKOFF: f32 = 0.5; // seconds before the second attempt
+pub const RECONNECT_MAX_BACKOFF: f32 = 8.0; // seconds - the wait between attempts doubles up to this
+
+// Account logins
+pub const PASSWORD_ENV_VAR: &str = "CUBOID_WARS_PASSWORD"; // Password for `--password`, instead of prompting for it
+
+// ============================================================================
+// Matches
+// ============================================================================
+
+pub const MATCH_SUMMARY_DURATION: f32 = 10.0; // seconds the post-match summary stays on screen
+
 // ============================================================================
 // Player Labels
 // ============================================================================
@@ -94,6 +118,34 @@ pub const ITEM_SPEED_COLOR: [f32; 3] = [0.2, 0.7, 1.0]; // Light blue
 pub const ITEM_MULTISHOT_COLOR: [f32; 3] = [1.0, 0.2, 0.2]; // Red
 pub const ITEM_PHASING_COLOR: [f32; 3] = [0.2, 1.0, 0.2]; // Green
 pub const ITEM_SENTRY_HUNT_COLOR: [f32; 3] = [0.973, 0.973, 1.0]; // Pale blue
+pub const ITEM_SHIELD_COLOR: [f32; 3] = [1.0, 0.8, 0.2]; // Gold
+pub const ITEM_CLOAK_COLOR: [f32; 3] = [0.45, 0.4, 0.55]; // Dusky violet
+
+// Countdown bars and expiry warning for the local player's power-ups
+pub const POWER_UP_BAR_WIDTH: f32 = 120.0; // pixels
+pub const POWER_UP_BAR_HEIGHT: f32 = 8.0; // pixels
+pub const POWER_UP_EXPIRY_WARNING: f32 = 3.0; // seconds before an effect ends
+
+// Shield bubble around shielded players
+pub const SHIELD_SIZE: [f32; 3] = [1.4, 2.2, 1.4]; // Ellipsoid diameters (meters)
+pub const SHIELD_ALPHA: f32 = 0.25;
+
+// ============================================================================
+// Projectiles
+// ============================================================================
+
+// Glow of each weapon's projectiles (HDR, values above 1.0 bloom)
+pub const PROJECTILE_BALL_GLOW: [f32; 3] = [10.0, 10.0, 0.0]; // Yellow
+pub const PROJECTILE_RAIL_GLOW: [f32; 3] = [2.0, 8.0, 12.0]; // Cyan
+pub const PROJECTILE_GRENADE_GLOW: [f32; 3] = [12.0, 3.0, 0.0]; // Orange
+pub const PROJECTILE_SHOTGUN_GLOW: [f32; 3] = [10.0, 10.0, 10.0]; // White
+pub const PROJECTILE_ROCKET_GLOW: [f32; 3] = [12.0, 0.5, 0.5]; // Red
+
+// Fireball of an explosion, growing to the blast radius while it fades
+pub const EXPLOSION_DURATION: f32 = 0.4; // seconds
+pub const EXPLOSION_START_SCALE: f32 = 0.2; // Fraction of the blast radius at detonation
+pub const EXPLOSION_GLOW: [f32; 3] = [8.0, 3.0, 0.5]; // Orange (HDR)
+pub const EXPLOSION_ALPHA: f32 = 0.6;
 
 // ============================================================================
 // Cookies
@@ -113,6 +165,9 @@ pub const SENTRY_MODEL_DEPTH_OFFSET: f32 = 0.1;
 pub const SENTRY_MODEL_ANIMATION_SPEED: f32 = 1.0; // Walk animation speed
 pub const SENTRY_WALK_ANIMATION_INDEX: usize = 20; // Index of walk animation in glb
 pub const SENTRY_BOUNDING_BOX: bool = false; // Debug visualization
+pub const SENTRY_RING_RADIUS_RATIO: f32 = 0.6; // Archetype ring radius relative to sentry width
+pub const SENTRY_RING_THICKNESS: f32 = 0.05; // meters
+pub const SENTRY_RING_EMISSIVE_LUMINANCE: f32 = 3.0;
 
 // ============================================================================
 // Textures & Materials
@@ -135,6 +190,10 @@ pub const TEXTURE_ROOF_ROUGHNESS: f32 = 0.5;
 pub const TEXTURE_FLOOR_METALLIC: f32 = 0.5;
 pub const TEXTURE_FLOOR_ROUGHNESS: f32 = 0.5;
 
+// Material properties - Jump pads
+pub const JUMP_PAD_EMISSIVE_LUMINANCE: f32 = 4.0;
+pub const JUMP_PAD_THICKNESS: f32 = 0.05; // meters
+
 // Material properties - Cookies
 pub const TEXTURE_COOKIE_METALLIC: f32 = 1.0;
 pub const TEXTURE_COOKIE_ROUGHNESS: f32 = 1.0;
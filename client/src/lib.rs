@@ -3,5 +3,6 @@ pub mod constants;
 pub mod markers;
 pub mod net;
 pub mod resources;
+pub mod session;
 pub mod spawning;
 pub mod systems;
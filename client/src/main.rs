@@ -1,21 +1,22 @@
-use anyhow::{Context, Result};
+use anyhow::Result;
 use bevy::{
     prelude::*,
     window::{CursorGrabMode, CursorOptions, WindowPlugin, WindowPosition},
 };
 use clap::Parser;
-use quinn::Endpoint;
+use std::path::PathBuf;
 use tokio::{runtime::Runtime, time::Duration};
 
 use client::{
-    config::configure_client,
-    net::network_io_task,
+    net::{connect, network_io_task, send_login},
     resources::{DebugColors, *},
+    session::{load_session_token, read_password, session_token_path},
     systems::{
-        cameras::*, input::*, items::*, map::*, network::*, players::*, projectiles::*, sentries::*, skybox::*, ui::*,
+        cameras::*, input::*, items::*, map::*, network::*, players::*, projectiles::*, sentries::*, skybox::*,
+        spectator::*, ui::*,
     },
 };
-use common::{net::MessageStream, protocol::*};
+use common::protocol::*;
 
 // ============================================================================
 // CLI Arguments
@@ -32,6 +33,23 @@ struct Args {
     #[arg(short, long)]
     name: Option<String>,
 
+    // Log in to the account with the player name, with the password from CUBOID_WARS_PASSWORD or a prompt
+    // (otherwise a cached session is resumed, or play as a guest)
+    #[arg(long, default_value_t = false)]
+    password: bool,
+
+    // Register the account with the player name first (asks for the password like --password)
+    #[arg(long, default_value_t = false)]
+    register: bool,
+
+    // Watch the game without playing
+    #[arg(long, default_value_t = false, conflicts_with_all = ["password", "register"])]
+    spectate: bool,
+
+    // Directory for persistent data (cached session tokens)
+    #[arg(long, default_value = "data")]
+    data_dir: PathBuf,
+
     // Simulated network lag in milliseconds
     #[arg(long, default_value = "0")]
     lag_ms: u64,
@@ -74,9 +92,34 @@ fn main() -> Result<()> {
         first_name.unwrap_or("").to_string()
     });
 
+    // Log in to an account with the password, or with the session token cached by an earlier login (spectators
+    // don't log in to accounts)
+    let session_path = session_token_path(&args.data_dir, &args.server, &player_name);
+    let auth = if args.spectate {
+        None
+    } else if args.register {
+        Some(LoginAuth::Register {
+            password: read_password()?,
+        })
+    } else if args.password {
+        Some(LoginAuth::Password {
+            password: read_password()?,
+        })
+    } else {
+        load_session_token(&session_path).map(|token| LoginAuth::Token { token })
+    };
+    let session_token_file = SessionTokenFile(auth.is_some().then_some(session_path));
+
+    let login = CLogin {
+        name: player_name,
+        auth,
+        resume_token: None,
+        spectate: args.spectate,
+    };
+
     let rt = Runtime::new()?;
-    let connection = connect_to_server(&rt, args.server.as_str())?;
-    send_login(&rt, &connection, &player_name)?;
+    let connection = rt.block_on(connect(&args.server))?;
+    rt.block_on(send_login(&connection, &login))?;
 
     // Channel for sending from the network I/O task to the client
     let (to_client, from_server) = tokio::sync::mpsc::unbounded_channel();
@@ -84,12 +127,22 @@ fn main() -> Result<()> {
     let (to_server, from_client) = tokio::sync::mpsc::unbounded_channel();
 
     let artificial_lag = (args.lag_ms > 0).then(|| Duration::from_millis(args.lag_ms));
-    rt.spawn(network_io_task(connection, to_client, from_client, artificial_lag));
+    rt.spawn(network_io_task(
+        connection,
+        args.server.clone(),
+        login,
+        to_client,
+        from_client,
+        artificial_lag,
+    ));
 
     let window_position = window_position_from_args(&args);
 
     // Start Bevy app
     let mut app = App::new();
+    if args.spectate {
+        app.insert_resource(Spectator::default());
+    }
     app.add_plugins(
         DefaultPlugins
             .set(asset_plugin())
@@ -100,11 +153,24 @@ fn main() -> Result<()> {
     .insert_resource(PlayerMap::default())
     .insert_resource(ItemMap::default())
     .insert_resource(SentryMap::default())
+    .insert_resource(ProjectileMap::default())
+    .insert_resource(PredictedShotMap::default())
     .insert_resource(LocalPlayerInfo::default())
     .insert_resource(RoundTripTime::default())
     .insert_resource(FpsMeasurement::default())
     .insert_resource(LastUpdateSeq::default())
-    .insert_resource(CameraViewMode::default())
+    .insert_resource(ServerClock::default())
+    .insert_resource(Scoreboard::default())
+    .insert_resource(MatchSummary::default())
+    .insert_resource(Leaderboard::default())
+    .insert_resource(session_token_file)
+    .insert_resource(Reconnecting::default())
+    .insert_resource(SpectatorList::default())
+    .insert_resource(if args.spectate {
+        CameraViewMode::FreeFly
+    } else {
+        CameraViewMode::default()
+    })
     .insert_resource(RoofRenderingEnabled::default())
     .insert_resource(InputSettings {
         invert_pitch: args.invert_pitch,
@@ -123,11 +189,20 @@ fn main() -> Result<()> {
         Update,
         (
             input_movement_system,
+            input_jump_system,
             input_shooting_system,
+            input_weapon_switch_system,
+        )
+            .run_if(not(resource_exists::<Spectator>)),
+    )
+    .add_systems(
+        Update,
+        (
             input_cursor_toggle_system,
             input_camera_view_toggle_system,
             input_roof_toggle_system,
             input_fullscreen_toggle_system,
+            input_leaderboard_toggle_system,
         ),
     )
     .add_systems(Update, (network_echo_system, network_server_message_system))
@@ -138,6 +213,7 @@ fn main() -> Result<()> {
             players_transform_sync_system,
             players_face_to_transform_system,
             players_billboard_system,
+            players_shield_visibility_system,
         ),
     )
     .add_systems(
@@ -145,19 +221,30 @@ fn main() -> Result<()> {
         (
             local_player_camera_shake_system,
             local_player_cuboid_shake_system,
+            local_player_power_up_warning_system,
             local_player_camera_sync_system,
             local_player_rearview_sync_system.after(input_movement_system), // Run after input sets camera rotation
             local_player_rearview_system,
             local_player_visibility_sync_system,
         ),
     )
+    .add_systems(
+        Update,
+        (
+            spectator_follow_cycle_system,
+            spectator_free_fly_system,
+            spectator_camera_sync_system,
+        )
+            .run_if(resource_exists::<Spectator>),
+    )
     .add_systems(Update, (sentries_movement_system, sentries_transform_sync_system))
-    .add_systems(Update, projectiles_movement_system)
+    .add_systems(Update, (projectiles_movement_system, explosions_animation_system))
     .add_systems(Update, items_animation_system)
     .add_systems(
         Update,
         (
             map_spawn_walls_system,
+            map_despawn_destroyed_walls_system,
             map_toggle_wall_opacity_system,
             map_toggle_roof_visibility_system,
             map_make_wall_lights_emissive_system,
@@ -168,7 +255,16 @@ fn main() -> Result<()> {
         (
             ui_toggle_crosshair_system,
             ui_player_list_system,
+            ui_scoreboard_system,
+            ui_match_summary_system,
+            ui_leaderboard_system,
+            ui_reconnecting_system,
+            ui_spectator_list_system,
+            ui_spectator_system.run_if(resource_exists::<Spectator>),
             ui_stunned_blink_system,
+            ui_weapon_system,
+            ui_shield_charges_system,
+            ui_power_up_bars_system,
             ui_rtt_system,
             ui_fps_system,
         ),
@@ -185,26 +281,6 @@ fn main() -> Result<()> {
     Ok(())
 }
 
-fn connect_to_server(rt: &Runtime, server_addr: &str) -> Result<quinn::Connection> {
-    rt.block_on(async {
-        let mut endpoint = Endpoint::client("0.0.0.0:0".parse()?)?;
-        let client_config = configure_client()?;
-        endpoint.set_default_client_config(client_config);
-        endpoint
-            .connect(server_addr.parse()?, "localhost")?
-            .await
-            .context("failed to connect to server")
-    })
-}
-
-fn send_login(rt: &Runtime, connection: &quinn::Connection, name: &str) -> Result<()> {
-    rt.block_on(async {
-        let msg = ClientMessage::Login(CLogin { name: name.to_string() });
-        let stream = MessageStream::new(connection);
-        stream.send(&msg).await
-    })
-}
-
 const fn window_position_from_args(args: &Args) -> WindowPosition {
     match (args.window_x, args.window_y) {
         (Some(x), Some(y)) => WindowPosition::At(IVec2::new(x, y)),
@@ -12,6 +12,10 @@ pub struct LocalPlayerMarker;
 #[derive(Component)]
 pub struct PlayerModelMarker;
 
+// Marker component for the shield bubble around a player
+#[derive(Component)]
+pub struct ShieldMarker;
+
 // Marker component for sentry model entities (for animation)
 #[derive(Component)]
 pub struct SentryModelMarker;
@@ -40,14 +44,14 @@ pub struct WallMarker;
 #[derive(Component)]
 pub struct RoofMarker;
 
-// Marker component for roof walls
-#[derive(Component)]
-pub struct RoofWallMarker;
-
 // Marker component for ramps
 #[derive(Component)]
 pub struct RampMarker;
 
+// Marker component for jump pads
+#[derive(Component)]
+pub struct JumpPadMarker;
+
 // ============================================================================
 // UI Markers
 // ============================================================================
@@ -56,6 +60,30 @@ pub struct RampMarker;
 #[derive(Component)]
 pub struct PlayerListUIMarker;
 
+// Marker component for the spectator list UI
+#[derive(Component)]
+pub struct SpectatorListUIMarker;
+
+// Marker component for the spectator camera notice UI (spectators only)
+#[derive(Component)]
+pub struct SpectatorUIMarker;
+
+// Marker component for the scoreboard UI (shown while Tab is held)
+#[derive(Component)]
+pub struct ScoreboardUIMarker;
+
+// Marker component for the post-match summary UI
+#[derive(Component)]
+pub struct MatchSummaryUIMarker;
+
+// Marker component for the all-time leaderboard UI
+#[derive(Component)]
+pub struct LeaderboardUIMarker;
+
+// Marker component for the reconnecting notice UI
+#[derive(Component)]
+pub struct ReconnectingUIMarker;
+
 // Marker component for the crosshair UI
 #[derive(Component)]
 pub struct CrosshairUIMarker;
@@ -68,6 +96,18 @@ pub struct RttUIMarker;
 #[derive(Component)]
 pub struct FpsUIMarker;
 
+// Marker component for the local player's shield charges display
+#[derive(Component)]
+pub struct ShieldChargesUIMarker;
+
+// Marker component for the local player's selected weapon display
+#[derive(Component)]
+pub struct WeaponUIMarker;
+
+// Marker component for the local player's power-up countdown bars
+#[derive(Component)]
+pub struct PowerUpBarsUIMarker;
+
 // Marker component for the bump flash overlay
 #[derive(Component)]
 pub struct BumpFlashUIMarker;
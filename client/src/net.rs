@@ -1,17 +1,22 @@
-use anyhow::Error;
-use bevy::prelude::{debug, error, trace};
-use quinn::{Connection, ConnectionError};
+use anyhow::{Context, Error, Result};
+use bevy::prelude::{debug, error, trace, warn};
+use quinn::{Connection, ConnectionError, Endpoint};
 use tokio::{
-    sync::mpsc::{UnboundedReceiver, UnboundedSender},
-    time::{Duration, sleep},
+    sync::mpsc::{UnboundedReceiver, UnboundedSender, error::TryRecvError},
+    time::{Duration, Instant, sleep},
 };
 
-use common::{net::MessageStream, protocol::*};
+use crate::{
+    config::configure_client,
+    constants::{RECONNECT_INITIAL_BACKOFF, RECONNECT_MAX_BACKOFF},
+};
+use common::{constants::RECONNECT_GRACE_PERIOD, net::MessageStream, protocol::*};
 
 // Message emitted by the network task toward the Bevy world.
 #[derive(Debug, Clone)]
 pub enum ServerToClient {
     Message(ServerMessage),
+    Reconnecting, // Connection dropped, trying to resume the session
     Disconnected,
 }
 
@@ -22,34 +27,140 @@ pub enum ClientToServer {
     Close,
 }
 
-// Bidirectional bridge between the server connection and the Bevy world.
+// Connect to the server.
+pub async fn connect(server_addr: &str) -> Result<Connection> {
+    let mut endpoint = Endpoint::client("0.0.0.0:0".parse()?)?;
+    let client_config = configure_client()?;
+    endpoint.set_default_client_config(client_config);
+    endpoint
+        .connect(server_addr.parse()?, "localhost")?
+        .await
+        .context("failed to connect to server")
+}
+
+pub async fn send_login(connection: &Connection, login: &CLogin) -> Result<()> {
+    let stream = MessageStream::new(connection);
+    stream.send(&ClientMessage::Login(login.clone())).await
+}
+
+// Bidirectional bridge between the server connection and the Bevy world. When the connection drops after
+// logging in, reconnects and resumes the session with the resume token of the last Init.
 pub async fn network_io_task(
-    connection: Connection,
+    mut connection: Connection,
+    server_addr: String,
+    mut login: CLogin,
     to_client: UnboundedSender<ServerToClient>,
     mut from_client: UnboundedReceiver<ClientToServer>,
     lag: Option<Duration>,
 ) {
-    let stream = MessageStream::new(&connection);
+    let mut resume_token = None;
+    let mut awaiting_init = false;
+
+    loop {
+        exchange_messages(
+            &connection,
+            &mut resume_token,
+            &mut awaiting_init,
+            &to_client,
+            &mut from_client,
+            lag,
+        )
+        .await;
+
+        // Don't reconnect if the client closed the connection or hasn't logged in yet
+        if matches!(connection.close_reason(), Some(ConnectionError::LocallyClosed)) || to_client.is_closed() {
+            break;
+        }
+        let Some(token) = resume_token.take() else {
+            break;
+        };
+
+        warn!("connection to server lost, reconnecting");
+        let _ = to_client.send(ServerToClient::Reconnecting);
+        login.resume_token = Some(token);
+        let Some(new_connection) = reconnect(&server_addr, &login, &mut from_client).await else {
+            break;
+        };
+        connection = new_connection;
+        awaiting_init = true;
+    }
+
+    // Ensure disconnect notification is sent before task exits
+    debug!("network task exiting");
+    let _ = to_client.send(ServerToClient::Disconnected);
+}
+
+// Exchange messages over the connection until it closes.
+async fn exchange_messages(
+    connection: &Connection,
+    resume_token: &mut Option<String>,
+    awaiting_init: &mut bool,
+    to_client: &UnboundedSender<ServerToClient>,
+    from_client: &mut UnboundedReceiver<ClientToServer>,
+    lag: Option<Duration>,
+) {
+    let stream = MessageStream::new(connection);
 
     loop {
         tokio::select! {
             result = stream.recv::<ServerMessage>() => {
-                if !handle_server_message(result, lag, &to_client) {
+                if let Ok(ServerMessage::Init(init)) = &result {
+                    *resume_token = Some(init.resume_token.clone());
+                    *awaiting_init = false;
+                }
+                if !handle_server_message(result, lag, to_client) {
                     break;
                 }
             }
 
             cmd = from_client.recv() => {
-                if !handle_client_command(cmd, lag, &connection, &stream).await {
+                // Messages from before the session is resumed are stale
+                if *awaiting_init && matches!(cmd, Some(ClientToServer::Send(_))) {
+                    continue;
+                }
+                if !handle_client_command(cmd, lag, connection, &stream).await {
                     break;
                 }
             }
         }
     }
+}
 
-    // Ensure disconnect notification is sent before task exits
-    debug!("network task exiting");
-    let _ = to_client.send(ServerToClient::Disconnected);
+// Reconnect and send the login, backing off exponentially between attempts, until the server's grace period for
+// resuming is over. Returns `None` when giving up or when the client closes meanwhile.
+async fn reconnect(
+    server_addr: &str,
+    login: &CLogin,
+    from_client: &mut UnboundedReceiver<ClientToServer>,
+) -> Option<Connection> {
+    let deadline = Instant::now() + Duration::from_secs_f32(RECONNECT_GRACE_PERIOD);
+    let mut backoff = Duration::from_secs_f32(RECONNECT_INITIAL_BACKOFF);
+
+    loop {
+        // Drop messages sent while disconnected, but stop if the client is closing
+        loop {
+            match from_client.try_recv() {
+                Ok(ClientToServer::Send(_)) => {}
+                Ok(ClientToServer::Close) | Err(TryRecvError::Disconnected) => return None,
+                Err(TryRecvError::Empty) => break,
+            }
+        }
+
+        match connect(server_addr).await {
+            Ok(connection) => match send_login(&connection, login).await {
+                Ok(()) => return Some(connection),
+                Err(e) => warn!("failed to resume session: {e:#}"),
+            },
+            Err(e) => warn!("failed to reconnect: {e:#}"),
+        }
+
+        if Instant::now() + backoff > deadline {
+            error!("giving up reconnecting");
+            return None;
+        }
+        sleep(backoff).await;
+        backoff = (backoff * 2).min(Duration::from_secs_f32(RECONNECT_MAX_BACKOFF));
+    }
 }
 
 fn handle_server_message(
@@ -133,6 +244,7 @@ async fn handle_client_command(
         }
         None => {
             debug!("client channel closed");
+            connection.close(0u32.into(), b"client closing");
             false
         }
     }
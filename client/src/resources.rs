@@ -1,6 +1,7 @@
 use bevy::prelude::*;
 use std::{
     collections::{HashMap, VecDeque},
+    path::PathBuf,
     time::Duration,
 };
 use tokio::sync::mpsc::{
@@ -9,7 +10,12 @@ use tokio::sync::mpsc::{
 };
 
 use crate::net::{ClientToServer, ServerToClient};
-use common::protocol::{ItemId, PlayerId, SentryId, Speed, SpeedLevel};
+use common::{
+    power_ups::{ActiveEffects, EffectKind, ticks_to_seconds},
+    protocol::{ItemId, PlayerId, ProjectileId, SentryId, Speed, SpeedLevel},
+    stats::{LeaderboardEntry, ScoreboardEntry},
+    weapons::WeaponId,
+};
 
 // ============================================================================
 // Bevy Resources
@@ -24,10 +30,9 @@ pub struct PlayerInfo {
     pub entity: Entity,
     pub hits: i32,
     pub name: String,
-    pub speed_power_up: bool,
-    pub multi_shot_power_up: bool,
-    pub phasing_power_up: bool,
-    pub sentry_hunt_power_up: bool,
+    pub effects: ActiveEffects,
+    pub effect_expiries: Vec<(EffectKind, f32)>, // Local time at which each timed effect expires (seconds)
+    pub shield_charges: u8,
     pub stunned: bool,
 }
 
@@ -53,6 +58,53 @@ pub struct SentryInfo {
 #[derive(Resource, Default)]
 pub struct SentryMap(pub HashMap<SentryId, SentryInfo>);
 
+// Map of projectiles the server has announced (entities may already be gone locally)
+#[derive(Resource, Default)]
+pub struct ProjectileMap(pub HashMap<ProjectileId, Entity>);
+
+// Projectiles predicted for my own shots, by shot sequence number, until the server's `SShot` assigns their IDs
+#[derive(Resource, Default)]
+pub struct PredictedShotMap(pub HashMap<u32, Vec<Entity>>);
+
+// Latest scoreboard of the current match
+#[derive(Resource, Default)]
+pub struct Scoreboard {
+    pub remaining: f32,                // seconds until the match ends (when the scoreboard was received)
+    pub entries: Vec<ScoreboardEntry>, // Best score first
+}
+
+// Final scoreboard of the last match, shown as a summary for a while after the match ends
+#[derive(Resource, Default)]
+pub struct MatchSummary {
+    pub entries: Vec<ScoreboardEntry>, // Best score first
+    pub hide_at: f32,                  // Local time (seconds)
+}
+
+// All-time leaderboard panel, toggled with L (requested from the server whenever it opens)
+#[derive(Resource, Default)]
+pub struct Leaderboard {
+    pub visible: bool,
+    pub entries: Vec<LeaderboardEntry>, // Highest total score first
+}
+
+// Session token cache of the account the player logs in to (None for guests)
+#[derive(Resource, Default)]
+pub struct SessionTokenFile(pub Option<PathBuf>);
+
+// Whether the connection dropped and the session is being resumed
+#[derive(Resource, Default)]
+pub struct Reconnecting(pub bool);
+
+// Present when logged in as a spectator, with the player the follow camera is on
+#[derive(Resource, Default)]
+pub struct Spectator {
+    pub followed: Option<PlayerId>,
+}
+
+// Spectators watching the game, sorted by ID
+#[derive(Resource, Default)]
+pub struct SpectatorList(pub Vec<(PlayerId, String)>);
+
 // Last received SUpdate sequence number
 #[derive(Resource, Default)]
 pub struct LastUpdateSeq(pub u32);
@@ -61,6 +113,9 @@ pub struct LastUpdateSeq(pub u32);
 #[derive(Resource)]
 pub struct LocalPlayerInfo {
     pub last_shot_time: f32,
+    pub weapon_shot_times: [f32; WeaponId::ALL.len()],
+    pub weapon: WeaponId, // Selected weapon
+    pub next_shot_seq: u32,
     pub last_sent_speed: Speed,
     pub last_sent_face: f32,
     pub last_send_speed_time: f32,
@@ -73,6 +128,9 @@ impl Default for LocalPlayerInfo {
     fn default() -> Self {
         Self {
             last_shot_time: f32::NEG_INFINITY,
+            weapon_shot_times: [f32::NEG_INFINITY; WeaponId::ALL.len()],
+            weapon: WeaponId::Ball,
+            next_shot_seq: 0,
             last_sent_speed: Speed {
                 speed_level: SpeedLevel::Idle,
                 move_dir: 0.0,
@@ -102,12 +160,48 @@ pub struct RoundTripTime {
     pub measurements: VecDeque<Duration>,
 }
 
+// Offset of the server's clock from the local one, to convert replicated server ticks to local time
+#[derive(Resource, Default)]
+pub struct ServerClock {
+    pub offset: f64, // Server time minus local time (seconds)
+}
+
+impl ServerClock {
+    // Sync with a server tick received at local time `now`, which the server sent about half a round trip earlier.
+    pub fn sync(&mut self, tick: u32, now: f32, rtt: Duration) {
+        self.offset = ticks_to_seconds(tick) + rtt.as_secs_f64() / 2.0 - f64::from(now);
+    }
+
+    // Local time of a server tick (seconds).
+    #[must_use]
+    pub fn local_time(&self, tick: u32) -> f32 {
+        (ticks_to_seconds(tick) - self.offset) as f32
+    }
+}
+
 // Camera view mode
 #[derive(Resource, Default, PartialEq, Eq, Clone, Copy, Debug)]
 pub enum CameraViewMode {
     #[default]
     FirstPerson,
     TopDown,
+    FreeFly, // Spectators only
+    Follow,  // Spectators only, behind `Spectator::followed`
+}
+
+impl CameraViewMode {
+    // View mode after toggling with V. Players switch between first-person and top-down, spectators cycle through
+    // their cameras.
+    #[must_use]
+    pub const fn next(self, spectating: bool) -> Self {
+        match (self, spectating) {
+            (Self::FirstPerson, false) => Self::TopDown,
+            (_, false) => Self::FirstPerson,
+            (Self::FreeFly, true) => Self::Follow,
+            (Self::Follow, true) => Self::TopDown,
+            (_, true) => Self::FreeFly,
+        }
+    }
 }
 
 // Roof rendering toggle
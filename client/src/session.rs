@@ -0,0 +1,48 @@
+use anyhow::{Context, Result};
+use std::path::{Path, PathBuf};
+
+use crate::constants::PASSWORD_ENV_VAR;
+
+// ============================================================================
+// Session Token Cache
+// ============================================================================
+
+// File caching the session token of an account on a server
+#[must_use]
+pub fn session_token_path(data_dir: &Path, server: &str, name: &str) -> PathBuf {
+    // Keep only characters that are safe in file names on every platform
+    let file_name: String = format!("{server}_{name}")
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect();
+    data_dir.join("sessions").join(file_name + ".token")
+}
+
+// Cached session token, if any
+#[must_use]
+pub fn load_session_token(path: &Path) -> Option<String> {
+    std::fs::read_to_string(path)
+        .ok()
+        .map(|token| token.trim().to_string())
+        .filter(|token| !token.is_empty())
+}
+
+pub fn store_session_token(path: &Path, token: &str) -> Result<()> {
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir).with_context(|| format!("failed to create {}", dir.display()))?;
+    }
+    std::fs::write(path, token).with_context(|| format!("failed to write {}", path.display()))
+}
+
+// ============================================================================
+// Password Input
+// ============================================================================
+
+// Account password from `PASSWORD_ENV_VAR`, or else prompted for without echoing it. Never taken from the command
+// line, where other users can see it.
+pub fn read_password() -> Result<String> {
+    if let Ok(password) = std::env::var(PASSWORD_ENV_VAR) {
+        return Ok(password);
+    }
+    rpassword::prompt_password("Password: ").context("failed to read password")
+}
@@ -1,7 +1,7 @@
 use bevy::{gltf::GltfAssetLabel, prelude::*, scene::SceneRoot};
 
 use crate::constants::*;
-use common::{markers::ItemMarker, protocol::*};
+use common::{markers::ItemMarker, power_ups::EffectKind, protocol::*};
 
 // ============================================================================
 // Components
@@ -10,6 +10,10 @@ use common::{markers::ItemMarker, protocol::*};
 #[derive(Component)]
 pub struct ItemAnimTimer(pub f32);
 
+// Index of a wall light in `MapLayout::wall_lights`, set on both the fixture and its point light
+#[derive(Component, Clone, Copy)]
+pub struct WallLightIndex(pub u32);
+
 // ============================================================================
 // Bundles
 // ============================================================================
@@ -30,21 +34,31 @@ struct ItemBundle {
 
 // Get the color for an item type
 #[must_use]
-pub const fn item_type_color(item_type: ItemType) -> Color {
+pub fn item_type_color(item_type: ItemType) -> Color {
     match item_type {
-        ItemType::SpeedPowerUp => Color::srgb(ITEM_SPEED_COLOR[0], ITEM_SPEED_COLOR[1], ITEM_SPEED_COLOR[2]),
-        ItemType::MultiShotPowerUp => Color::srgb(
+        ItemType::PowerUp(power_up) => effect_color(power_up.def().effect),
+        ItemType::Cookie => Color::WHITE, // Cookies use textures, not colors
+    }
+}
+
+// Get the color for a power-up effect
+#[must_use]
+pub const fn effect_color(effect: EffectKind) -> Color {
+    match effect {
+        EffectKind::Speed => Color::srgb(ITEM_SPEED_COLOR[0], ITEM_SPEED_COLOR[1], ITEM_SPEED_COLOR[2]),
+        EffectKind::MultiShot => Color::srgb(
             ITEM_MULTISHOT_COLOR[0],
             ITEM_MULTISHOT_COLOR[1],
             ITEM_MULTISHOT_COLOR[2],
         ),
-        ItemType::PhasingPowerUp => Color::srgb(ITEM_PHASING_COLOR[0], ITEM_PHASING_COLOR[1], ITEM_PHASING_COLOR[2]),
-        ItemType::SentryHunterPowerUp => Color::srgb(
+        EffectKind::Phasing => Color::srgb(ITEM_PHASING_COLOR[0], ITEM_PHASING_COLOR[1], ITEM_PHASING_COLOR[2]),
+        EffectKind::SentryHunt => Color::srgb(
             ITEM_SENTRY_HUNT_COLOR[0],
             ITEM_SENTRY_HUNT_COLOR[1],
             ITEM_SENTRY_HUNT_COLOR[2],
         ),
-        ItemType::Cookie => Color::WHITE, // Cookies use textures, not colors
+        EffectKind::Shield => Color::srgb(ITEM_SHIELD_COLOR[0], ITEM_SHIELD_COLOR[1], ITEM_SHIELD_COLOR[2]),
+        EffectKind::Cloak => Color::srgb(ITEM_CLOAK_COLOR[0], ITEM_CLOAK_COLOR[1], ITEM_CLOAK_COLOR[2]),
     }
 }
 
@@ -109,7 +123,12 @@ pub fn spawn_item(
 }
 
 // Spawn a wall light from precomputed layout data (world-space position and yaw).
-pub fn spawn_wall_light_from_layout(commands: &mut Commands, asset_server: &Res<AssetServer>, light: &WallLight) {
+pub fn spawn_wall_light_from_layout(
+    commands: &mut Commands,
+    asset_server: &Res<AssetServer>,
+    light: &WallLight,
+    index: WallLightIndex,
+) {
     let light_scene: Handle<Scene> = asset_server.load(GltfAssetLabel::Scene(0).from_asset(WALL_LIGHT_MODEL));
 
     let model_yaw = Quat::from_rotation_y(light.yaw);
@@ -129,6 +148,7 @@ pub fn spawn_wall_light_from_layout(commands: &mut Commands, asset_server: &Res<
         Visibility::Visible,
         InheritedVisibility::default(),
         ViewVisibility::default(),
+        index,
     ));
 
     commands.spawn((
@@ -137,9 +157,10 @@ pub fn spawn_wall_light_from_layout(commands: &mut Commands, asset_server: &Res<
             range: WALL_LIGHT_RANGE,
             radius: WALL_LIGHT_RADIUS,
             shadows_enabled: false,
-            color: Color::srgb(1.0, 0.95, 0.85),
+            color: Color::srgb(light.state.color.r, light.state.color.g, light.state.color.b),
             ..default()
         },
         Transform::from_xyz(light_pos.x, light_pos.y, light_pos.z),
+        index,
     ));
 }
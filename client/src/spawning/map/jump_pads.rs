@@ -0,0 +1,37 @@
+use bevy::prelude::*;
+
+use crate::{constants::*, markers::*};
+use common::{constants::*, protocol::*};
+
+const JUMP_PAD_COLOR: Color = Color::srgb(0.2, 0.8, 1.0);
+
+#[derive(Bundle)]
+struct JumpPadBundle {
+    mesh: Mesh3d,
+    material: MeshMaterial3d<StandardMaterial>,
+    transform: Transform,
+    visibility: Visibility,
+    marker: JumpPadMarker,
+}
+
+// Spawn a jump pad entity based on a shared `JumpPad` config.
+pub fn spawn_jump_pad(
+    commands: &mut Commands,
+    meshes: &mut ResMut<Assets<Mesh>>,
+    materials: &mut ResMut<Assets<StandardMaterial>>,
+    pad: &JumpPad,
+) {
+    let pad_material = StandardMaterial {
+        base_color: JUMP_PAD_COLOR,
+        emissive: JUMP_PAD_COLOR.to_linear() * JUMP_PAD_EMISSIVE_LUMINANCE,
+        ..default()
+    };
+
+    commands.spawn(JumpPadBundle {
+        mesh: Mesh3d(meshes.add(Cuboid::new(JUMP_PAD_SIZE, JUMP_PAD_THICKNESS, JUMP_PAD_SIZE))),
+        material: MeshMaterial3d(materials.add(pad_material)),
+        transform: Transform::from_xyz(pad.x, JUMP_PAD_THICKNESS / 2.0, pad.z),
+        visibility: Visibility::Visible,
+        marker: JumpPadMarker,
+    });
+}
@@ -1,9 +1,11 @@
 pub mod helpers;
+pub mod jump_pads;
 pub mod ramps;
 pub mod roofs;
 pub mod walls;
 
 pub use helpers::{build_ramp_meshes, load_repeating_texture, load_repeating_texture_linear, tiled_cuboid};
+pub use jump_pads::spawn_jump_pad;
 pub use ramps::spawn_ramp;
 pub use roofs::spawn_roof;
-pub use walls::{spawn_roof_wall, spawn_wall};
+pub use walls::spawn_wall;
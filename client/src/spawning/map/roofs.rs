@@ -69,7 +69,7 @@ pub fn spawn_roof(
         material: MeshMaterial3d(materials.add(roof_material)),
         transform: Transform::from_xyz(
             center_x,
-            WALL_HEIGHT + roof.thickness / 2.0, // Position so bottom of roof sits on top of wall
+            roof.y - roof.thickness / 2.0, // Position so top of roof is at the roof level
             center_z,
         ),
         visibility: Visibility::Visible,
@@ -4,10 +4,14 @@ pub mod player;
 pub mod projectile;
 pub mod sentry;
 
-pub use item::{ItemAnimTimer, item_type_color, spawn_item, spawn_wall_light_from_layout};
+pub use item::{
+    ItemAnimTimer, WallLightIndex, effect_color, item_type_color, spawn_item, spawn_wall_light_from_layout,
+};
 pub use map::{
-    load_repeating_texture, load_repeating_texture_linear, spawn_ramp, spawn_roof, spawn_roof_wall, spawn_wall,
+    load_repeating_texture, load_repeating_texture_linear, spawn_jump_pad, spawn_ramp, spawn_roof, spawn_wall,
 };
 pub use player::{spawn_player, spawn_player_id_display};
-pub use projectile::{spawn_projectile_for_player, spawn_projectiles};
+pub use projectile::{
+    ExplosionFireball, spawn_explosion, spawn_projectile_for_player, spawn_projectiles, spawn_replicated_projectiles,
+};
 pub use sentry::spawn_sentry;
@@ -6,6 +6,7 @@ use bevy::{
     scene::SceneRoot,
 };
 
+use super::item::effect_color;
 use crate::{
     constants::*,
     markers::*,
@@ -14,7 +15,7 @@ use crate::{
         players::BumpFlashState,
     },
 };
-use common::{constants::*, markers::PlayerMarker, protocol::*};
+use common::{constants::*, markers::PlayerMarker, power_ups::EffectKind, protocol::*};
 
 // ============================================================================
 // Bundles
@@ -26,6 +27,7 @@ struct PlayerBundle {
     player_marker: PlayerMarker,
     position: Position,
     velocity: Velocity,
+    vertical_velocity: VerticalVelocity,
     face_direction: FaceDirection,
     transform: Transform,
     visibility: Visibility,
@@ -63,6 +65,7 @@ pub fn spawn_player(
                 player_marker: PlayerMarker,
                 position: *position,
                 velocity,
+                vertical_velocity: VerticalVelocity::default(),
                 face_direction: FaceDirection(face_dir),
                 transform: Transform::from_xyz(position.x, position.y + PLAYER_HEIGHT / 2.0, position.z)
                     .with_rotation(Quat::from_rotation_y(face_dir)),
@@ -109,6 +112,24 @@ pub fn spawn_player(
         .id();
     children.push(model);
 
+    // Add the shield bubble, shown while the shield power-up is active
+    let shield_color = effect_color(EffectKind::Shield).with_alpha(SHIELD_ALPHA);
+    let shield = commands
+        .spawn((
+            ShieldMarker,
+            Mesh3d(meshes.add(Sphere::new(0.5))),
+            MeshMaterial3d(materials.add(StandardMaterial {
+                base_color: shield_color,
+                emissive: LinearRgba::from(shield_color),
+                alpha_mode: AlphaMode::Blend,
+                ..default()
+            })),
+            Transform::from_scale(Vec3::from_array(SHIELD_SIZE)),
+            Visibility::Hidden,
+        ))
+        .id();
+    children.push(shield);
+
     // Create individual texture and camera for this player's ID text
     let (image_handle, text_camera) = setup_player_id_text_rendering(commands, images);
     let (_text_entity, mesh_entity) =
@@ -1,13 +1,44 @@
 use bevy::prelude::*;
 
+use crate::{
+    constants::{
+        EXPLOSION_ALPHA, EXPLOSION_DURATION, EXPLOSION_GLOW, EXPLOSION_START_SCALE, PROJECTILE_BALL_GLOW,
+        PROJECTILE_GRENADE_GLOW, PROJECTILE_RAIL_GLOW, PROJECTILE_ROCKET_GLOW, PROJECTILE_SHOTGUN_GLOW,
+    },
+    resources::ProjectileMap,
+};
 use common::{
     collision::Projectile,
     constants::*,
     markers::{PlayerMarker, ProjectileMarker},
     protocol::*,
     spawning::{ProjectileSpawnInfo, calculate_projectile_spawns},
+    weapons::WeaponId,
 };
 
+// Glow color of a weapon's projectiles
+fn weapon_glow(weapon: WeaponId) -> LinearRgba {
+    let [r, g, b] = match weapon {
+        WeaponId::Ball => PROJECTILE_BALL_GLOW,
+        WeaponId::Rail => PROJECTILE_RAIL_GLOW,
+        WeaponId::Grenade => PROJECTILE_GRENADE_GLOW,
+        WeaponId::Shotgun => PROJECTILE_SHOTGUN_GLOW,
+        WeaponId::Rocket => PROJECTILE_ROCKET_GLOW,
+    };
+    LinearRgba::rgb(r, g, b)
+}
+
+// ============================================================================
+// Components
+// ============================================================================
+
+// Fireball of an explosion, growing to the blast radius while it fades
+#[derive(Component)]
+pub struct ExplosionFireball {
+    pub timer: Timer,
+    pub radius: f32, // meters
+}
+
 // ============================================================================
 // Bundles
 // ============================================================================
@@ -18,7 +49,6 @@ struct ProjectileBundle {
     material: MeshMaterial3d<StandardMaterial>,
     transform: Transform,
     projectile: Projectile,
-    player_id: PlayerId,
     projectile_marker: ProjectileMarker,
 }
 
@@ -27,20 +57,18 @@ impl ProjectileBundle {
         meshes: &mut Assets<Mesh>,
         materials: &mut Assets<StandardMaterial>,
         position: Vec3,
-        direction_yaw: f32,
-        direction_pitch: f32,
-        shooter_id: PlayerId,
+        projectile: Projectile,
     ) -> Self {
+        let glow = weapon_glow(projectile.weapon);
         Self {
             mesh: Mesh3d(meshes.add(Sphere::new(PROJECTILE_RADIUS))),
             material: MeshMaterial3d(materials.add(StandardMaterial {
-                base_color: Color::srgb(10.0, 10.0, 0.0),
-                emissive: LinearRgba::rgb(10.0, 10.0, 0.0),
+                base_color: Color::srgb(glow.red, glow.green, glow.blue),
+                emissive: glow,
                 ..default()
             })),
             transform: Transform::from_translation(position),
-            projectile: Projectile::new(direction_yaw, direction_pitch),
-            player_id: shooter_id,
+            projectile,
             projectile_marker: ProjectileMarker,
         }
     }
@@ -50,47 +78,75 @@ impl ProjectileBundle {
 // Projectile Spawning
 // ============================================================================
 
-// Spawn projectile(s) on whether player has multi-shot power-up
-pub fn spawn_projectiles(
+// Spawn the projectile(s) of one shot of a weapon, several if the player has the multi-shot power-up. The shooter is a `PlayerId` or,
+// for turret shots, a `SentryId`. Returns the spawned projectile entities.
+pub fn spawn_projectiles<S: Component + Copy>(
     commands: &mut Commands,
     meshes: &mut ResMut<Assets<Mesh>>,
     materials: &mut ResMut<Assets<StandardMaterial>>,
     pos: &Position,
     face_dir: f32,
     face_pitch: f32,
+    weapon: WeaponId,
     has_multi_shot: bool,
     walls: &[Wall],
     ramps: &[Ramp],
     roofs: &[Roof],
-    shooter_id: PlayerId,
-) -> usize {
-    let spawns = calculate_projectile_spawns(pos, face_dir, face_pitch, has_multi_shot, walls, ramps, roofs);
+    shooter_id: S,
+) -> Vec<Entity> {
+    let spawns = calculate_projectile_spawns(pos, face_dir, face_pitch, weapon, has_multi_shot, walls, ramps, roofs);
 
-    for spawn_info in &spawns {
-        spawn_single_projectile(commands, meshes, materials, spawn_info, shooter_id);
-    }
+    spawns
+        .iter()
+        .map(|spawn_info| spawn_single_projectile(commands, meshes, materials, spawn_info, weapon, shooter_id))
+        .collect()
+}
 
-    spawns.len()
+// Spawn projectiles replicated from the server with their exact initial state and register them by ID
+pub fn spawn_replicated_projectiles<S: Component + Copy>(
+    commands: &mut Commands,
+    meshes: &mut ResMut<Assets<Mesh>>,
+    materials: &mut ResMut<Assets<StandardMaterial>>,
+    projectile_map: &mut ProjectileMap,
+    projectiles: &[ProjectileSpawn],
+    weapon: WeaponId,
+    shooter_id: S,
+) {
+    for spawn in projectiles {
+        let entity = commands
+            .spawn((
+                ProjectileBundle::new(
+                    meshes,
+                    materials,
+                    spawn.position.into(),
+                    Projectile::with_velocity(spawn.velocity.into(), weapon),
+                ),
+                spawn.id,
+                shooter_id,
+            ))
+            .id();
+        projectile_map.0.insert(spawn.id, entity);
+    }
 }
 
 // Internal helper to spawn a single projectile
-fn spawn_single_projectile(
+fn spawn_single_projectile<S: Component>(
     commands: &mut Commands,
     meshes: &mut ResMut<Assets<Mesh>>,
     materials: &mut ResMut<Assets<StandardMaterial>>,
     spawn_info: &ProjectileSpawnInfo,
-    shooter_id: PlayerId,
-) {
+    weapon: WeaponId,
+    shooter_id: S,
+) -> Entity {
     let spawn_pos = Vec3::new(spawn_info.position.x, spawn_info.position.y, spawn_info.position.z);
+    let projectile = Projectile::new(spawn_info.direction_yaw, spawn_info.direction_pitch, weapon);
 
-    commands.spawn(ProjectileBundle::new(
-        meshes,
-        materials,
-        spawn_pos,
-        spawn_info.direction_yaw,
-        spawn_info.direction_pitch,
-        shooter_id,
-    ));
+    commands
+        .spawn((
+            ProjectileBundle::new(meshes, materials, spawn_pos, projectile),
+            shooter_id,
+        ))
+        .id()
 }
 
 // Spawn a projectile for a player (when receiving shot from server).
@@ -100,6 +156,7 @@ pub fn spawn_projectile_for_player(
     materials: &mut ResMut<Assets<StandardMaterial>>,
     player_query: &Query<(&PlayerId, &Position, &FaceDirection), With<PlayerMarker>>,
     entity: Entity,
+    weapon: WeaponId,
     has_multi_shot: bool,
     walls: &[Wall],
     ramps: &[Ramp],
@@ -114,6 +171,7 @@ pub fn spawn_projectile_for_player(
             pos,
             face_dir.0,
             0.0,
+            weapon,
             has_multi_shot,
             walls,
             ramps,
@@ -122,3 +180,33 @@ pub fn spawn_projectile_for_player(
         );
     }
 }
+
+// ============================================================================
+// Explosion Spawning
+// ============================================================================
+
+// Spawn the fireball of an explosion (a unit sphere scaled up to the blast radius by the animation system).
+pub fn spawn_explosion(
+    commands: &mut Commands,
+    meshes: &mut ResMut<Assets<Mesh>>,
+    materials: &mut ResMut<Assets<StandardMaterial>>,
+    position: &Position,
+    radius: f32,
+) {
+    let [r, g, b] = EXPLOSION_GLOW;
+    commands.spawn((
+        Mesh3d(meshes.add(Sphere::new(1.0))),
+        MeshMaterial3d(materials.add(StandardMaterial {
+            base_color: Color::srgba(r, g, b, EXPLOSION_ALPHA),
+            emissive: LinearRgba::rgb(r, g, b),
+            alpha_mode: AlphaMode::Blend,
+            unlit: true,
+            ..default()
+        })),
+        Transform::from_translation((*position).into()).with_scale(Vec3::splat(radius * EXPLOSION_START_SCALE)),
+        ExplosionFireball {
+            timer: Timer::from_seconds(EXPLOSION_DURATION, TimerMode::Once),
+            radius,
+        },
+    ));
+}
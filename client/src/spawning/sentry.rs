@@ -25,6 +25,16 @@ struct SentryBundle {
 // Sentry Spawning
 // ============================================================================
 
+// Model scale multiplier and base ring color of a sentry archetype
+const fn archetype_appearance(archetype: SentryArchetype) -> (f32, Color) {
+    match archetype {
+        SentryArchetype::Standard => (1.0, Color::srgb(0.9, 0.9, 0.9)),
+        SentryArchetype::Heavy => (1.25, Color::srgb(1.0, 0.3, 0.1)),
+        SentryArchetype::Scout => (0.8, Color::srgb(0.2, 1.0, 0.3)),
+        SentryArchetype::Turret => (1.0, Color::srgb(0.3, 0.5, 1.0)),
+    }
+}
+
 // Spawn a sentry cube
 pub fn spawn_sentry(
     commands: &mut Commands,
@@ -33,9 +43,11 @@ pub fn spawn_sentry(
     asset_server: &Res<AssetServer>,
     graphs: &mut ResMut<Assets<AnimationGraph>>,
     sentry_id: SentryId,
-    position: &Position,
-    velocity: &Velocity,
+    sentry: &Sentry,
 ) -> Entity {
+    let (position, velocity) = (&sentry.pos, &sentry.vel);
+    let (model_scale, ring_color) = archetype_appearance(sentry.archetype);
+
     // Create animation graph with walk animation
     let mut graph = AnimationGraph::new();
     let walk_clip = asset_server.load(GltfAssetLabel::Animation(SENTRY_WALK_ANIMATION_INDEX).from_asset(SENTRY_MODEL));
@@ -88,12 +100,29 @@ pub fn spawn_sentry(
         children.push(debug_box);
     }
 
+    // Add a glowing ring at the feet showing the sentry archetype
+    let ring = commands
+        .spawn((
+            Mesh3d(meshes.add(Cylinder::new(
+                SENTRY_WIDTH * SENTRY_RING_RADIUS_RATIO,
+                SENTRY_RING_THICKNESS,
+            ))),
+            MeshMaterial3d(materials.add(StandardMaterial {
+                base_color: ring_color,
+                emissive: ring_color.to_linear() * SENTRY_RING_EMISSIVE_LUMINANCE,
+                ..default()
+            })),
+            Transform::from_xyz(0.0, SENTRY_RING_THICKNESS / 2.0 - SENTRY_HEIGHT / 2.0, 0.0),
+        ))
+        .id();
+    children.push(ring);
+
     // Add the GLB sentry model with animation observer
     let base_y = SENTRY_MODEL_HEIGHT_OFFSET - SENTRY_HEIGHT / 2.0;
     let model = commands
         .spawn((
             SceneRoot(asset_server.load(SENTRY_MODEL)),
-            Transform::from_scale(Vec3::splat(SENTRY_MODEL_SCALE))
+            Transform::from_scale(Vec3::splat(SENTRY_MODEL_SCALE * model_scale))
                 .with_rotation(Quat::from_rotation_x(std::f32::consts::PI))
                 .with_translation(Vec3::new(0.0, base_y, SENTRY_MODEL_DEPTH_OFFSET)),
             animation_to_play,
@@ -51,3 +51,21 @@ pub fn setup_cameras_system(mut commands: Commands) {
             .looking_at(Vec3::new(0.0, 0.0, 1.0), Vec3::Y), // Looking backwards (positive Z)
     ));
 }
+
+// ============================================================================
+// Camera Helpers
+// ============================================================================
+
+// Point the main camera at the arena from above for the top-down view, moving it into place when the view was
+// just switched to.
+pub fn apply_topdown_camera(transform: &mut Transform, projection: &mut Projection, switched: bool) {
+    if switched {
+        transform.translation = Vec3::new(0.0, TOPDOWN_CAMERA_HEIGHT, TOPDOWN_CAMERA_Z_OFFSET);
+    }
+    transform.look_at(Vec3::new(TOPDOWN_LOOKAT_X, TOPDOWN_LOOKAT_Y, TOPDOWN_LOOKAT_Z), Vec3::Y);
+
+    // Set top-down FOV
+    if let Projection::Perspective(persp) = projection {
+        persp.fov = TOPDOWN_CAMERA_FOV_DEGREES.to_radians();
+    }
+}
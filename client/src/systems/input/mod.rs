@@ -2,9 +2,9 @@ mod movement;
 mod shooting;
 mod toggles;
 
-pub use movement::input_movement_system;
-pub use shooting::input_shooting_system;
+pub use movement::{input_jump_system, input_movement_system};
+pub use shooting::{input_shooting_system, input_weapon_switch_system};
 pub use toggles::{
     input_camera_view_toggle_system, input_cursor_toggle_system, input_fullscreen_toggle_system,
-    input_roof_toggle_system,
+    input_leaderboard_toggle_system, input_roof_toggle_system,
 };
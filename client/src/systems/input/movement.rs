@@ -12,11 +12,45 @@ use crate::{
     resources::{CameraViewMode, ClientToServerChannel, InputSettings, LocalPlayerInfo, MyPlayerId, PlayerMap},
 };
 use common::{
-    constants::{ALWAYS_SPEED, POWER_UP_SPEED_MULTIPLIER},
+    collision::player_is_grounded,
+    constants::{ALWAYS_SPEED, PLAYER_JUMP_SPEED, POWER_UP_SPEED_MULTIPLIER},
+    power_ups::EffectKind,
     protocol::*,
 };
 
-const MAX_PITCH: f32 = std::f32::consts::FRAC_PI_2 - 0.05;
+// Handle jumping with the space bar (predicted locally, confirmed by the server)
+pub fn input_jump_system(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    cursor_options: Single<&CursorOptions>,
+    to_server: Res<ClientToServerChannel>,
+    my_player_id: Option<Res<MyPlayerId>>,
+    players: Res<PlayerMap>,
+    map_layout: Option<Res<MapLayout>>,
+    mut local_player_query: Query<(&Position, &mut VerticalVelocity), With<LocalPlayerMarker>>,
+) {
+    if cursor_options.grab_mode == CursorGrabMode::None || !keyboard.just_pressed(KeyCode::Space) {
+        return;
+    }
+
+    let Some(map_layout) = map_layout else {
+        return;
+    };
+
+    // Stunned players cannot jump
+    if let Some(my_id) = my_player_id
+        && let Some(player_info) = players.0.get(&my_id.0)
+        && player_info.stunned
+    {
+        return;
+    }
+
+    for (pos, mut vertical_velocity) in &mut local_player_query {
+        if player_is_grounded(&map_layout, pos, vertical_velocity.0) {
+            vertical_velocity.0 = PLAYER_JUMP_SPEED;
+            let _ = to_server.send(ClientToServer::Send(ClientMessage::Jump(CJump)));
+        }
+    }
+}
 
 // Handle WASD movement and mouse rotation
 pub fn input_movement_system(
@@ -93,7 +127,7 @@ fn handle_unlocked_cursor(
         for (mut velocity, _) in local_player_query.iter_mut() {
             let has_speed_powerup = my_player_id
                 .and_then(|my_id| players.0.get(&my_id.0))
-                .is_some_and(|info| ALWAYS_SPEED || info.speed_power_up);
+                .is_some_and(|info| ALWAYS_SPEED || info.effects.contains(EffectKind::Speed));
             let multiplier = if has_speed_powerup {
                 POWER_UP_SPEED_MULTIPLIER
             } else {
@@ -210,7 +244,7 @@ fn update_player_velocity_and_face(
     for (mut velocity, mut face_direction) in local_player_query.iter_mut() {
         let has_speed_powerup = my_player_id
             .and_then(|my_id| players.0.get(&my_id.0))
-            .is_some_and(|info| ALWAYS_SPEED || info.speed_power_up);
+            .is_some_and(|info| ALWAYS_SPEED || info.effects.contains(EffectKind::Speed));
         let multiplier = if has_speed_powerup {
             POWER_UP_SPEED_MULTIPLIER
         } else {
@@ -1,5 +1,5 @@
 use bevy::{
-    input::mouse::MouseButton,
+    input::mouse::{AccumulatedMouseScroll, MouseButton},
     prelude::*,
     window::{CursorGrabMode, CursorOptions},
 };
@@ -7,14 +7,61 @@ use bevy::{
 use crate::{
     markers::{LocalPlayerMarker, MainCameraMarker},
     net::ClientToServer,
-    resources::{CameraViewMode, ClientToServerChannel, LocalPlayerInfo, MyPlayerId, PlayerMap},
+    resources::{CameraViewMode, ClientToServerChannel, LocalPlayerInfo, MyPlayerId, PlayerMap, PredictedShotMap},
     spawning::spawn_projectiles,
 };
 use common::{
     constants::{ALWAYS_MULTI_SHOT, PROJECTILE_COOLDOWN_TIME},
+    power_ups::EffectKind,
     protocol::*,
+    weapons::WeaponId,
 };
 
+// Keys selecting a weapon directly, in the order of `WeaponId::ALL`
+const WEAPON_KEYS: [KeyCode; WeaponId::ALL.len()] = [
+    KeyCode::Digit1,
+    KeyCode::Digit2,
+    KeyCode::Digit3,
+    KeyCode::Digit4,
+    KeyCode::Digit5,
+];
+
+// ============================================================================
+// Input Weapon Switch System
+// ============================================================================
+
+// Select a weapon with the number keys or cycle through the weapons with the mouse wheel
+pub fn input_weapon_switch_system(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    mouse_scroll: Res<AccumulatedMouseScroll>,
+    cursor_options: Single<&CursorOptions>,
+    mut local_player_info: ResMut<LocalPlayerInfo>,
+) {
+    if cursor_options.grab_mode == CursorGrabMode::None {
+        return;
+    }
+
+    let selected = WEAPON_KEYS
+        .iter()
+        .zip(WeaponId::ALL)
+        .find_map(|(key, weapon)| keyboard.just_pressed(*key).then_some(weapon));
+
+    let weapon = if let Some(weapon) = selected {
+        weapon
+    } else if mouse_scroll.delta.y > 0.0 {
+        local_player_info.weapon.cycle(-1)
+    } else if mouse_scroll.delta.y < 0.0 {
+        local_player_info.weapon.cycle(1)
+    } else {
+        return;
+    };
+
+    if weapon != local_player_info.weapon {
+        debug!("switched to {}", weapon.def().name);
+        local_player_info.weapon = weapon;
+    }
+}
+
 // ============================================================================
 // Input Shooting System
 // ============================================================================
@@ -35,6 +82,7 @@ pub fn input_shooting_system(
     view_mode: Res<CameraViewMode>,
     time: Res<Time>,
     mut local_player_info: ResMut<LocalPlayerInfo>,
+    mut predicted_shots: ResMut<PredictedShotMap>,
 ) {
     // Only allow shooting when cursor is locked
     let cursor_locked = cursor_options.grab_mode != CursorGrabMode::None;
@@ -55,7 +103,10 @@ pub fn input_shooting_system(
         };
 
         // Client-side cooldown guard (server still authoritative)
-        if now - local_player_info.last_shot_time < PROJECTILE_COOLDOWN_TIME {
+        let weapon = local_player_info.weapon;
+        if now - local_player_info.last_shot_time < PROJECTILE_COOLDOWN_TIME
+            || now - local_player_info.weapon_shot_times[weapon as usize] < weapon.def().fire_interval
+        {
             commands.spawn((
                 AudioPlayer::new(asset_server.load("sounds/player_dry_click.ogg")),
                 PlaybackSettings::DESPAWN,
@@ -64,11 +115,16 @@ pub fn input_shooting_system(
         }
 
         local_player_info.last_shot_time = now;
+        local_player_info.weapon_shot_times[weapon as usize] = now;
+        let seq = local_player_info.next_shot_seq;
+        local_player_info.next_shot_seq = seq.wrapping_add(1);
 
         // Send shot message with current face direction to server
         let shot_msg = ClientMessage::Shot(CShot {
             face_dir: face_dir.0,
             face_pitch: pitch,
+            weapon,
+            seq,
         });
         let _ = to_server.send(ClientToServer::Send(shot_msg));
 
@@ -77,32 +133,35 @@ pub fn input_shooting_system(
             || my_player_id
                 .as_ref()
                 .and_then(|id| players.0.get(&id.0))
-                .is_some_and(|info| info.multi_shot_power_up);
+                .is_some_and(|info| info.effects.contains(EffectKind::MultiShot));
 
         if let Some(my_id) = my_player_id.as_ref()
             && let Some(map_layout) = map_layout.as_ref()
         {
-            if spawn_projectiles(
+            // Predict the shot's projectiles until the server's `SShot` confirms them
+            let predicted = spawn_projectiles(
                 &mut commands,
                 &mut meshes,
                 &mut materials,
                 pos,
                 face_dir.0,
                 pitch,
+                weapon,
                 has_multi_shot,
-                map_layout.lower_walls.as_slice(),
+                &map_layout.visible_walls().copied().collect::<Vec<_>>(),
                 map_layout.ramps.as_slice(),
                 map_layout.roofs.as_slice(),
                 my_id.0,
-            ) > 0
-            {
+            );
+            if predicted.is_empty() {
                 commands.spawn((
-                    AudioPlayer::new(asset_server.load("sounds/player_fires.ogg")),
+                    AudioPlayer::new(asset_server.load("sounds/player_dry_click.ogg")),
                     PlaybackSettings::DESPAWN,
                 ));
             } else {
+                predicted_shots.0.insert(seq, predicted);
                 commands.spawn((
-                    AudioPlayer::new(asset_server.load("sounds/player_dry_click.ogg")),
+                    AudioPlayer::new(asset_server.load("sounds/player_fires.ogg")),
                     PlaybackSettings::DESPAWN,
                 ));
             }
@@ -4,19 +4,24 @@ use bevy::{
     window::{CursorOptions, MonitorSelection, WindowMode},
 };
 
-use crate::resources::{CameraViewMode, RoofRenderingEnabled};
+use crate::{
+    net::ClientToServer,
+    resources::{CameraViewMode, ClientToServerChannel, Leaderboard, RoofRenderingEnabled, Spectator},
+};
+use common::protocol::{CLeaderboard, ClientMessage};
 
 // ============================================================================
 // Input Toggle Systems
 // ============================================================================
 
 // Toggle camera view mode with V key
-pub fn input_camera_view_toggle_system(keyboard: Res<ButtonInput<KeyCode>>, mut view_mode: ResMut<CameraViewMode>) {
+pub fn input_camera_view_toggle_system(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    spectator: Option<Res<Spectator>>,
+    mut view_mode: ResMut<CameraViewMode>,
+) {
     if keyboard.just_pressed(KeyCode::KeyV) {
-        *view_mode = match *view_mode {
-            CameraViewMode::FirstPerson => CameraViewMode::TopDown,
-            CameraViewMode::TopDown => CameraViewMode::FirstPerson,
-        };
+        *view_mode = view_mode.next(spectator.is_some());
     }
 }
 
@@ -27,6 +32,20 @@ pub fn input_roof_toggle_system(keyboard: Res<ButtonInput<KeyCode>>, mut roof_en
     }
 }
 
+// Toggle the all-time leaderboard with L key, fetching the latest standings whenever it opens
+pub fn input_leaderboard_toggle_system(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    to_server: Res<ClientToServerChannel>,
+    mut leaderboard: ResMut<Leaderboard>,
+) {
+    if keyboard.just_pressed(KeyCode::KeyL) {
+        leaderboard.visible = !leaderboard.visible;
+        if leaderboard.visible {
+            let _ = to_server.send(ClientToServer::Send(ClientMessage::Leaderboard(CLeaderboard {})));
+        }
+    }
+}
+
 // Toggle fullscreen with Cmd/Ctrl+F or F11
 pub fn input_fullscreen_toggle_system(keyboard: Res<ButtonInput<KeyCode>>, mut window: Single<&mut Window>) {
     let cmd_held = keyboard.pressed(KeyCode::SuperLeft) || keyboard.pressed(KeyCode::SuperRight);
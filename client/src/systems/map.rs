@@ -5,13 +5,13 @@ use crate::{
     markers::*,
     resources::{CameraViewMode, DebugColors, RoofRenderingEnabled},
     spawning::{
-        load_repeating_texture, load_repeating_texture_linear, spawn_ramp, spawn_roof, spawn_roof_wall, spawn_wall,
-        spawn_wall_light_from_layout,
+        WallLightIndex, load_repeating_texture, load_repeating_texture_linear, spawn_jump_pad, spawn_ramp, spawn_roof,
+        spawn_wall, spawn_wall_light_from_layout,
     },
 };
 use common::{
     constants::{FIELD_DEPTH, FIELD_WIDTH},
-    protocol::MapLayout,
+    protocol::{LightColor, LightMode, MapLayout, WallId},
 };
 
 // ============================================================================
@@ -97,15 +97,16 @@ pub fn map_spawn_walls_system(
     }
 
     info!(
-        "spawning {} wall segments, {} roofs, {} ramps, {} roof walls",
+        "spawning {} wall segments, {} upper wall segments, {} roofs, {} ramps, {} jump pads",
         map_layout.lower_walls.len(),
+        map_layout.upper_walls.len(),
         map_layout.roofs.len(),
         map_layout.ramps.len(),
-        map_layout.roof_walls.len()
+        map_layout.jump_pads.len()
     );
 
-    for wall in &map_layout.lower_walls {
-        spawn_wall(
+    for wall in map_layout.visible_walls() {
+        let entity = spawn_wall(
             &mut commands,
             &mut meshes,
             &mut materials,
@@ -113,10 +114,15 @@ pub fn map_spawn_walls_system(
             wall,
             debug_colors.0,
         );
+
+        // Tag destructible walls so they can be despawned when destroyed
+        if let Some(wall_id) = map_layout.destructible_wall_id(wall) {
+            commands.entity(entity).insert(wall_id);
+        }
     }
 
-    for light in &map_layout.wall_lights {
-        spawn_wall_light_from_layout(&mut commands, &asset_server, light);
+    for (index, light) in map_layout.wall_lights.iter().enumerate() {
+        spawn_wall_light_from_layout(&mut commands, &asset_server, light, WallLightIndex(index as u32));
     }
 
     for roof in &map_layout.roofs {
@@ -134,13 +140,38 @@ pub fn map_spawn_walls_system(
         spawn_ramp(&mut commands, &mut meshes, &mut materials, &asset_server, ramp);
     }
 
-    for roof_wall in &map_layout.roof_walls {
-        spawn_roof_wall(&mut commands, &mut meshes, &mut materials, roof_wall, debug_colors.0);
+    for pad in &map_layout.jump_pads {
+        spawn_jump_pad(&mut commands, &mut meshes, &mut materials, pad);
     }
 
     *spawned = true;
 }
 
+// ============================================================================
+// Destroyed Wall Despawn System
+// ============================================================================
+
+// System to despawn destructible wall meshes that are no longer part of the map layout
+pub fn map_despawn_destroyed_walls_system(
+    mut commands: Commands,
+    map_layout: Option<Res<MapLayout>>,
+    wall_query: Query<(Entity, &WallId)>,
+) {
+    let Some(map_layout) = map_layout else {
+        return;
+    };
+
+    if !map_layout.is_changed() {
+        return;
+    }
+
+    for (entity, wall_id) in &wall_query {
+        if !map_layout.destructible_walls.iter().any(|dw| dw.id == *wall_id) {
+            commands.entity(entity).despawn();
+        }
+    }
+}
+
 // ============================================================================
 // Wall Opacity System
 // ============================================================================
@@ -157,8 +188,8 @@ pub fn map_toggle_wall_opacity_system(
     }
 
     match *view_mode {
-        CameraViewMode::FirstPerson => {
-            // Walls and roofs fully opaque in first-person
+        CameraViewMode::FirstPerson | CameraViewMode::FreeFly | CameraViewMode::Follow => {
+            // Walls and roofs fully opaque in first-person and the spectator cameras close to the ground
             for material_handle in wall_query.iter().chain(roof_query.iter()) {
                 if let Some(material) = materials.get_mut(&material_handle.0) {
                     material.base_color.set_alpha(1.0);
@@ -221,31 +252,127 @@ pub fn map_toggle_roof_visibility_system(
 // Wall Light Emissive System
 // ============================================================================
 
-// System to make wall light glass materials emissive after they load
-#[allow(clippy::implicit_hasher)]
+// Glass materials of a wall light fixture, cloned per fixture so lights can change independently
+#[derive(Component)]
+pub struct WallLightGlass {
+    materials: Vec<Handle<StandardMaterial>>,
+    applied: Option<(f32, LightColor)>, // Last applied intensity and color
+}
+
+// System to make wall light glass materials emissive after they load, and to apply the light
+// states driven by the server to the fixtures and their point lights
 pub fn map_make_wall_lights_emissive_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    map_layout: Option<Res<MapLayout>>,
     mut materials: ResMut<Assets<StandardMaterial>>,
-    mut processed: Local<std::collections::HashSet<AssetId<StandardMaterial>>>,
+    children: Query<&Children>,
+    mut mesh_materials: Query<&mut MeshMaterial3d<StandardMaterial>>,
+    mut fixtures: Query<(Entity, &WallLightIndex, Option<&mut WallLightGlass>), With<SceneRoot>>,
+    mut point_lights: Query<(&WallLightIndex, &mut PointLight)>,
 ) {
-    // Check all materials for ones that look like wall light glass
-    for (id, material) in materials.iter_mut() {
-        // Skip if already processed
-        if processed.contains(&id) {
+    let Some(map_layout) = map_layout else {
+        return;
+    };
+    let elapsed = time.elapsed_secs();
+
+    for (entity, index, glass) in &mut fixtures {
+        let Some(light) = map_layout.wall_lights.get(index.0 as usize) else {
+            continue;
+        };
+
+        // Give the fixture its own glass materials once its scene has spawned
+        let Some(mut glass) = glass else {
+            if let Some(glass) = clone_glass_materials(entity, &children, &mut mesh_materials, &mut materials) {
+                commands.entity(entity).insert(glass);
+            }
+            continue;
+        };
+
+        let intensity = light_intensity(light.state.mode, index.0, elapsed);
+        if glass.applied == Some((intensity, light.state.color)) {
+            continue;
+        }
+
+        let color = light.state.color;
+        for handle in &glass.materials {
+            if let Some(material) = materials.get_mut(handle) {
+                let luminance = WALL_LIGHT_EMISSIVE_LUMINANCE * intensity;
+                material.emissive = LinearRgba::rgb(color.r * luminance, color.g * luminance, color.b * luminance);
+                material.base_color = Color::srgba(color.r, color.g, color.b, material.base_color.alpha());
+            }
+        }
+        glass.applied = Some((intensity, color));
+    }
+
+    for (index, mut point_light) in &mut point_lights {
+        let Some(light) = map_layout.wall_lights.get(index.0 as usize) else {
+            continue;
+        };
+
+        let intensity = WALL_LIGHT_BRIGHTNESS * light_intensity(light.state.mode, index.0, elapsed);
+        let color = Color::srgb(light.state.color.r, light.state.color.g, light.state.color.b);
+        if (point_light.intensity - intensity).abs() > f32::EPSILON || point_light.color != color {
+            point_light.intensity = intensity;
+            point_light.color = color;
+        }
+    }
+}
+
+// Replace the glass materials below a fixture scene with per-fixture copies.
+// Returns `None` while the scene has not spawned yet.
+fn clone_glass_materials(
+    entity: Entity,
+    children: &Query<&Children>,
+    mesh_materials: &mut Query<&mut MeshMaterial3d<StandardMaterial>>,
+    materials: &mut Assets<StandardMaterial>,
+) -> Option<WallLightGlass> {
+    let mut cloned: Vec<(AssetId<StandardMaterial>, Handle<StandardMaterial>)> = Vec::new();
+
+    for descendant in children.iter_descendants(entity) {
+        let Ok(mut mesh_material) = mesh_materials.get_mut(descendant) else {
+            continue;
+        };
+        let id = mesh_material.0.id();
+
+        if let Some((_, handle)) = cloned.iter().find(|(original, _)| *original == id) {
+            mesh_material.0 = handle.clone();
             continue;
         }
 
+        let Some(material) = materials.get(id) else {
+            continue;
+        };
+
         // Check if this material has properties suggesting it's glass
         // (typically has some transparency or specific naming)
         if material.alpha_mode != AlphaMode::Opaque || material.base_color.alpha() < 1.0 {
-            // Make it emissive using configurable fixture settings
-            let warm_tint = (1.0, 0.95, 0.85);
-            material.emissive = LinearRgba::rgb(
-                warm_tint.0 * WALL_LIGHT_EMISSIVE_LUMINANCE,
-                warm_tint.1 * WALL_LIGHT_EMISSIVE_LUMINANCE,
-                warm_tint.2 * WALL_LIGHT_EMISSIVE_LUMINANCE,
-            );
-            material.base_color = Color::srgba(warm_tint.0, warm_tint.1, warm_tint.2, material.base_color.alpha());
-            processed.insert(id);
+            let handle = materials.add(material.clone());
+            mesh_material.0 = handle.clone();
+            cloned.push((id, handle));
+        }
+    }
+
+    (!cloned.is_empty()).then(|| WallLightGlass {
+        materials: cloned.into_iter().map(|(_, handle)| handle).collect(),
+        applied: None,
+    })
+}
+
+// Brightness of a wall light (0.0 = dark, 1.0 = fully lit). Flickering lights are
+// desynchronized by their index.
+#[must_use]
+fn light_intensity(mode: LightMode, index: u32, elapsed: f32) -> f32 {
+    match mode {
+        LightMode::On => 1.0,
+        LightMode::Off => 0.0,
+        LightMode::Flicker => {
+            let phase = (index as f32).mul_add(WALL_LIGHT_FLICKER_PHASE_OFFSET, elapsed * WALL_LIGHT_FLICKER_RATE);
+            if phase.sin() + (phase * 2.3).sin() > 0.5 {
+                1.0
+            } else {
+                WALL_LIGHT_FLICKER_DIM
+            }
         }
     }
 }
@@ -8,18 +8,19 @@ pub mod players;
 pub mod projectiles;
 pub mod sentries;
 pub mod skybox;
+pub mod spectator;
 pub mod ui;
 
 pub use animations::{AnimationToPlay, players_animation_system, sentries_animation_system};
 pub use cameras::setup_cameras_system;
 pub use input::{
     input_camera_view_toggle_system, input_cursor_toggle_system, input_fullscreen_toggle_system,
-    input_movement_system, input_roof_toggle_system, input_shooting_system,
+    input_jump_system, input_movement_system, input_roof_toggle_system, input_shooting_system,
 };
 pub use items::items_animation_system;
 pub use map::{
-    map_make_wall_lights_emissive_system, map_spawn_walls_system, map_toggle_roof_visibility_system,
-    map_toggle_wall_opacity_system, setup_world_geometry_system,
+    map_despawn_destroyed_walls_system, map_make_wall_lights_emissive_system, map_spawn_walls_system,
+    map_toggle_roof_visibility_system, map_toggle_wall_opacity_system, setup_world_geometry_system,
 };
 pub use network::{AssetManagers, ServerReconciliation, network_echo_system, network_server_message_system};
 pub use players::{
@@ -34,6 +35,7 @@ pub use skybox::{
     SkyboxCrossImage, SkyboxCubemap, setup_skybox_from_cross, skybox_convert_cross_to_cubemap_system,
     skybox_update_camera_system,
 };
+pub use spectator::{spectator_camera_sync_system, spectator_follow_cycle_system, spectator_free_fly_system};
 pub use ui::{
     setup_ui_system, ui_fps_system, ui_player_list_system, ui_rtt_system, ui_stunned_blink_system,
     ui_toggle_crosshair_system,
@@ -1,4 +1,9 @@
 use bevy::{ecs::system::SystemParam, prelude::*};
+
+use crate::resources::{
+    ItemMap, LastUpdateSeq, Leaderboard, MatchSummary, PlayerMap, PredictedShotMap, ProjectileMap, Reconnecting,
+    RoundTripTime, Scoreboard, SentryMap, ServerClock, SessionTokenFile, Spectator, SpectatorList,
+};
 use common::protocol::{Position, Velocity};
 
 // ============================================================================
@@ -27,3 +32,38 @@ pub struct AssetManagers<'w> {
     pub images: ResMut<'w, Assets<Image>>,
     pub graphs: ResMut<'w, Assets<AnimationGraph>>,
 }
+
+// Entity maps, grouped for the same reason.
+#[derive(SystemParam)]
+pub struct EntityMaps<'w> {
+    pub players: ResMut<'w, PlayerMap>,
+    pub items: ResMut<'w, ItemMap>,
+    pub sentries: ResMut<'w, SentryMap>,
+    pub projectiles: ResMut<'w, ProjectileMap>,
+    pub predicted_shots: ResMut<'w, PredictedShotMap>,
+}
+
+// Match statistics, grouped for the same reason.
+#[derive(SystemParam)]
+pub struct MatchResources<'w> {
+    pub scoreboard: ResMut<'w, Scoreboard>,
+    pub match_summary: ResMut<'w, MatchSummary>,
+    pub leaderboard: ResMut<'w, Leaderboard>,
+}
+
+// Login session state and spectators, grouped for the same reason.
+#[derive(SystemParam)]
+pub struct SessionState<'w> {
+    pub session_token_file: Res<'w, SessionTokenFile>,
+    pub reconnecting: ResMut<'w, Reconnecting>,
+    pub spectator: Option<Res<'w, Spectator>>,
+    pub spectator_list: ResMut<'w, SpectatorList>,
+}
+
+// Round-trip time, update order and the server's clock, grouped for the same reason.
+#[derive(SystemParam)]
+pub struct ServerSync<'w> {
+    pub rtt: ResMut<'w, RoundTripTime>,
+    pub last_update_seq: ResMut<'w, LastUpdateSeq>,
+    pub server_clock: ResMut<'w, ServerClock>,
+}
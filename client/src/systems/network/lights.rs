@@ -0,0 +1,23 @@
+use bevy::prelude::*;
+
+use common::protocol::*;
+
+// ============================================================================
+// Light Message Handlers
+// ============================================================================
+
+// Handle wall light state changes - store them in the map layout. The lights are updated by
+// `map_make_wall_lights_emissive_system`.
+pub fn handle_light_state_message(map_layout: &mut Option<ResMut<MapLayout>>, msg: SLightState) {
+    let Some(map_layout) = map_layout.as_mut() else {
+        return;
+    };
+
+    for (index, state) in msg.lights {
+        if let Some(light) = map_layout.wall_lights.get_mut(index as usize) {
+            light.state = state;
+        } else {
+            warn!("light state for unknown wall light {index}");
+        }
+    }
+}
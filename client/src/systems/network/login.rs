@@ -1,30 +1,89 @@
 use bevy::prelude::*;
 
+use super::players::effect_expiries;
 use crate::{
-    resources::{MyPlayerId, PlayerInfo, PlayerMap},
+    resources::{
+        MyPlayerId, PlayerInfo, PlayerMap, Reconnecting, RoundTripTime, ServerClock, SessionTokenFile, SpectatorList,
+    },
+    session::store_session_token,
     spawning::spawn_player,
 };
-use common::{constants::POWER_UP_SPEED_MULTIPLIER, protocol::*};
+use common::{constants::POWER_UP_SPEED_MULTIPLIER, power_ups::EffectKind, protocol::*};
 
 // ============================================================================
 // Login/Logout Handlers
 // ============================================================================
 
-// Handle Init message when not yet logged in - stores player ID and map layout.
-pub fn handle_init_message(msg: ServerMessage, commands: &mut Commands) {
-    if let ServerMessage::Init(init_msg) = msg {
-        debug!("received Init: my_id={:?}", init_msg.id);
+// Handle Init message when not yet logged in - stores player ID and map layout and syncs the server clock, or
+// exits if the login was rejected. The spectator list is only sent on changes, so it is kept even when it arrives
+// in the same frame.
+pub fn handle_init_message(
+    msg: ServerMessage,
+    commands: &mut Commands,
+    session_token_file: &SessionTokenFile,
+    spectator_list: &mut ResMut<SpectatorList>,
+    server_clock: &mut ResMut<ServerClock>,
+    rtt: &RoundTripTime,
+    now: f32,
+    exit: &mut MessageWriter<AppExit>,
+) {
+    match msg {
+        ServerMessage::Init(init_msg) => {
+            debug!("received Init: my_id={:?}", init_msg.id);
+
+            // Cache the session token to log in to the account without the password next time
+            if let (Some(path), Some(token)) = (&session_token_file.0, &init_msg.session_token)
+                && let Err(e) = store_session_token(path, token)
+            {
+                warn!("failed to cache session token: {e:#}");
+            }
 
-        // Store player ID as resource
-        commands.insert_resource(MyPlayerId(init_msg.id));
+            // Store player ID as resource
+            commands.insert_resource(MyPlayerId(init_msg.id));
 
-        // Store grid configuration
-        commands.insert_resource(init_msg.map_layout);
+            // Store grid configuration
+            commands.insert_resource(*init_msg.map_layout);
+
+            // Effect timers are server ticks, sync before the first ones arrive
+            server_clock.sync(init_msg.tick, now, rtt.rtt);
+
+            // Note: We don't spawn anything here. The first SUpdate will contain
+            // all players including ourselves and will trigger spawning via the
+            // Update message handler.
+        }
+        ServerMessage::LoginRejected(rejected_msg) => {
+            error!("login rejected: {}", rejected_msg.reason);
+            exit.write(AppExit::error());
+        }
+        ServerMessage::Spectators(spectators_msg) => handle_spectators_message(spectator_list, spectators_msg),
+        _ => {}
+    }
+}
 
-        // Note: We don't spawn anything here. The first SUpdate will contain
-        // all players including ourselves and will trigger spawning via the
-        // Update message handler.
+// Handle Init after reconnecting - the session was resumed under the same player ID. Spectators aren't held by the
+// server and log in again under a new ID. The map layout may have lost destructible walls meanwhile.
+pub fn handle_resume_message(
+    commands: &mut Commands,
+    my_player_id: PlayerId,
+    spectating: bool,
+    reconnecting: &mut ResMut<Reconnecting>,
+    server_clock: &mut ResMut<ServerClock>,
+    rtt: &RoundTripTime,
+    now: f32,
+    msg: SInit,
+) {
+    if msg.id != my_player_id {
+        if !spectating {
+            error!("resumed as {:?} instead of {:?}", msg.id, my_player_id);
+            return;
+        }
+        commands.insert_resource(MyPlayerId(msg.id));
     }
+
+    info!("session resumed");
+    reconnecting.0 = false;
+    server_clock.sync(msg.tick, now, rtt.rtt);
+    commands.insert_resource(*msg.map_layout);
 }
 
 // Handle another player logging in - spawn their entity.
@@ -36,6 +95,7 @@ pub fn handle_player_login_message(
     graphs: &mut ResMut<Assets<AnimationGraph>>,
     players: &mut ResMut<PlayerMap>,
     asset_server: &Res<AssetServer>,
+    server_clock: &ServerClock,
     msg: SLogin,
 ) {
     debug!("{:?} logged in", msg.id);
@@ -44,7 +104,7 @@ pub fn handle_player_login_message(
     }
 
     let mut velocity = msg.player.speed.to_velocity();
-    if msg.player.speed_power_up {
+    if msg.player.effects.contains(EffectKind::Speed) {
         velocity.x *= POWER_UP_SPEED_MULTIPLIER;
         velocity.z *= POWER_UP_SPEED_MULTIPLIER;
     }
@@ -68,15 +128,20 @@ pub fn handle_player_login_message(
             entity,
             hits: 0,
             name: msg.player.name,
-            speed_power_up: msg.player.speed_power_up,
-            multi_shot_power_up: msg.player.multi_shot_power_up,
-            phasing_power_up: msg.player.phasing_power_up,
-            sentry_hunt_power_up: msg.player.sentry_hunt_power_up,
+            effects: msg.player.effects,
+            effect_expiries: effect_expiries(&msg.player.effect_timers, server_clock),
+            shield_charges: msg.player.shield_charges,
             stunned: msg.player.stunned,
         },
     );
 }
 
+// Handle the spectator list changing - someone started or stopped spectating.
+pub fn handle_spectators_message(spectator_list: &mut ResMut<SpectatorList>, msg: SSpectators) {
+    debug!("{} spectators", msg.spectators.len());
+    spectator_list.0 = msg.spectators;
+}
+
 // Handle player logging off - despawn their entity.
 pub fn handle_player_logoff_message(commands: &mut Commands, players: &mut ResMut<PlayerMap>, msg: SLogoff) {
     debug!("{:?} logged off (graceful: {})", msg.id, msg.graceful);
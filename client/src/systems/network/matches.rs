@@ -0,0 +1,31 @@
+use bevy::prelude::*;
+
+use crate::{
+    constants::MATCH_SUMMARY_DURATION,
+    resources::{Leaderboard, MatchSummary, Scoreboard},
+};
+use common::protocol::*;
+
+// ============================================================================
+// Match Message Handlers
+// ============================================================================
+
+// Handle periodic scoreboard - keep it for the scoreboard UI.
+pub fn handle_scoreboard_message(scoreboard: &mut ResMut<Scoreboard>, msg: SScoreboard) {
+    trace!("scoreboard: {:?}", msg);
+    scoreboard.remaining = msg.remaining;
+    scoreboard.entries = msg.entries;
+}
+
+// Handle match end - show the final scoreboard as the post-match summary.
+pub fn handle_match_end_message(match_summary: &mut ResMut<MatchSummary>, now: f32, msg: SMatchEnd) {
+    debug!("match ended: {:?}", msg);
+    match_summary.entries = msg.entries;
+    match_summary.hide_at = now + MATCH_SUMMARY_DURATION;
+}
+
+// Handle all-time leaderboard - keep it for the leaderboard UI.
+pub fn handle_leaderboard_message(leaderboard: &mut ResMut<Leaderboard>, msg: SLeaderboard) {
+    debug!("leaderboard with {} entries", msg.entries.len());
+    leaderboard.entries = msg.entries;
+}
@@ -1,19 +1,30 @@
 use bevy::prelude::*;
 
 use super::{
-    components::AssetManagers,
+    components::{AssetManagers, MatchResources, ServerSync, SessionState},
     items::handle_item_collected_message,
-    login::{handle_player_login_message, handle_player_logoff_message},
+    lights::handle_light_state_message,
+    login::{
+        handle_player_login_message, handle_player_logoff_message, handle_resume_message, handle_spectators_message,
+    },
+    matches::{handle_leaderboard_message, handle_match_end_message, handle_scoreboard_message},
     players::{
-        handle_player_face_message, handle_player_hit_message, handle_player_shot_message, handle_player_speed_message,
-        handle_player_status_message,
+        handle_explosion_message, handle_player_face_message, handle_player_hit_message, handle_player_jump_message,
+        handle_player_shot_message, handle_player_speed_message, handle_player_status_message,
+        handle_shield_absorb_message,
+    },
+    projectiles::handle_projectile_despawn_message,
+    sentries::{
+        handle_sentry_despawn_message, handle_sentry_hit_message, handle_sentry_message, handle_sentry_shot_message,
     },
-    sentries::{handle_sentry_hit_message, handle_sentry_message},
     systems::handle_echo_message,
+    walls::handle_wall_destroyed_message,
 };
 use crate::{
     markers::MainCameraMarker,
-    resources::{ItemMap, LastUpdateSeq, PlayerMap, RoundTripTime, SentryMap},
+    resources::{
+        ItemMap, LastUpdateSeq, PlayerMap, PredictedShotMap, ProjectileMap, RoundTripTime, SentryMap, ServerClock,
+    },
 };
 use common::{markers::PlayerMarker, markers::SentryMarker, protocol::*};
 
@@ -29,19 +40,37 @@ pub fn dispatch_message(
     players: &mut ResMut<PlayerMap>,
     items: &mut ResMut<ItemMap>,
     sentries: &mut ResMut<SentryMap>,
-    rtt: &mut ResMut<RoundTripTime>,
-    last_update_seq: &mut ResMut<LastUpdateSeq>,
+    projectiles: &mut ResMut<ProjectileMap>,
+    predicted_shots: &mut ResMut<PredictedShotMap>,
+    sync: &mut ServerSync,
     assets: &mut AssetManagers,
+    match_resources: &mut MatchResources,
+    session: &mut SessionState,
+    exit: &mut MessageWriter<AppExit>,
     player_data: &Query<(&Position, &FaceDirection), With<PlayerMarker>>,
     sentry_positions: &Query<&Position, With<SentryMarker>>,
     cameras: &Query<Entity, (With<Camera3d>, With<MainCameraMarker>)>,
     time: &Res<Time>,
     asset_server: &Res<AssetServer>,
-    map_layout: Option<&MapLayout>,
+    map_layout: &mut Option<ResMut<MapLayout>>,
 ) {
     match msg {
-        ServerMessage::Init(_) => {
-            error!("received Init more than once");
+        ServerMessage::Init(init_msg) => {
+            handle_resume_message(
+                commands,
+                my_player_id,
+                session.spectator.is_some(),
+                &mut session.reconnecting,
+                &mut sync.server_clock,
+                &sync.rtt,
+                time.elapsed_secs(),
+                init_msg,
+            );
+        }
+        ServerMessage::LoginRejected(rejected_msg) => {
+            // Only a resume can be rejected after Init
+            error!("session could not be resumed: {}", rejected_msg.reason);
+            exit.write(AppExit::error());
         }
         ServerMessage::Login(login) => handle_player_login_message(
             commands,
@@ -51,22 +80,28 @@ pub fn dispatch_message(
             &mut assets.graphs,
             players,
             asset_server,
+            &sync.server_clock,
             login,
         ),
         ServerMessage::Logoff(logoff) => handle_player_logoff_message(commands, players, logoff),
+        ServerMessage::Spectators(spectators_msg) => {
+            handle_spectators_message(&mut session.spectator_list, spectators_msg);
+        }
         ServerMessage::Speed(speed_msg) => {
-            handle_player_speed_message(commands, players, player_data, rtt, speed_msg);
+            handle_player_speed_message(commands, players, player_data, &sync.rtt, speed_msg);
         }
         ServerMessage::Face(face_msg) => handle_player_face_message(commands, players, face_msg),
+        ServerMessage::Jump(jump_msg) => handle_player_jump_message(commands, players, jump_msg),
         ServerMessage::Shot(shot_msg) => {
             handle_player_shot_message(
                 commands,
                 &mut assets.meshes,
                 &mut assets.materials,
                 players,
-                player_data,
+                projectiles,
+                predicted_shots,
+                my_player_id,
                 shot_msg,
-                map_layout,
             );
         }
         ServerMessage::Update(update_msg) => handle_update_message(
@@ -78,20 +113,29 @@ pub fn dispatch_message(
             players,
             items,
             sentries,
-            rtt,
-            last_update_seq,
+            &sync.rtt,
+            &mut sync.last_update_seq,
+            &mut sync.server_clock,
             player_data,
             sentry_positions,
             cameras,
             my_player_id,
             asset_server,
+            time.elapsed_secs(),
             update_msg,
         ),
         ServerMessage::Hit(hit_msg) => handle_player_hit_message(commands, players, cameras, my_player_id, hit_msg),
         ServerMessage::PlayerStatus(player_status_msg) => {
-            handle_player_status_message(commands, players, player_status_msg, my_player_id, asset_server);
+            handle_player_status_message(
+                commands,
+                players,
+                player_status_msg,
+                my_player_id,
+                asset_server,
+                &sync.server_clock,
+            );
         }
-        ServerMessage::Echo(echo_msg) => handle_echo_message(time, rtt, echo_msg),
+        ServerMessage::Echo(echo_msg) => handle_echo_message(time, &mut sync.rtt, echo_msg),
         ServerMessage::Sentry(sentry_msg) => {
             handle_sentry_message(
                 commands,
@@ -99,18 +143,66 @@ pub fn dispatch_message(
                 &mut assets.materials,
                 &mut assets.graphs,
                 sentries,
-                rtt,
+                &sync.rtt,
                 sentry_positions,
                 sentry_msg,
                 asset_server,
             );
         }
+        ServerMessage::SentryDespawn(sentry_despawn_msg) => {
+            handle_sentry_despawn_message(commands, sentries, sentry_despawn_msg);
+        }
+        ServerMessage::SentryShot(sentry_shot_msg) => {
+            handle_sentry_shot_message(
+                commands,
+                &mut assets.meshes,
+                &mut assets.materials,
+                sentries,
+                projectiles,
+                sentry_shot_msg,
+            );
+        }
+        ServerMessage::ProjectileDespawn(projectile_despawn_msg) => {
+            handle_projectile_despawn_message(
+                commands,
+                projectiles,
+                my_player_id,
+                asset_server,
+                projectile_despawn_msg,
+            );
+        }
         ServerMessage::CookieCollected(cookie_msg) => {
             handle_item_collected_message(commands, cookie_msg, asset_server);
         }
         ServerMessage::SentryHit(sentry_hit_msg) => {
             handle_sentry_hit_message(commands, sentry_hit_msg, asset_server);
         }
+        ServerMessage::ShieldAbsorb(shield_absorb_msg) => {
+            handle_shield_absorb_message(commands, shield_absorb_msg, asset_server);
+        }
+        ServerMessage::Explosion(explosion_msg) => {
+            handle_explosion_message(
+                commands,
+                &mut assets.meshes,
+                &mut assets.materials,
+                players,
+                asset_server,
+                explosion_msg,
+            );
+        }
+        ServerMessage::WallDestroyed(wall_destroyed_msg) => {
+            handle_wall_destroyed_message(commands, map_layout, asset_server, wall_destroyed_msg);
+        }
+        ServerMessage::LightState(light_state_msg) => handle_light_state_message(map_layout, light_state_msg),
+        ServerMessage::Scoreboard(scoreboard_msg) => {
+            handle_scoreboard_message(&mut match_resources.scoreboard, scoreboard_msg);
+        }
+        ServerMessage::MatchEnd(match_end_msg) => {
+            handle_match_end_message(&mut match_resources.match_summary, time.elapsed_secs(), match_end_msg);
+        }
+        ServerMessage::Leaderboard(leaderboard_msg) => {
+            handle_leaderboard_message(&mut match_resources.leaderboard, leaderboard_msg);
+        }
     }
 }
 
@@ -130,11 +222,13 @@ pub fn handle_update_message(
     sentries: &mut ResMut<SentryMap>,
     rtt: &ResMut<RoundTripTime>,
     last_update_seq: &mut ResMut<LastUpdateSeq>,
+    server_clock: &mut ResMut<ServerClock>,
     player_data: &Query<(&Position, &FaceDirection), With<PlayerMarker>>,
     sentry_query: &Query<&Position, With<SentryMarker>>,
     camera_query: &Query<Entity, (With<Camera3d>, With<MainCameraMarker>)>,
     my_player_id: PlayerId,
     asset_server: &Res<AssetServer>,
+    now: f32,
     msg: SUpdate,
 ) {
     // Ignore outdated updates
@@ -149,6 +243,9 @@ pub fn handle_update_message(
     // Update the last received sequence number
     last_update_seq.0 = msg.seq;
 
+    // Keep the server clock in sync for the effect timers
+    server_clock.sync(msg.tick, now, rtt.rtt);
+
     super::players::sync_players(
         commands,
         meshes,
@@ -161,6 +258,7 @@ pub fn handle_update_message(
         camera_query,
         my_player_id,
         asset_server,
+        server_clock,
         &msg.players,
     );
     super::items::sync_items(commands, meshes, materials, items, asset_server, &msg.items);
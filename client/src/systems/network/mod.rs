@@ -1,10 +1,14 @@
 mod components;
 mod items;
+mod lights;
 mod login;
+mod matches;
 mod messages;
 mod players;
+mod projectiles;
 mod sentries;
 mod systems;
+mod walls;
 
 pub use components::{AssetManagers, ServerReconciliation};
 pub use systems::{network_echo_system, network_server_message_system};
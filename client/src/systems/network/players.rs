@@ -4,11 +4,29 @@ use std::collections::HashSet;
 use super::components::ServerReconciliation;
 use crate::{
     markers::MainCameraMarker,
-    resources::{PlayerInfo, PlayerMap, RoundTripTime},
-    spawning::{spawn_player, spawn_projectiles},
+    resources::{PlayerInfo, PlayerMap, PredictedShotMap, ProjectileMap, RoundTripTime, ServerClock},
+    spawning::{spawn_explosion, spawn_player, spawn_replicated_projectiles},
     systems::players::{CameraShake, CuboidShake},
 };
-use common::{constants::POWER_UP_SPEED_MULTIPLIER, markers::PlayerMarker, protocol::*};
+use common::{
+    constants::{PLAYER_JUMP_SPEED, POWER_UP_SPEED_MULTIPLIER},
+    markers::PlayerMarker,
+    power_ups::EffectKind,
+    protocol::*,
+};
+
+// ============================================================================
+// Helper Functions
+// ============================================================================
+
+// Convert replicated effect timers (server ticks at which they expire) to local expiry times.
+#[must_use]
+pub fn effect_expiries(effect_timers: &[(EffectKind, u32)], server_clock: &ServerClock) -> Vec<(EffectKind, f32)> {
+    effect_timers
+        .iter()
+        .map(|(effect, tick)| (*effect, server_clock.local_time(*tick)))
+        .collect()
+}
 
 // ============================================================================
 // Player Message Handlers
@@ -24,7 +42,7 @@ pub fn handle_player_speed_message(
 ) {
     trace!("{:?} speed: {:?}", msg.id, msg);
     if let Some(player) = players.0.get(&msg.id) {
-        let multiplier = if player.speed_power_up {
+        let multiplier = if player.effects.contains(EffectKind::Speed) {
             POWER_UP_SPEED_MULTIPLIER
         } else {
             1.0
@@ -49,6 +67,16 @@ pub fn handle_player_speed_message(
     }
 }
 
+// Handle another player jumping - predict the jump locally until the next update.
+pub fn handle_player_jump_message(commands: &mut Commands, players: &ResMut<PlayerMap>, msg: SJump) {
+    trace!("{:?} jump", msg.id);
+    if let Some(player) = players.0.get(&msg.id) {
+        commands
+            .entity(player.entity)
+            .insert(VerticalVelocity(PLAYER_JUMP_SPEED));
+    }
+}
+
 // Handle player face direction update.
 pub fn handle_player_face_message(commands: &mut Commands, players: &ResMut<PlayerMap>, msg: SFace) {
     trace!("{:?} face direction: {}", msg.id, msg.dir);
@@ -57,42 +85,62 @@ pub fn handle_player_face_message(commands: &mut Commands, players: &ResMut<Play
     }
 }
 
-// Handle player shooting - spawn projectile(s) on client.
+// Handle player shooting - spawn the shot's projectiles as replicated by the server. For my own shots the
+// predicted projectiles are matched up with the server's by order and only the difference is spawned or removed.
 pub fn handle_player_shot_message(
     commands: &mut Commands,
     meshes: &mut ResMut<Assets<Mesh>>,
     materials: &mut ResMut<Assets<StandardMaterial>>,
     players: &ResMut<PlayerMap>,
-    player_data: &Query<(&Position, &FaceDirection), With<PlayerMarker>>,
+    projectiles: &mut ResMut<ProjectileMap>,
+    predicted_shots: &mut ResMut<PredictedShotMap>,
+    my_player_id: PlayerId,
     msg: SShot,
-    map_layout: Option<&MapLayout>,
 ) {
     trace!("{:?} shot: {:?}", msg.id, msg);
-    if let Some(player) = players.0.get(&msg.id) {
-        commands.entity(player.entity).insert(FaceDirection(msg.face_dir));
-
-        // Spawn projectile(s) based on player's multi-shot power-up status
-        if let Ok((position, _)) = player_data.get(player.entity)
-            && let Some(map_layout) = map_layout
-        {
-            spawn_projectiles(
-                commands,
-                meshes,
-                materials,
-                position,
-                msg.face_dir,
-                msg.face_pitch,
-                player.multi_shot_power_up,
-                map_layout.lower_walls.as_slice(),
-                map_layout.ramps.as_slice(),
-                map_layout.roofs.as_slice(),
-                msg.id,
-            );
+
+    if msg.id != my_player_id {
+        if let Some(player) = players.0.get(&msg.id) {
+            commands.entity(player.entity).insert(FaceDirection(msg.face_dir));
         }
+        spawn_replicated_projectiles(
+            commands,
+            meshes,
+            materials,
+            projectiles,
+            &msg.projectiles,
+            msg.weapon,
+            msg.id,
+        );
+        return;
     }
+
+    // The server handles shots in order, so earlier shots still waiting for confirmation were rejected
+    predicted_shots.0.retain(|seq, entities| {
+        if *seq >= msg.seq {
+            return true;
+        }
+        for entity in entities.drain(..) {
+            commands.entity(entity).try_despawn();
+        }
+        false
+    });
+
+    let predicted = predicted_shots.0.remove(&msg.seq).unwrap_or_default();
+    for (index, entity) in predicted.iter().enumerate() {
+        if let Some(spawn) = msg.projectiles.get(index) {
+            commands.entity(*entity).try_insert(spawn.id);
+            projectiles.0.insert(spawn.id, *entity);
+        } else {
+            commands.entity(*entity).try_despawn();
+        }
+    }
+
+    let missing = msg.projectiles.get(predicted.len()..).unwrap_or_default();
+    spawn_replicated_projectiles(commands, meshes, materials, projectiles, missing, msg.weapon, msg.id);
 }
 
-// Handle player being hit - apply camera shake or cuboid shake.
+// Handle player being hit - push the player like the server does and apply camera shake or cuboid shake.
 pub fn handle_player_hit_message(
     commands: &mut Commands,
     players: &ResMut<PlayerMap>,
@@ -101,6 +149,14 @@ pub fn handle_player_hit_message(
     msg: SHit,
 ) {
     debug!("player {:?} was hit", msg.id);
+
+    // Predict the knockback of the hit, so the player doesn't snap to the pushed server position
+    if let Some(player) = players.0.get(&msg.id) {
+        commands
+            .entity(player.entity)
+            .insert(Knockback::from_hit(msg.hit_dir_x, msg.hit_dir_z));
+    }
+
     if msg.id == my_player_id {
         if let Ok(camera_entity) = camera_query.single() {
             commands.entity(camera_entity).insert(CameraShake {
@@ -125,6 +181,45 @@ pub fn handle_player_hit_message(
     }
 }
 
+// Handle a shield absorbing a hit - play the absorb sound effect.
+pub fn handle_shield_absorb_message(commands: &mut Commands, msg: SShieldAbsorb, asset_server: &AssetServer) {
+    debug!("shield of {:?} absorbed a hit", msg.id);
+    commands.spawn((
+        AudioPlayer::new(asset_server.load("sounds/shield_absorb.wav")),
+        PlaybackSettings::DESPAWN,
+    ));
+}
+
+// Handle explosion - show the fireball, play the blast and push the players caught in it (the server pushes
+// them the same way).
+pub fn handle_explosion_message(
+    commands: &mut Commands,
+    meshes: &mut ResMut<Assets<Mesh>>,
+    materials: &mut ResMut<Assets<StandardMaterial>>,
+    players: &ResMut<PlayerMap>,
+    asset_server: &AssetServer,
+    msg: SExplosion,
+) {
+    debug!("{:?} of {:?} exploded at {:?}", msg.weapon, msg.shooter, msg.position);
+    let radius = msg
+        .weapon
+        .def()
+        .explosion
+        .as_ref()
+        .map_or(0.0, |explosion| explosion.radius);
+    spawn_explosion(commands, meshes, materials, &msg.position, radius);
+    commands.spawn((
+        AudioPlayer::new(asset_server.load("sounds/explosion.wav")),
+        PlaybackSettings::DESPAWN,
+    ));
+
+    for (id, knockback) in msg.knockbacks {
+        if let Some(player) = players.0.get(&id) {
+            commands.entity(player.entity).insert(knockback);
+        }
+    }
+}
+
 // Handle player status update (power-ups, stun).
 pub fn handle_player_status_message(
     commands: &mut Commands,
@@ -132,17 +227,17 @@ pub fn handle_player_status_message(
     msg: SPlayerStatus,
     my_player_id: PlayerId,
     asset_server: &AssetServer,
+    server_clock: &ServerClock,
 ) {
     if let Some(player_info) = players.0.get_mut(&msg.id) {
         // Play power-up sound effect only for the local player
         if msg.id == my_player_id {
             // Don't play power-up sound effect if this message is due to a stun change
             if player_info.stunned == msg.stunned {
-                // Only play power-up sound effect if it wasn't a downgrade
-                #[allow(clippy::nonminimal_bool)]
-                let lost_power_up = player_info.speed_power_up && !msg.speed_power_up
-                    || player_info.multi_shot_power_up && !msg.multi_shot_power_up
-                    || player_info.phasing_power_up && !msg.phasing_power_up;
+                // Only play power-up sound effect if it wasn't a downgrade (shield charges used up
+                // by an absorbed hit count as one)
+                let lost_power_up = player_info.effects.iter().any(|effect| !msg.effects.contains(effect))
+                    || msg.shield_charges < player_info.shield_charges;
 
                 if !lost_power_up {
                     commands.spawn((
@@ -153,10 +248,9 @@ pub fn handle_player_status_message(
             }
         }
 
-        player_info.speed_power_up = msg.speed_power_up;
-        player_info.multi_shot_power_up = msg.multi_shot_power_up;
-        player_info.phasing_power_up = msg.phasing_power_up;
-        player_info.sentry_hunt_power_up = msg.sentry_hunt_power_up;
+        player_info.effects = msg.effects;
+        player_info.effect_expiries = effect_expiries(&msg.effect_timers, server_clock);
+        player_info.shield_charges = msg.shield_charges;
         player_info.stunned = msg.stunned;
     }
 }
@@ -178,6 +272,7 @@ pub fn sync_players(
     camera_query: &Query<Entity, (With<Camera3d>, With<MainCameraMarker>)>,
     my_player_id: PlayerId,
     asset_server: &Res<AssetServer>,
+    server_clock: &ServerClock,
     server_players: &[(PlayerId, Player)],
 ) {
     // Track which players the server knows about in this snapshot
@@ -191,7 +286,7 @@ pub fn sync_players(
 
         let is_local = *id == my_player_id;
         debug!("spawning player {:?} from Update (is_local: {})", id, is_local);
-        let multiplier = if player.speed_power_up {
+        let multiplier = if player.effects.contains(EffectKind::Speed) {
             POWER_UP_SPEED_MULTIPLIER
         } else {
             1.0
@@ -226,10 +321,9 @@ pub fn sync_players(
                 entity,
                 hits: player.hits,
                 name: player.name.clone(),
-                speed_power_up: player.speed_power_up,
-                multi_shot_power_up: player.multi_shot_power_up,
-                phasing_power_up: player.phasing_power_up,
-                sentry_hunt_power_up: player.sentry_hunt_power_up,
+                effects: player.effects,
+                effect_expiries: effect_expiries(&player.effect_timers, server_clock),
+                shield_charges: player.shield_charges,
                 stunned: player.stunned,
             },
         );
@@ -249,7 +343,7 @@ pub fn sync_players(
     for (id, server_player) in server_players {
         if let Some(client_player) = players.0.get_mut(id) {
             if let Ok((client_pos, _)) = player_data.get(client_player.entity) {
-                let multiplier = if server_player.speed_power_up {
+                let multiplier = if server_player.effects.contains(EffectKind::Speed) {
                     POWER_UP_SPEED_MULTIPLIER
                 } else {
                     1.0
@@ -271,9 +365,9 @@ pub fn sync_players(
             }
 
             client_player.hits = server_player.hits;
-            client_player.speed_power_up = server_player.speed_power_up;
-            client_player.multi_shot_power_up = server_player.multi_shot_power_up;
-            client_player.phasing_power_up = server_player.phasing_power_up;
+            client_player.effects = server_player.effects;
+            client_player.effect_expiries = effect_expiries(&server_player.effect_timers, server_clock);
+            client_player.shield_charges = server_player.shield_charges;
         }
     }
 }
@@ -0,0 +1,76 @@
+use bevy::{
+    audio::{PlaybackMode, Volume},
+    prelude::*,
+};
+
+use crate::resources::ProjectileMap;
+use common::protocol::*;
+
+// ============================================================================
+// Projectile Message Handlers
+// ============================================================================
+
+// Handle projectile removed by the server - despawn it (unless it already expired or stopped locally) and play
+// the sound of what it hit.
+pub fn handle_projectile_despawn_message(
+    commands: &mut Commands,
+    projectiles: &mut ResMut<ProjectileMap>,
+    my_player_id: PlayerId,
+    asset_server: &AssetServer,
+    msg: SProjectileDespawn,
+) {
+    trace!("{:?} despawned: {:?}", msg.id, msg.hit);
+    if let Some(entity) = projectiles.0.remove(&msg.id) {
+        commands.entity(entity).try_despawn();
+    }
+
+    match msg.hit {
+        // Shield absorbs are announced by their own message
+        ProjectileHit::Nothing | ProjectileHit::Shield(_) => {}
+        ProjectileHit::Player(player_id) => {
+            play_sound(
+                commands,
+                asset_server,
+                "sounds/projectile_hits_player.ogg",
+                PlaybackSettings::DESPAWN,
+            );
+            if player_id == my_player_id {
+                play_sound(
+                    commands,
+                    asset_server,
+                    "sounds/player_gets_hit.ogg",
+                    PlaybackSettings::DESPAWN,
+                );
+            }
+        }
+        ProjectileHit::Sentry { scored: true, .. } => {
+            play_sound(
+                commands,
+                asset_server,
+                "sounds/player_hits_sentry.wav",
+                PlaybackSettings::DESPAWN,
+            );
+        }
+        ProjectileHit::Sentry { scored: false, .. } => {
+            play_sound(
+                commands,
+                asset_server,
+                "sounds/projectile_hits_sentry_no_damage.ogg",
+                PlaybackSettings {
+                    mode: PlaybackMode::Despawn,
+                    volume: Volume::Linear(0.2),
+                    ..default()
+                },
+            );
+        }
+    }
+}
+
+fn play_sound(
+    commands: &mut Commands,
+    asset_server: &AssetServer,
+    asset_path: &'static str,
+    settings: PlaybackSettings,
+) {
+    commands.spawn((AudioPlayer::new(asset_server.load(asset_path)), settings));
+}
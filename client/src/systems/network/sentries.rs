@@ -3,10 +3,10 @@ use std::collections::HashSet;
 
 use super::components::ServerReconciliation;
 use crate::{
-    resources::{RoundTripTime, SentryInfo, SentryMap},
-    spawning::spawn_sentry,
+    resources::{ProjectileMap, RoundTripTime, SentryInfo, SentryMap},
+    spawning::{spawn_replicated_projectiles, spawn_sentry},
 };
-use common::{markers::SentryMarker, protocol::*};
+use common::{markers::SentryMarker, protocol::*, weapons::WeaponId};
 
 // ============================================================================
 // Sentry Message Handlers
@@ -45,20 +45,18 @@ pub fn handle_sentry_message(
         }
     } else {
         // Spawn new sentry
-        let entity = spawn_sentry(
-            commands,
-            meshes,
-            materials,
-            asset_server,
-            graphs,
-            msg.id,
-            &msg.sentry.pos,
-            &msg.sentry.vel,
-        );
+        let entity = spawn_sentry(commands, meshes, materials, asset_server, graphs, msg.id, &msg.sentry);
         sentries.0.insert(msg.id, SentryInfo { entity });
     }
 }
 
+// Handle sentry removed by the server - despawn it.
+pub fn handle_sentry_despawn_message(commands: &mut Commands, sentries: &mut ResMut<SentryMap>, msg: SSentryDespawn) {
+    if let Some(sentry_info) = sentries.0.remove(&msg.id) {
+        commands.entity(sentry_info.entity).despawn();
+    }
+}
+
 // Handle sentry hitting player - play sound effect.
 pub fn handle_sentry_hit_message(commands: &mut Commands, _msg: SSentryHit, asset_server: &AssetServer) {
     // Play sound - this message is only sent to the player who was hit
@@ -68,6 +66,31 @@ pub fn handle_sentry_hit_message(commands: &mut Commands, _msg: SSentryHit, asse
     ));
 }
 
+// Handle turret sentry shooting - face the shot direction and spawn its projectile as replicated by the server.
+pub fn handle_sentry_shot_message(
+    commands: &mut Commands,
+    meshes: &mut ResMut<Assets<Mesh>>,
+    materials: &mut ResMut<Assets<StandardMaterial>>,
+    sentries: &ResMut<SentryMap>,
+    projectiles: &mut ResMut<ProjectileMap>,
+    msg: SSentryShot,
+) {
+    trace!("{:?} shot: {:?}", msg.id, msg);
+    if let Some(sentry_info) = sentries.0.get(&msg.id) {
+        commands.entity(sentry_info.entity).insert(FaceDirection(msg.face_dir));
+    }
+
+    spawn_replicated_projectiles(
+        commands,
+        meshes,
+        materials,
+        projectiles,
+        &msg.projectiles,
+        WeaponId::Ball,
+        msg.id,
+    );
+}
+
 // ============================================================================
 // Sentry Synchronization Helper
 // ============================================================================
@@ -98,8 +121,7 @@ pub fn sync_sentries(
             asset_server,
             graphs,
             *sentry_id,
-            &server_sentry.pos,
-            &server_sentry.vel,
+            server_sentry,
         );
         sentries.0.insert(*sentry_id, SentryInfo { entity });
     }
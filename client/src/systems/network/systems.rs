@@ -1,15 +1,16 @@
 use bevy::prelude::*;
 use std::time::Duration;
 
-use super::{components::AssetManagers, login::handle_init_message, messages::dispatch_message};
+use super::{
+    components::{AssetManagers, EntityMaps, MatchResources, ServerSync, SessionState},
+    login::handle_init_message,
+    messages::dispatch_message,
+};
 use crate::{
     constants::ECHO_INTERVAL,
     markers::MainCameraMarker,
     net::{ClientToServer, ServerToClient},
-    resources::{
-        ClientToServerChannel, ItemMap, LastUpdateSeq, MyPlayerId, PlayerMap, RoundTripTime, SentryMap,
-        ServerToClientChannel,
-    },
+    resources::{ClientToServerChannel, MyPlayerId, RoundTripTime, ServerToClientChannel},
 };
 use common::{markers::PlayerMarker, markers::SentryMarker, protocol::*};
 
@@ -22,23 +23,25 @@ pub fn network_server_message_system(
     mut commands: Commands,
     mut from_server: ResMut<ServerToClientChannel>,
     mut exit: MessageWriter<AppExit>,
-    mut players: ResMut<PlayerMap>,
-    mut items: ResMut<ItemMap>,
-    mut sentries: ResMut<SentryMap>,
-    mut rtt: ResMut<RoundTripTime>,
-    mut last_update_seq: ResMut<LastUpdateSeq>,
+    mut maps: EntityMaps,
+    mut sync: ServerSync,
     mut assets: AssetManagers,
+    mut match_resources: MatchResources,
     player_data: Query<(&Position, &FaceDirection), With<PlayerMarker>>,
     sentry_positions: Query<&Position, With<SentryMarker>>,
     cameras: Query<Entity, (With<Camera3d>, With<MainCameraMarker>)>,
     my_player_id: Option<Res<MyPlayerId>>,
-    map_layout: Option<Res<MapLayout>>,
+    mut session: SessionState,
+    mut map_layout: Option<ResMut<MapLayout>>,
     time: Res<Time>,
     asset_server: Res<AssetServer>,
 ) {
     // Process all messages from the server
     while let Ok(msg) = from_server.try_recv() {
         match msg {
+            ServerToClient::Reconnecting => {
+                session.reconnecting.0 = true;
+            }
             ServerToClient::Disconnected => {
                 error!("disconnected from server");
                 exit.write(AppExit::Success);
@@ -49,21 +52,34 @@ pub fn network_server_message_system(
                         message,
                         my_id.0,
                         &mut commands,
-                        &mut players,
-                        &mut items,
-                        &mut sentries,
-                        &mut rtt,
-                        &mut last_update_seq,
+                        &mut maps.players,
+                        &mut maps.items,
+                        &mut maps.sentries,
+                        &mut maps.projectiles,
+                        &mut maps.predicted_shots,
+                        &mut sync,
                         &mut assets,
+                        &mut match_resources,
+                        &mut session,
+                        &mut exit,
                         &player_data,
                         &sentry_positions,
                         &cameras,
                         &time,
                         &asset_server,
-                        map_layout.as_deref(),
+                        &mut map_layout,
                     );
                 } else {
-                    handle_init_message(message, &mut commands);
+                    handle_init_message(
+                        message,
+                        &mut commands,
+                        &session.session_token_file,
+                        &mut session.spectator_list,
+                        &mut sync.server_clock,
+                        &sync.rtt,
+                        time.elapsed_secs(),
+                        &mut exit,
+                    );
                 }
             }
         }
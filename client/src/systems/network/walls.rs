@@ -0,0 +1,29 @@
+use bevy::prelude::*;
+
+use common::protocol::*;
+
+// ============================================================================
+// Wall Message Handlers
+// ============================================================================
+
+// Handle destructible wall destroyed - remove it from the map layout. The wall mesh is despawned by
+// `map_despawn_destroyed_walls_system` once the layout changes.
+pub fn handle_wall_destroyed_message(
+    commands: &mut Commands,
+    map_layout: &mut Option<ResMut<MapLayout>>,
+    asset_server: &AssetServer,
+    msg: SWallDestroyed,
+) {
+    debug!("{:?} destroyed", msg.id);
+
+    let Some(map_layout) = map_layout.as_mut() else {
+        return;
+    };
+
+    if map_layout.remove_destructible_wall(msg.id).is_some() {
+        commands.spawn((
+            AudioPlayer::new(asset_server.load("sounds/projectile_hits_wall.ogg")),
+            PlaybackSettings::DESPAWN,
+        ));
+    }
+}
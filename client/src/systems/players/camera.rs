@@ -1,7 +1,7 @@
 use bevy::{camera::Viewport, prelude::*};
 
 use super::components::CameraShake;
-use crate::{constants::*, markers::*, resources::CameraViewMode};
+use crate::{constants::*, markers::*, resources::CameraViewMode, systems::cameras::apply_topdown_camera};
 use common::{
     constants::{PLAYER_EYE_HEIGHT_RATIO, PLAYER_HEIGHT},
     protocol::Position,
@@ -43,16 +43,9 @@ pub fn local_player_camera_sync_system(
                 }
             }
             CameraViewMode::TopDown => {
-                if view_mode.is_changed() {
-                    camera_transform.translation = Vec3::new(0.0, TOPDOWN_CAMERA_HEIGHT, TOPDOWN_CAMERA_Z_OFFSET);
-                }
-                camera_transform.look_at(Vec3::new(TOPDOWN_LOOKAT_X, TOPDOWN_LOOKAT_Y, TOPDOWN_LOOKAT_Z), Vec3::Y);
-
-                // Set top-down FOV
-                if let Projection::Perspective(persp) = projection.as_mut() {
-                    persp.fov = TOPDOWN_CAMERA_FOV_DEGREES.to_radians();
-                }
+                apply_topdown_camera(&mut camera_transform, &mut projection, view_mode.is_changed());
             }
+            CameraViewMode::FreeFly | CameraViewMode::Follow => {} // Spectators only (they have no local player)
         }
     }
 }
@@ -66,7 +59,7 @@ pub fn local_player_visibility_sync_system(
     for (_entity, mut visibility, _has_mesh) in &mut local_player_query {
         let desired_visibility = match *view_mode {
             CameraViewMode::FirstPerson => Visibility::Hidden,
-            CameraViewMode::TopDown => Visibility::Visible,
+            CameraViewMode::TopDown | CameraViewMode::FreeFly | CameraViewMode::Follow => Visibility::Visible,
         };
 
         if *visibility != desired_visibility {
@@ -2,6 +2,10 @@ use bevy::prelude::*;
 use std::time::Duration;
 
 use super::components::{CameraShake, CuboidShake};
+use crate::{
+    constants::POWER_UP_EXPIRY_WARNING,
+    resources::{MyPlayerId, PlayerMap},
+};
 use common::markers::PlayerMarker;
 
 // ============================================================================
@@ -30,6 +34,33 @@ pub fn local_player_cuboid_shake_system(
     }
 }
 
+// Play a warning sound when one of the local player's power-ups is about to run out
+pub fn local_player_power_up_warning_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    asset_server: Res<AssetServer>,
+    players: Res<PlayerMap>,
+    my_player_id: Option<Res<MyPlayerId>>,
+) {
+    let Some(player_info) = my_player_id.and_then(|id| players.0.get(&id.0)) else {
+        return;
+    };
+
+    // Warn once, in the frame the remaining time drops below the threshold
+    let now = time.elapsed_secs();
+    let previous = now - time.delta_secs();
+    let expiring = player_info.effect_expiries.iter().any(|(_, expires_at)| {
+        expires_at - now <= POWER_UP_EXPIRY_WARNING && expires_at - previous > POWER_UP_EXPIRY_WARNING
+    });
+
+    if expiring {
+        commands.spawn((
+            AudioPlayer::new(asset_server.load("sounds/power_up_expiring.wav")),
+            PlaybackSettings::DESPAWN,
+        ));
+    }
+}
+
 fn update_camera_shake(commands: &mut Commands, entity: Entity, delta: Duration, shake: &mut CameraShake) {
     shake.timer.tick(delta);
     if shake.timer.is_finished() {
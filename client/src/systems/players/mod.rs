@@ -9,6 +9,11 @@ pub use camera::{
     local_player_visibility_sync_system,
 };
 pub use components::{BumpFlashState, CameraShake, CuboidShake};
-pub use effects::{local_player_camera_shake_system, local_player_cuboid_shake_system};
+pub use effects::{
+    local_player_camera_shake_system, local_player_cuboid_shake_system, local_player_power_up_warning_system,
+};
 pub use movement::players_movement_system;
-pub use rendering::{players_billboard_system, players_face_to_transform_system, players_transform_sync_system};
+pub use rendering::{
+    players_billboard_system, players_face_to_transform_system, players_shield_visibility_system,
+    players_transform_sync_system,
+};
@@ -3,11 +3,15 @@ use bevy::prelude::*;
 use super::components::BumpFlashState;
 use crate::{markers::*, resources::PlayerMap, systems::network::ServerReconciliation};
 use common::{
-    collision::{slide_player_along_obstacles, sweep_player_vs_ramp_edges, sweep_player_vs_wall},
-    constants::{ALWAYS_PHASING, PHYSICS_EPSILON, ROOF_HEIGHT, SPEED_RUN, UPDATE_BROADCAST_INTERVAL},
-    map::{close_to_roof, has_roof, height_on_ramp},
+    collision::{
+        jump_pad_launch, player_is_grounded, slide_player_along_obstacles, step_player_vertical,
+        sweep_player_vs_ramp_edges, sweep_player_vs_wall,
+    },
+    constants::{ALWAYS_PHASING, PHYSICS_EPSILON, SPEED_RUN, UPDATE_BROADCAST_INTERVAL},
+    map::walls_at_height,
     players::{PlannedMove, overlaps_other_player},
-    protocol::{MapLayout, PlayerId, Position, Velocity},
+    power_ups::EffectKind,
+    protocol::{JumpPadLaunch, Knockback, MapLayout, PlayerId, Position, Velocity, VerticalVelocity},
 };
 
 // ============================================================================
@@ -78,6 +82,9 @@ type MovementQuery<'w, 's> = Query<
         &'static PlayerId,
         &'static mut Position,
         &'static Velocity,
+        &'static mut VerticalVelocity,
+        Option<&'static JumpPadLaunch>,
+        Option<&'static mut Knockback>,
         Option<&'static mut BumpFlashState>,
         Option<&'static mut ServerReconciliation>,
         Has<LocalPlayerMarker>,
@@ -98,12 +105,57 @@ pub fn players_movement_system(
     // Pass 1: For each player, calculate intended position, then apply wall collision logic
     let mut planned_moves: Vec<PlannedMove> = Vec::new();
 
-    for (entity, player_id, mut client_pos, client_vel, mut flash_state, mut recon_option, is_local) in &mut query {
+    for (
+        entity,
+        player_id,
+        mut client_pos,
+        client_vel,
+        mut vertical_velocity,
+        launch,
+        knockback,
+        mut flash_state,
+        mut recon_option,
+        is_local,
+    ) in &mut query
+    {
         if let Some(state) = flash_state.as_mut() {
             decay_flash_timer(state, delta, is_local, &mut bump_flash_ui);
         }
 
-        let abs_velocity = client_vel.x.hypot(client_vel.z);
+        // Launch players stepping onto a jump pad (same as the server)
+        let mut launch = launch.copied();
+        let is_stunned = players.0.get(player_id).is_some_and(|info| info.stunned);
+        if launch.is_none()
+            && !is_stunned
+            && let Some(map_layout) = map_layout.as_ref()
+            && let Some(launch_velocity) = jump_pad_launch(map_layout, &client_pos, vertical_velocity.0)
+        {
+            vertical_velocity.0 = launch_velocity.y;
+            let new_launch = JumpPadLaunch {
+                vel_x: launch_velocity.x,
+                vel_z: launch_velocity.z,
+            };
+            commands.entity(entity).insert(new_launch);
+            launch = Some(new_launch);
+        }
+
+        // Launched players follow the jump pad arc instead of their movement input
+        let mut move_vel = launch.map_or(*client_vel, |launch| Velocity {
+            x: launch.vel_x,
+            y: 0.0,
+            z: launch.vel_z,
+        });
+
+        // Explosions push players on top of their own movement until the push dies out (same as the server)
+        if let Some(mut knockback) = knockback {
+            move_vel.x += knockback.vel_x;
+            move_vel.z += knockback.vel_z;
+            if !knockback.decay(delta) {
+                commands.entity(entity).remove::<Knockback>();
+            }
+        }
+
+        let abs_velocity = move_vel.x.hypot(move_vel.z);
         let is_standing_still = abs_velocity < PHYSICS_EPSILON;
 
         // Calculate intended position from velocity (with server reconciliation if needed)
@@ -138,8 +190,8 @@ pub fn players_movement_system(
             let dx = total_dx * delta * correction_factor / UPDATE_BROADCAST_INTERVAL;
             let dz = total_dz * delta * correction_factor / UPDATE_BROADCAST_INTERVAL;
 
-            let new_x = client_vel.x.mul_add(delta, client_pos.x) + dx;
-            let new_z = client_vel.z.mul_add(delta, client_pos.z) + dz;
+            let new_x = move_vel.x.mul_add(delta, client_pos.x) + dx;
+            let new_z = move_vel.z.mul_add(delta, client_pos.z) + dz;
 
             Position {
                 x: new_x,
@@ -147,8 +199,8 @@ pub fn players_movement_system(
                 z: new_z,
             }
         } else {
-            let new_x = client_vel.x.mul_add(delta, client_pos.x);
-            let new_z = client_vel.z.mul_add(delta, client_pos.z);
+            let new_x = move_vel.x.mul_add(delta, client_pos.x);
+            let new_z = move_vel.z.mul_add(delta, client_pos.z);
             Position {
                 x: new_x,
                 y: client_pos.y, // Keep current Y for collision detection
@@ -156,68 +208,54 @@ pub fn players_movement_system(
             }
         };
 
-        // Skip collision checks if player is standing still
-        if is_standing_still {
-            planned_moves.push(PlannedMove {
-                entity,
-                start: *client_pos,
-                target: target_pos,
-                collides: false,
-            });
-            continue;
-        }
-
-        // Check collision and calculate target (with sliding if collision)
+        // Check collision and calculate target (with sliding if collision), skipped if standing still
         let mut collides = false;
 
         if let Some(map_layout) = map_layout.as_ref() {
-            let walls_to_check = if close_to_roof(client_pos.y) {
-                &map_layout.roof_walls
-            } else {
-                let has_phasing = ALWAYS_PHASING || players.0.get(player_id).is_some_and(|info| info.phasing_power_up);
-                if has_phasing {
-                    &map_layout.boundary_walls
-                } else {
-                    &map_layout.lower_walls
-                }
-            };
-
-            for wall in walls_to_check {
-                if sweep_player_vs_wall(&client_pos, &target_pos, wall) {
-                    collides = true;
-                    break;
-                }
-            }
-
-            if !collides {
-                for ramp in &map_layout.ramps {
-                    if sweep_player_vs_ramp_edges(&client_pos, &target_pos, ramp) {
+            if !is_standing_still {
+                // Walls that vertically overlap the player at the current height
+                let has_phasing = ALWAYS_PHASING
+                    || players
+                        .0
+                        .get(player_id)
+                        .is_some_and(|info| info.effects.contains(EffectKind::Phasing));
+                let walls_to_check = walls_at_height(map_layout, client_pos.y, has_phasing);
+
+                for wall in &walls_to_check {
+                    if sweep_player_vs_wall(&client_pos, &target_pos, wall) {
                         collides = true;
                         break;
                     }
                 }
-            }
 
-            if collides {
-                target_pos = slide_player_along_obstacles(
-                    walls_to_check,
-                    &map_layout.ramps,
-                    &client_pos,
-                    client_vel.x,
-                    client_vel.z,
-                    delta,
-                );
+                if !collides {
+                    for ramp in &map_layout.ramps {
+                        if sweep_player_vs_ramp_edges(&client_pos, &target_pos, ramp) {
+                            collides = true;
+                            break;
+                        }
+                    }
+                }
+
+                if collides {
+                    target_pos = slide_player_along_obstacles(
+                        &walls_to_check,
+                        &map_layout.ramps,
+                        &client_pos,
+                        move_vel.x,
+                        move_vel.z,
+                        delta,
+                    );
+                }
             }
 
-            let target_height_on_ramp = height_on_ramp(&map_layout.ramps, target_pos.x, target_pos.z);
-            let target_has_roof = has_roof(&map_layout.roofs, target_pos.x, target_pos.z);
+            // Apply gravity and land on ramps, roofs or the ground (same as the server)
+            (target_pos.y, vertical_velocity.0) =
+                step_player_vertical(map_layout, &target_pos, vertical_velocity.0, delta);
 
-            if target_height_on_ramp > 0.0 {
-                target_pos.y = target_height_on_ramp;
-            } else if target_has_roof && close_to_roof(client_pos.y) {
-                target_pos.y = ROOF_HEIGHT;
-            } else {
-                target_pos.y = 0.0;
+            // The jump pad flight ends on landing
+            if launch.is_some() && player_is_grounded(map_layout, &target_pos, vertical_velocity.0) {
+                commands.entity(entity).remove::<JumpPadLaunch>();
             }
         }
 
@@ -231,7 +269,9 @@ pub fn players_movement_system(
 
     // Pass 2: Check player-player collisions and apply final positions
     for planned_move in &planned_moves {
-        let Ok((_, _, mut client_pos, _, mut flash_state, _, is_local)) = query.get_mut(planned_move.entity) else {
+        let Ok((_, _, mut client_pos, _, mut vertical_velocity, _, _, mut flash_state, _, is_local)) =
+            query.get_mut(planned_move.entity)
+        else {
             continue;
         };
 
@@ -239,7 +279,8 @@ pub fn players_movement_system(
 
         // Apply final position and feedback
         if hits_player {
-            // Stop for player collisions
+            // Stop for player collisions (e.g. landed on another player's head)
+            vertical_velocity.0 = 0.0;
             if is_local && let Some(state) = flash_state.as_mut() {
                 trigger_collision_feedback(&mut commands, &asset_server, &mut bump_flash_ui, state, false);
             }
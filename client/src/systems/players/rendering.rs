@@ -1,11 +1,12 @@
 use bevy::prelude::*;
 
 use super::components::CuboidShake;
-use crate::markers::*;
+use crate::{markers::*, resources::PlayerMap};
 use common::{
-    constants::PLAYER_HEIGHT,
+    constants::{ALWAYS_SHIELD, PLAYER_HEIGHT},
     markers::PlayerMarker,
-    protocol::{FaceDirection, Position},
+    power_ups::EffectKind,
+    protocol::{FaceDirection, PlayerId, Position},
 };
 
 // ============================================================================
@@ -39,6 +40,28 @@ pub fn players_face_to_transform_system(mut query: Query<(&FaceDirection, &mut T
     }
 }
 
+// Show the shield bubble of players with an active shield
+pub fn players_shield_visibility_system(
+    players: Res<PlayerMap>,
+    player_query: Query<&PlayerId, With<PlayerMarker>>,
+    mut shield_query: Query<(&ChildOf, &mut Visibility), With<ShieldMarker>>,
+) {
+    for (child_of, mut visibility) in &mut shield_query {
+        let has_shield = player_query
+            .get(child_of.parent())
+            .ok()
+            .and_then(|player_id| players.0.get(player_id))
+            .is_some_and(|info| ALWAYS_SHIELD || info.effects.contains(EffectKind::Shield));
+
+        // Inherit so the local player's bubble stays hidden along with its cuboid in first-person view
+        visibility.set_if_neq(if has_shield {
+            Visibility::Inherited
+        } else {
+            Visibility::Hidden
+        });
+    }
+}
+
 // ============================================================================
 // Billboard System
 // ============================================================================
@@ -3,103 +3,20 @@ use bevy::{
     prelude::*,
 };
 
-use crate::{markers::LocalPlayerMarker, resources::PlayerMap};
+use crate::{
+    constants::{EXPLOSION_ALPHA, EXPLOSION_START_SCALE},
+    spawning::ExplosionFireball,
+};
 use common::{
-    collision::{Projectile, projectile_hits_sentry, sweep_projectile_vs_player},
-    constants::ALWAYS_SENTRY_HUNT,
-    markers::{PlayerMarker, ProjectileMarker, SentryMarker},
-    protocol::{FaceDirection, MapLayout, PlayerId, Position},
+    collision::Projectile,
+    markers::ProjectileMarker,
+    protocol::{MapLayout, Position},
 };
 
 // ============================================================================
 // Helper Functions
 // ============================================================================
 
-fn handle_sentry_collisions(
-    commands: &mut Commands,
-    asset_server: &AssetServer,
-    projectile_entity: Entity,
-    projectile: &Projectile,
-    projectile_pos: &Position,
-    shooter_id: &PlayerId,
-    delta: f32,
-    sentry_query: &Query<(&Position, &FaceDirection), With<SentryMarker>>,
-    players: &PlayerMap,
-) -> bool {
-    // Always check sentry collisions
-    for (sentry_pos, sentry_face_dir) in sentry_query.iter() {
-        if projectile_hits_sentry(projectile_pos, projectile, delta, sentry_pos, sentry_face_dir.0) {
-            // Check if shooter has sentry hunt power-up
-            let shooter_has_hunt = players
-                .0
-                .get(shooter_id)
-                .is_some_and(|info| ALWAYS_SENTRY_HUNT || info.sentry_hunt_power_up);
-
-            if shooter_has_hunt {
-                // With hunt power-up: play sentry hit sound
-                play_sound(
-                    commands,
-                    asset_server,
-                    "sounds/player_hits_sentry.wav",
-                    PlaybackSettings::DESPAWN,
-                );
-            } else {
-                // Without hunt power-up: play wall hit sound
-                play_sound(
-                    commands,
-                    asset_server,
-                    "sounds/projectile_hits_sentry_no_damage.ogg",
-                    PlaybackSettings {
-                        mode: PlaybackMode::Despawn,
-                        volume: Volume::Linear(0.2),
-                        ..default()
-                    },
-                );
-            }
-
-            commands.entity(projectile_entity).despawn();
-            return true;
-        }
-    }
-
-    false
-}
-
-fn handle_player_collisions(
-    commands: &mut Commands,
-    asset_server: &AssetServer,
-    projectile_entity: Entity,
-    projectile: &Projectile,
-    projectile_pos: &Position,
-    delta: f32,
-    player_query: &Query<(Entity, &Position, &FaceDirection, Has<LocalPlayerMarker>), With<PlayerMarker>>,
-) -> bool {
-    for (_player_entity, player_pos, face_dir, is_local_player) in player_query.iter() {
-        if sweep_projectile_vs_player(projectile_pos, projectile, delta, player_pos, face_dir.0).is_some() {
-            play_sound(
-                commands,
-                asset_server,
-                "sounds/projectile_hits_player.ogg",
-                PlaybackSettings::DESPAWN,
-            );
-
-            if is_local_player {
-                play_sound(
-                    commands,
-                    asset_server,
-                    "sounds/player_gets_hit.ogg",
-                    PlaybackSettings::DESPAWN,
-                );
-            }
-
-            commands.entity(projectile_entity).despawn();
-            return true;
-        }
-    }
-
-    false
-}
-
 fn play_sound(
     commands: &mut Commands,
     asset_server: &AssetServer,
@@ -113,20 +30,19 @@ fn play_sound(
 // Projectiles Movement System
 // ============================================================================
 
+// Move projectiles and bounce them off the map geometry. Hits on players and sentries are decided by the server,
+// which despawns the projectile with an `SProjectileDespawn`.
 pub fn projectiles_movement_system(
     mut commands: Commands,
     time: Res<Time>,
     asset_server: Res<AssetServer>,
-    mut projectile_query: Query<(Entity, &mut Transform, &mut Projectile, &PlayerId), With<ProjectileMarker>>,
-    player_query: Query<(Entity, &Position, &FaceDirection, Has<LocalPlayerMarker>), With<PlayerMarker>>,
-    sentry_query: Query<(&Position, &FaceDirection), With<SentryMarker>>,
-    players: Res<PlayerMap>,
+    mut projectile_query: Query<(Entity, &mut Transform, &mut Projectile), With<ProjectileMarker>>,
     map_layout: Option<Res<MapLayout>>,
 ) {
     let delta = time.delta_secs();
     let map_layout = map_layout.as_deref();
 
-    for (projectile_entity, mut projectile_transform, mut projectile, shooter_id) in &mut projectile_query {
+    for (projectile_entity, mut projectile_transform, mut projectile) in &mut projectile_query {
         // Check lifetime and despawn if expired
         projectile.lifetime.tick(time.delta());
         if projectile.lifetime.is_finished() {
@@ -149,39 +65,14 @@ pub fn projectiles_movement_system(
             delta,
             map_layout,
         ) {
-            pos_after_bounce
-        } else {
-            // No wall collision, check sentry collisions first
-            if handle_sentry_collisions(
-                &mut commands,
-                asset_server.as_ref(),
-                projectile_entity,
-                &projectile,
-                &projectile_pos,
-                shooter_id,
-                delta,
-                &sentry_query,
-                &players,
-            ) {
-                // Hit a sentry, projectile was despawned
-                continue;
-            }
-
-            // Check player collisions
-            if handle_player_collisions(
-                &mut commands,
-                asset_server.as_ref(),
-                projectile_entity,
-                &projectile,
-                &projectile_pos,
-                delta,
-                &player_query,
-            ) {
-                // Hit a player, projectile was despawned
+            // Non-bouncing projectiles stop at the first obstacle
+            if !projectile.bounces() {
+                commands.entity(projectile_entity).despawn();
                 continue;
             }
-
-            // No collisions, move normally
+            pos_after_bounce
+        } else {
+            // No geometry collision, move normally
             Position {
                 x: projectile.velocity.x.mul_add(delta, projectile_pos.x),
                 y: projectile.velocity.y.mul_add(delta, projectile_pos.y),
@@ -282,3 +173,34 @@ fn handle_wall_collisions(
 
     result_pos
 }
+
+// ============================================================================
+// Explosions Animation System
+// ============================================================================
+
+// Grow explosion fireballs to their blast radius while fading them out, then remove them
+pub fn explosions_animation_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut query: Query<(
+        Entity,
+        &mut Transform,
+        &mut ExplosionFireball,
+        &MeshMaterial3d<StandardMaterial>,
+    )>,
+) {
+    for (entity, mut transform, mut fireball, material_handle) in &mut query {
+        fireball.timer.tick(time.delta());
+        if fireball.timer.is_finished() {
+            commands.entity(entity).despawn();
+            continue;
+        }
+
+        let progress = fireball.timer.fraction();
+        transform.scale = Vec3::splat(fireball.radius * EXPLOSION_START_SCALE.lerp(1.0, progress));
+        if let Some(material) = materials.get_mut(&material_handle.0) {
+            material.base_color.set_alpha(EXPLOSION_ALPHA * (1.0 - progress));
+        }
+    }
+}
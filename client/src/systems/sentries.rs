@@ -4,6 +4,7 @@ use super::network::ServerReconciliation;
 use common::{
     collision::slide_sentry_along_obstacles,
     constants::{SENTRY_HEIGHT, UPDATE_BROADCAST_INTERVAL},
+    map::{floor_height_at, walls_at_height},
     markers::SentryMarker,
     protocol::{FaceDirection, MapLayout, Position, Velocity},
 };
@@ -90,14 +91,12 @@ fn apply_sentry_wall_sliding(
         return *target_pos;
     };
 
-    slide_sentry_along_obstacles(
-        &map_layout.lower_walls,
-        &map_layout.ramps,
-        current_pos,
-        velocity.x,
-        velocity.z,
-        delta,
-    )
+    // Slide against the walls on the sentry's level, then follow the floor (e.g. up a ramp)
+    let walls = walls_at_height(map_layout, current_pos.y, false);
+    let mut final_pos =
+        slide_sentry_along_obstacles(&walls, &map_layout.ramps, current_pos, velocity.x, velocity.z, delta);
+    final_pos.y = floor_height_at(map_layout, final_pos.x, final_pos.z, current_pos.y);
+    final_pos
 }
 
 // ============================================================================
@@ -110,7 +109,7 @@ pub fn sentries_transform_sync_system(
 ) {
     for (pos, face_dir, mut transform) in &mut sentry_query {
         transform.translation.x = pos.x;
-        transform.translation.y = SENTRY_HEIGHT / 2.0; // Sentry center at correct height
+        transform.translation.y = pos.y + SENTRY_HEIGHT / 2.0; // Sentry center at correct height
         transform.translation.z = pos.z;
         transform.rotation = Quat::from_rotation_y(face_dir.0);
     }
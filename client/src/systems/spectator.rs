@@ -0,0 +1,171 @@
+use bevy::{
+    input::mouse::MouseMotion,
+    prelude::*,
+    window::{CursorGrabMode, CursorOptions},
+};
+
+use super::cameras::apply_topdown_camera;
+use crate::{
+    constants::*,
+    markers::MainCameraMarker,
+    resources::{CameraViewMode, InputSettings, PlayerMap, Spectator},
+};
+use common::{
+    constants::{PLAYER_EYE_HEIGHT_RATIO, PLAYER_HEIGHT},
+    markers::PlayerMarker,
+    protocol::{FaceDirection, PlayerId, Position},
+};
+
+// ============================================================================
+// Spectator Input Systems
+// ============================================================================
+
+// Cycle through the players to follow with Q and E (in player list order), switching to the follow camera
+pub fn spectator_follow_cycle_system(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    players: Res<PlayerMap>,
+    mut spectator: ResMut<Spectator>,
+    mut view_mode: ResMut<CameraViewMode>,
+) {
+    let forward = keyboard.just_pressed(KeyCode::KeyE);
+    if !forward && !keyboard.just_pressed(KeyCode::KeyQ) {
+        return;
+    }
+
+    let mut player_ids: Vec<PlayerId> = players.0.keys().copied().collect();
+    if player_ids.is_empty() {
+        return;
+    }
+    player_ids.sort_by_key(|player_id| player_id.0);
+
+    let count = player_ids.len();
+    let current = spectator
+        .followed
+        .and_then(|followed| player_ids.iter().position(|player_id| *player_id == followed));
+    let next = match current {
+        Some(index) if forward => (index + 1) % count,
+        Some(index) => (index + count - 1) % count,
+        None => 0,
+    };
+
+    spectator.followed = Some(player_ids[next]);
+    *view_mode = CameraViewMode::Follow;
+}
+
+// Fly the free camera with WASD, Space and C (up and down) and the mouse, faster while Shift is held
+pub fn spectator_free_fly_system(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    mut mouse_motion: MessageReader<MouseMotion>,
+    cursor_options: Single<&CursorOptions>,
+    time: Res<Time>,
+    input_settings: Res<InputSettings>,
+    view_mode: Res<CameraViewMode>,
+    mut camera: Single<&mut Transform, (With<Camera3d>, With<MainCameraMarker>)>,
+) {
+    if *view_mode != CameraViewMode::FreeFly || cursor_options.grab_mode == CursorGrabMode::None {
+        // Drain pending mouse events so the view doesn't jump later
+        for _ in mouse_motion.read() {}
+        return;
+    }
+
+    // Look around
+    let pitch_sign = if input_settings.invert_pitch {
+        MOUSE_SENSITIVITY
+    } else {
+        -MOUSE_SENSITIVITY
+    };
+    let (mut yaw, mut pitch, _roll) = camera.rotation.to_euler(EulerRot::YXZ);
+    for motion in mouse_motion.read() {
+        yaw = motion.delta.x.mul_add(-MOUSE_SENSITIVITY, yaw);
+        pitch = motion.delta.y.mul_add(pitch_sign, pitch);
+    }
+    camera.rotation = Quat::from_euler(EulerRot::YXZ, yaw, pitch.clamp(-MAX_PITCH, MAX_PITCH), 0.0);
+
+    // Move along the view direction
+    let mut direction = Vec3::ZERO;
+    if keyboard.pressed(KeyCode::KeyW) {
+        direction += *camera.forward();
+    }
+    if keyboard.pressed(KeyCode::KeyS) {
+        direction -= *camera.forward();
+    }
+    if keyboard.pressed(KeyCode::KeyD) {
+        direction += *camera.right();
+    }
+    if keyboard.pressed(KeyCode::KeyA) {
+        direction -= *camera.right();
+    }
+    if keyboard.pressed(KeyCode::Space) {
+        direction += Vec3::Y;
+    }
+    if keyboard.pressed(KeyCode::KeyC) {
+        direction -= Vec3::Y;
+    }
+
+    let speed = if keyboard.any_pressed([KeyCode::ShiftLeft, KeyCode::ShiftRight]) {
+        SPECTATOR_FLY_SPEED * SPECTATOR_FLY_FAST_MULTIPLIER
+    } else {
+        SPECTATOR_FLY_SPEED
+    };
+    camera.translation += direction.normalize_or_zero() * (speed * time.delta_secs());
+}
+
+// ============================================================================
+// Spectator Camera System
+// ============================================================================
+
+// Place the spectator camera for the follow and top-down views (the free-fly camera is moved by
+// `spectator_free_fly_system`). The follow camera moves on to another player when the followed one leaves.
+pub fn spectator_camera_sync_system(
+    players: Res<PlayerMap>,
+    player_query: Query<(&Position, &FaceDirection), With<PlayerMarker>>,
+    mut spectator: ResMut<Spectator>,
+    view_mode: Res<CameraViewMode>,
+    camera: Single<(&mut Transform, &mut Projection), (With<Camera3d>, With<MainCameraMarker>)>,
+) {
+    let (mut camera_transform, mut projection) = camera.into_inner();
+
+    match *view_mode {
+        CameraViewMode::FreeFly => {
+            // Start above the arena
+            if spectator.is_added() {
+                camera_transform.translation = Vec3::new(0.0, SPECTATOR_START_HEIGHT, TOPDOWN_CAMERA_Z_OFFSET);
+                camera_transform.look_at(Vec3::ZERO, Vec3::Y);
+            }
+            if let Projection::Perspective(persp) = projection.as_mut() {
+                persp.fov = FPV_CAMERA_FOV_DEGREES.to_radians();
+            }
+        }
+        CameraViewMode::Follow => {
+            let followed = spectator
+                .followed
+                .filter(|player_id| players.0.contains_key(player_id))
+                .or_else(|| players.0.keys().min_by_key(|player_id| player_id.0).copied());
+            if spectator.followed != followed {
+                spectator.followed = followed;
+            }
+
+            let Some((pos, face_dir)) = followed
+                .and_then(|player_id| players.0.get(&player_id))
+                .and_then(|player_info| player_query.get(player_info.entity).ok())
+            else {
+                return;
+            };
+
+            // Look over the followed player's shoulder
+            let eye = Vec3::new(pos.x, PLAYER_HEIGHT.mul_add(PLAYER_EYE_HEIGHT_RATIO, pos.y), pos.z);
+            let forward = Vec3::new(face_dir.0.sin(), 0.0, face_dir.0.cos());
+            camera_transform.translation =
+                eye - forward * SPECTATOR_FOLLOW_DISTANCE + Vec3::Y * SPECTATOR_FOLLOW_HEIGHT;
+            camera_transform.look_at(eye, Vec3::Y);
+
+            if let Projection::Perspective(persp) = projection.as_mut() {
+                persp.fov = FPV_CAMERA_FOV_DEGREES.to_radians();
+            }
+        }
+        CameraViewMode::TopDown => {
+            apply_topdown_camera(&mut camera_transform, &mut projection, view_mode.is_changed());
+        }
+        CameraViewMode::FirstPerson => {} // Players only
+    }
+}
@@ -2,15 +2,68 @@ use bevy::prelude::*;
 use std::time::Duration;
 
 use crate::{
+    constants::{POWER_UP_BAR_HEIGHT, POWER_UP_BAR_WIDTH},
     markers::*,
-    resources::{CameraViewMode, FpsMeasurement, MyPlayerId, PlayerInfo, PlayerMap, RoundTripTime},
-    spawning::item_type_color,
+    resources::{
+        CameraViewMode, FpsMeasurement, Leaderboard, LocalPlayerInfo, MatchSummary, MyPlayerId, PlayerInfo, PlayerMap,
+        Reconnecting, RoundTripTime, Scoreboard, Spectator, SpectatorList,
+    },
+    spawning::effect_color,
+};
+use common::{
+    power_ups::{EffectKind, POWER_UPS},
+    protocol::PlayerId,
+    stats::{LeaderboardEntry, ScoreboardEntry},
 };
-use common::protocol::{ItemType, PlayerId};
 
 // Background color for local player in the player list
 const LOCAL_PLAYER_BG_COLOR: Color = Color::srgba(0.8, 0.8, 0.0, 0.3);
 
+// Header color of the spectator list
+const SPECTATOR_HEADER_COLOR: Color = Color::srgb(0.7, 0.7, 0.7);
+
+// Background color of the power-up countdown bars
+const POWER_UP_BAR_BG_COLOR: Color = Color::srgba(0.0, 0.0, 0.0, 0.5);
+
+// Background color of the scoreboard, post-match summary and leaderboard panels
+const STATS_PANEL_BG_COLOR: Color = Color::srgba(0.0, 0.0, 0.0, 0.75);
+
+// Columns of the scoreboard and post-match summary tables, with their widths in pixels
+const STATS_COLUMNS: [(&str, f32); 11] = [
+    ("Player", 160.0),
+    ("Score", 60.0),
+    ("Shots", 60.0),
+    ("Hits", 50.0),
+    ("Acc.", 55.0),
+    ("Taken", 60.0),
+    ("Cookies", 75.0),
+    ("Power-ups", 90.0),
+    ("Stuns", 60.0),
+    ("Hunted", 70.0),
+    ("Distance", 85.0),
+];
+
+// Columns of the all-time leaderboard table, with their widths in pixels
+const LEADERBOARD_COLUMNS: [(&str, f32); 6] = [
+    ("#", 35.0),
+    ("Player", 160.0),
+    ("Games", 65.0),
+    ("Total", 65.0),
+    ("Best", 55.0),
+    ("Acc.", 55.0),
+];
+
+// ============================================================================
+// Components
+// ============================================================================
+
+// Fill of a power-up countdown bar, shrinking as the effect runs out
+#[derive(Component)]
+pub struct PowerUpBarFill {
+    pub effect: EffectKind,
+    pub expires_at: f32, // Local time (seconds)
+}
+
 // ============================================================================
 // UI Setup System
 // ============================================================================
@@ -109,6 +162,54 @@ pub fn setup_ui_system(mut commands: Commands) {
         },
     ));
 
+    // Create selected weapon display above RTT
+    commands.spawn((
+        WeaponUIMarker,
+        Text::new(""),
+        TextFont {
+            font_size: 20.0,
+            ..default()
+        },
+        TextColor(Color::WHITE),
+        Node {
+            position_type: PositionType::Absolute,
+            left: Val::Px(10.0),
+            bottom: Val::Px(70.0),
+            ..default()
+        },
+    ));
+
+    // Create power-up countdown bars above the shield charges display
+    commands.spawn((
+        PowerUpBarsUIMarker,
+        Node {
+            position_type: PositionType::Absolute,
+            right: Val::Px(10.0),
+            bottom: Val::Px(40.0),
+            flex_direction: FlexDirection::Column,
+            row_gap: Val::Px(4.0),
+            ..default()
+        },
+    ));
+
+    // Create shield charges display in lower right corner (hidden without a shield)
+    commands.spawn((
+        ShieldChargesUIMarker,
+        Text::new(""),
+        TextFont {
+            font_size: 20.0,
+            ..default()
+        },
+        TextColor(effect_color(EffectKind::Shield)),
+        Node {
+            position_type: PositionType::Absolute,
+            right: Val::Px(10.0),
+            bottom: Val::Px(10.0),
+            ..default()
+        },
+        Visibility::Hidden,
+    ));
+
     // Create bump flash overlay (invisible by default, shown on wall collision)
     commands.spawn((
         BumpFlashUIMarker,
@@ -123,6 +224,106 @@ pub fn setup_ui_system(mut commands: Commands) {
         BackgroundColor(Color::srgba(1.0, 1.0, 1.0, 0.0)), // Transparent by default (white)
         Visibility::Hidden,                                // Start hidden
     ));
+
+    // Create scoreboard (shown while Tab is held) and post-match summary panels in the center
+    commands.spawn((
+        ScoreboardUIMarker,
+        stats_panel_node(&STATS_COLUMNS),
+        BackgroundColor(STATS_PANEL_BG_COLOR),
+        Visibility::Hidden,
+    ));
+    commands.spawn((
+        MatchSummaryUIMarker,
+        stats_panel_node(&STATS_COLUMNS),
+        BackgroundColor(STATS_PANEL_BG_COLOR),
+        Visibility::Hidden,
+    ));
+
+    // Create all-time leaderboard panel (toggled with L) in the center
+    commands.spawn((
+        LeaderboardUIMarker,
+        stats_panel_node(&LEADERBOARD_COLUMNS),
+        BackgroundColor(STATS_PANEL_BG_COLOR),
+        Visibility::Hidden,
+    ));
+
+    // Create reconnecting notice at the top center (shown while the connection is down)
+    commands
+        .spawn((
+            ReconnectingUIMarker,
+            Node {
+                position_type: PositionType::Absolute,
+                width: Val::Percent(100.0),
+                top: Val::Px(60.0),
+                justify_content: JustifyContent::Center,
+                ..default()
+            },
+            Visibility::Hidden,
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::new("Connection lost - reconnecting..."),
+                TextFont {
+                    font_size: 28.0,
+                    ..default()
+                },
+                TextColor(Color::srgb(1.0, 0.3, 0.3)),
+            ));
+        });
+
+    // Create spectator list UI in the upper right corner (hidden while nobody is spectating)
+    commands.spawn((
+        SpectatorListUIMarker,
+        Node {
+            position_type: PositionType::Absolute,
+            right: Val::Px(10.0),
+            top: Val::Px(10.0),
+            flex_direction: FlexDirection::Column,
+            row_gap: Val::Px(5.0),
+            ..default()
+        },
+        Visibility::Hidden,
+    ));
+
+    // Create spectator camera notice at the top center (spectators only)
+    commands
+        .spawn(Node {
+            position_type: PositionType::Absolute,
+            width: Val::Percent(100.0),
+            top: Val::Px(10.0),
+            justify_content: JustifyContent::Center,
+            ..default()
+        })
+        .with_children(|parent| {
+            parent.spawn((
+                SpectatorUIMarker,
+                Text::new(""),
+                TextFont {
+                    font_size: 20.0,
+                    ..default()
+                },
+                TextColor(Color::WHITE),
+                Visibility::Hidden,
+            ));
+        });
+}
+
+fn stats_panel_node(columns: &[(&str, f32)]) -> Node {
+    let width: f32 = columns.iter().map(|(_, width)| width).sum();
+    Node {
+        position_type: PositionType::Absolute,
+        left: Val::Percent(50.0),
+        top: Val::Percent(50.0),
+        margin: UiRect {
+            left: Val::Px(-(width / 2.0 + 15.0)), // Half the width of all columns and the padding
+            top: Val::Px(-200.0),
+            ..default()
+        },
+        flex_direction: FlexDirection::Column,
+        row_gap: Val::Px(4.0),
+        padding: UiRect::all(Val::Px(15.0)),
+        ..default()
+    }
 }
 
 // ============================================================================
@@ -163,6 +364,102 @@ pub fn ui_fps_system(
     }
 }
 
+// Update the local player's selected weapon display
+pub fn ui_weapon_system(local_player_info: Res<LocalPlayerInfo>, mut query: Single<&mut Text, With<WeaponUIMarker>>) {
+    if !local_player_info.is_changed() {
+        return;
+    }
+
+    let weapon = local_player_info.weapon;
+    query.0 = format!("Weapon: {} [{}]", weapon.def().name, weapon as usize + 1);
+}
+
+// Update the local player's shield charges display
+pub fn ui_shield_charges_system(
+    players: Res<PlayerMap>,
+    my_player_id: Option<Res<MyPlayerId>>,
+    query: Single<(&mut Text, &mut Visibility), With<ShieldChargesUIMarker>>,
+) {
+    if !players.is_changed() {
+        return;
+    }
+
+    let charges = my_player_id
+        .and_then(|id| players.0.get(&id.0))
+        .map_or(0, |info| info.shield_charges);
+
+    let (mut text, mut visibility) = query.into_inner();
+    if charges > 0 {
+        text.0 = format!("Shield: {charges}");
+        *visibility = Visibility::Visible;
+    } else {
+        *visibility = Visibility::Hidden;
+    }
+}
+
+// Show a countdown bar for each of the local player's timed power-ups
+pub fn ui_power_up_bars_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    players: Res<PlayerMap>,
+    my_player_id: Option<Res<MyPlayerId>>,
+    bars_ui: Single<Entity, With<PowerUpBarsUIMarker>>,
+    children_query: Query<&Children>,
+    mut fill_query: Query<(&PowerUpBarFill, &mut Node)>,
+) {
+    let now = time.elapsed_secs();
+
+    // Rebuild the bars when the effects changed
+    if players.is_changed() {
+        if let Ok(children) = children_query.get(*bars_ui) {
+            for &child in children {
+                commands.entity(child).despawn();
+            }
+        }
+
+        let expiries = my_player_id
+            .and_then(|id| players.0.get(&id.0))
+            .map(|info| info.effect_expiries.clone())
+            .unwrap_or_default();
+
+        for (effect, expires_at) in expiries {
+            let bar = commands
+                .spawn((
+                    Node {
+                        width: Val::Px(POWER_UP_BAR_WIDTH),
+                        height: Val::Px(POWER_UP_BAR_HEIGHT),
+                        ..default()
+                    },
+                    BackgroundColor(POWER_UP_BAR_BG_COLOR),
+                ))
+                .with_children(|bar| {
+                    bar.spawn((
+                        PowerUpBarFill { effect, expires_at },
+                        Node {
+                            width: Val::Percent(bar_fill_percent(effect, expires_at, now)),
+                            height: Val::Percent(100.0),
+                            ..default()
+                        },
+                        BackgroundColor(effect_color(effect)),
+                    ));
+                })
+                .id();
+            commands.entity(*bars_ui).add_child(bar);
+        }
+        return;
+    }
+
+    // Otherwise just let the bars run down
+    for (fill, mut node) in &mut fill_query {
+        node.width = Val::Percent(bar_fill_percent(fill.effect, fill.expires_at, now));
+    }
+}
+
+fn bar_fill_percent(effect: EffectKind, expires_at: f32, now: f32) -> f32 {
+    let remaining = (expires_at - now).max(0.0);
+    (remaining / effect.max_duration()).min(1.0) * 100.0
+}
+
 // Toggle crosshair visibility based on camera view mode
 pub fn ui_toggle_crosshair_system(
     view_mode: Res<CameraViewMode>,
@@ -175,11 +472,125 @@ pub fn ui_toggle_crosshair_system(
     for mut visibility in &mut query {
         *visibility = match *view_mode {
             CameraViewMode::FirstPerson => Visibility::Visible,
-            CameraViewMode::TopDown => Visibility::Hidden,
+            CameraViewMode::TopDown | CameraViewMode::FreeFly | CameraViewMode::Follow => Visibility::Hidden,
         };
     }
 }
 
+// Show the reconnecting notice while the session is being resumed
+pub fn ui_reconnecting_system(
+    reconnecting: Res<Reconnecting>,
+    mut visibility: Single<&mut Visibility, With<ReconnectingUIMarker>>,
+) {
+    if !reconnecting.is_changed() {
+        return;
+    }
+
+    **visibility = if reconnecting.0 {
+        Visibility::Visible
+    } else {
+        Visibility::Hidden
+    };
+}
+
+// Show the spectator camera and the followed player (spectators only)
+pub fn ui_spectator_system(
+    spectator: Res<Spectator>,
+    view_mode: Res<CameraViewMode>,
+    players: Res<PlayerMap>,
+    notice: Single<(&mut Text, &mut Visibility), With<SpectatorUIMarker>>,
+) {
+    if !spectator.is_changed() && !view_mode.is_changed() && !players.is_changed() {
+        return;
+    }
+
+    let camera = match *view_mode {
+        CameraViewMode::Follow => spectator
+            .followed
+            .and_then(|player_id| players.0.get(&player_id))
+            .map_or_else(
+                || "nobody to follow".to_string(),
+                |info| format!("following {}", info.name),
+            ),
+        CameraViewMode::TopDown => "top-down".to_string(),
+        CameraViewMode::FirstPerson | CameraViewMode::FreeFly => "free camera".to_string(),
+    };
+
+    let (mut text, mut visibility) = notice.into_inner();
+    text.0 = format!("Spectating - {camera}   [V] camera  [Q/E] player");
+    visibility.set_if_neq(Visibility::Visible);
+}
+
+// Update spectator list UI with the names of all spectators
+pub fn ui_spectator_list_system(
+    mut commands: Commands,
+    spectators: Res<SpectatorList>,
+    my_player_id: Option<Res<MyPlayerId>>,
+    spectator_list_ui: Single<(Entity, &mut Visibility), With<SpectatorListUIMarker>>,
+    children_query: Query<&Children>,
+) {
+    if !spectators.is_changed() {
+        return;
+    }
+
+    let (spectator_list_entity, mut visibility) = spectator_list_ui.into_inner();
+    *visibility = if spectators.0.is_empty() {
+        Visibility::Hidden
+    } else {
+        Visibility::Visible
+    };
+
+    // Rebuild the entire list, like the player list
+    if let Ok(children) = children_query.get(spectator_list_entity) {
+        for &child in children {
+            commands.entity(child).despawn();
+        }
+    }
+
+    let local_player_id = my_player_id.as_ref().map(|id| id.0);
+    let mut ordered_children = Vec::with_capacity(spectators.0.len() + 1);
+    ordered_children.push(
+        commands
+            .spawn((
+                Text::new("Spectators"),
+                TextFont {
+                    font_size: 20.0,
+                    ..default()
+                },
+                TextColor(SPECTATOR_HEADER_COLOR),
+            ))
+            .id(),
+    );
+    for (player_id, name) in &spectators.0 {
+        let background_color = if local_player_id == Some(*player_id) {
+            LOCAL_PLAYER_BG_COLOR
+        } else {
+            Color::NONE
+        };
+        ordered_children.push(
+            commands
+                .spawn((
+                    Text::new(name),
+                    TextFont {
+                        font_size: 18.0,
+                        ..default()
+                    },
+                    TextColor(Color::WHITE),
+                    Node {
+                        padding: UiRect::all(Val::Px(5.0)),
+                        ..default()
+                    },
+                    BackgroundColor(background_color),
+                ))
+                .id(),
+        );
+    }
+
+    commands
+        .entity(spectator_list_entity)
+        .replace_children(&ordered_children);
+}
+
 // Update player list UI with all players and their hit counts
 pub fn ui_player_list_system(
     mut commands: Commands,
@@ -275,40 +686,7 @@ fn spawn_player_entry(
             ));
 
             // Add power-up indicators
-            if player_info.speed_power_up {
-                row.spawn((
-                    Node {
-                        width: Val::Px(12.0),
-                        height: Val::Px(12.0),
-                        align_self: AlignSelf::Center,
-                        ..default()
-                    },
-                    BackgroundColor(item_type_color(ItemType::SpeedPowerUp)),
-                ));
-            }
-            if player_info.multi_shot_power_up {
-                row.spawn((
-                    Node {
-                        width: Val::Px(12.0),
-                        height: Val::Px(12.0),
-                        align_self: AlignSelf::Center,
-                        ..default()
-                    },
-                    BackgroundColor(item_type_color(ItemType::MultiShotPowerUp)),
-                ));
-            }
-            if player_info.phasing_power_up {
-                row.spawn((
-                    Node {
-                        width: Val::Px(12.0),
-                        height: Val::Px(12.0),
-                        align_self: AlignSelf::Center,
-                        ..default()
-                    },
-                    BackgroundColor(item_type_color(ItemType::PhasingPowerUp)),
-                ));
-            }
-            if player_info.sentry_hunt_power_up {
+            for effect in player_info.effects.iter() {
                 row.spawn((
                     Node {
                         width: Val::Px(12.0),
@@ -316,7 +694,7 @@ fn spawn_player_entry(
                         align_self: AlignSelf::Center,
                         ..default()
                     },
-                    BackgroundColor(item_type_color(ItemType::SentryHunterPowerUp)),
+                    BackgroundColor(effect_color(effect)),
                 ));
             }
         })
@@ -379,3 +757,288 @@ pub fn ui_stunned_blink_system(
         }
     }
 }
+
+// ============================================================================
+// Scoreboard, Match Summary and Leaderboard Systems
+// ============================================================================
+
+// Show the scoreboard of the current match while Tab is held
+pub fn ui_scoreboard_system(
+    mut commands: Commands,
+    keyboard: Res<ButtonInput<KeyCode>>,
+    scoreboard: Res<Scoreboard>,
+    my_player_id: Option<Res<MyPlayerId>>,
+    panel: Single<(Entity, &mut Visibility), With<ScoreboardUIMarker>>,
+    children_query: Query<&Children>,
+) {
+    let (panel, mut visibility) = panel.into_inner();
+    visibility.set_if_neq(if keyboard.pressed(KeyCode::Tab) {
+        Visibility::Visible
+    } else {
+        Visibility::Hidden
+    });
+
+    if !scoreboard.is_changed() {
+        return;
+    }
+
+    let remaining = scoreboard.remaining.max(0.0) as u32;
+    let title = format!("Match ends in {}:{:02}", remaining / 60, remaining % 60);
+    let local_player_id = my_player_id.as_ref().map(|id| id.0);
+    rebuild_stats_table(
+        &mut commands,
+        panel,
+        &[title],
+        &scoreboard.entries,
+        local_player_id,
+        &children_query,
+    );
+}
+
+// Show the final scoreboard of the last match for a while after it ended
+pub fn ui_match_summary_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    match_summary: Res<MatchSummary>,
+    my_player_id: Option<Res<MyPlayerId>>,
+    panel: Single<(Entity, &mut Visibility), With<MatchSummaryUIMarker>>,
+    children_query: Query<&Children>,
+) {
+    let (panel, mut visibility) = panel.into_inner();
+    visibility.set_if_neq(if time.elapsed_secs() < match_summary.hide_at {
+        Visibility::Visible
+    } else {
+        Visibility::Hidden
+    });
+
+    if !match_summary.is_changed() {
+        return;
+    }
+
+    let local_player_id = my_player_id.as_ref().map(|id| id.0);
+    let mut lines = vec![match_summary.entries.first().map_or_else(
+        || "Match over".to_string(),
+        |winner| format!("Match over - {} wins!", winner.name),
+    )];
+
+    // Break down the local player's power-ups by type
+    if let Some(entry) = match_summary
+        .entries
+        .iter()
+        .find(|entry| Some(entry.id) == local_player_id)
+    {
+        let power_ups: Vec<String> = POWER_UPS
+            .iter()
+            .zip(entry.stats.power_ups)
+            .filter(|(_, count)| *count > 0)
+            .map(|(def, count)| format!("{} x{count}", def.name))
+            .collect();
+        if !power_ups.is_empty() {
+            lines.push(format!("Your power-ups: {}", power_ups.join(", ")));
+        }
+    }
+
+    rebuild_stats_table(
+        &mut commands,
+        panel,
+        &lines,
+        &match_summary.entries,
+        local_player_id,
+        &children_query,
+    );
+}
+
+// Show the all-time leaderboard while toggled on (refreshed from the server each time it opens)
+pub fn ui_leaderboard_system(
+    mut commands: Commands,
+    leaderboard: Res<Leaderboard>,
+    my_player_id: Option<Res<MyPlayerId>>,
+    players: Res<PlayerMap>,
+    panel: Single<(Entity, &mut Visibility), With<LeaderboardUIMarker>>,
+    children_query: Query<&Children>,
+) {
+    let (panel, mut visibility) = panel.into_inner();
+    visibility.set_if_neq(if leaderboard.visible {
+        Visibility::Visible
+    } else {
+        Visibility::Hidden
+    });
+
+    if !leaderboard.is_changed() {
+        return;
+    }
+
+    // Despawn all existing children first
+    if let Ok(children) = children_query.get(panel) {
+        for &child in children {
+            commands.entity(child).despawn();
+        }
+    }
+
+    // Entries are keyed by name, so highlight the one matching the local player's name
+    let local_player_name = my_player_id
+        .as_ref()
+        .and_then(|id| players.0.get(&id.0))
+        .map(|info| info.name.as_str());
+
+    let mut ordered_children = Vec::with_capacity(leaderboard.entries.len() + 2);
+    ordered_children.push(
+        commands
+            .spawn((
+                Text::new("All-time leaderboard"),
+                TextFont {
+                    font_size: 24.0,
+                    ..default()
+                },
+                TextColor(Color::WHITE),
+            ))
+            .id(),
+    );
+
+    let header = LEADERBOARD_COLUMNS.map(|(name, _)| name.to_string());
+    ordered_children.push(spawn_stats_row(
+        &mut commands,
+        &header,
+        &LEADERBOARD_COLUMNS,
+        Color::srgb(0.7, 0.7, 0.7),
+        Color::NONE,
+    ));
+
+    for (rank, entry) in leaderboard.entries.iter().enumerate() {
+        let background_color = if local_player_name == Some(entry.name.as_str()) {
+            LOCAL_PLAYER_BG_COLOR
+        } else {
+            Color::NONE
+        };
+        ordered_children.push(spawn_stats_row(
+            &mut commands,
+            &leaderboard_cells(rank + 1, entry),
+            &LEADERBOARD_COLUMNS,
+            Color::WHITE,
+            background_color,
+        ));
+    }
+
+    commands.entity(panel).replace_children(&ordered_children);
+}
+
+fn rebuild_stats_table(
+    commands: &mut Commands,
+    panel: Entity,
+    lines: &[String],
+    entries: &[ScoreboardEntry],
+    local_player_id: Option<PlayerId>,
+    children_query: &Query<&Children>,
+) {
+    // Despawn all existing children first
+    if let Ok(children) = children_query.get(panel) {
+        for &child in children {
+            commands.entity(child).despawn();
+        }
+    }
+
+    let mut ordered_children = Vec::with_capacity(lines.len() + entries.len() + 1);
+    for line in lines {
+        ordered_children.push(
+            commands
+                .spawn((
+                    Text::new(line.clone()),
+                    TextFont {
+                        font_size: 24.0,
+                        ..default()
+                    },
+                    TextColor(Color::WHITE),
+                ))
+                .id(),
+        );
+    }
+
+    let header = STATS_COLUMNS.map(|(name, _)| name.to_string());
+    ordered_children.push(spawn_stats_row(
+        commands,
+        &header,
+        &STATS_COLUMNS,
+        Color::srgb(0.7, 0.7, 0.7),
+        Color::NONE,
+    ));
+
+    for entry in entries {
+        let background_color = if local_player_id == Some(entry.id) {
+            LOCAL_PLAYER_BG_COLOR
+        } else {
+            Color::NONE
+        };
+        ordered_children.push(spawn_stats_row(
+            commands,
+            &stats_cells(entry),
+            &STATS_COLUMNS,
+            Color::WHITE,
+            background_color,
+        ));
+    }
+
+    commands.entity(panel).replace_children(&ordered_children);
+}
+
+fn stats_cells(entry: &ScoreboardEntry) -> [String; STATS_COLUMNS.len()] {
+    let stats = &entry.stats;
+    [
+        entry.name.clone(),
+        format_signed_hits(entry.score),
+        stats.shots_fired.to_string(),
+        stats.hits_landed.to_string(),
+        format!("{:.0}%", stats.accuracy() * 100.0),
+        stats.hits_taken.to_string(),
+        stats.cookies_eaten.to_string(),
+        stats.power_ups_collected().to_string(),
+        stats.sentry_stuns.to_string(),
+        stats.sentries_hunted.to_string(),
+        format!("{:.0} m", stats.distance),
+    ]
+}
+
+fn leaderboard_cells(rank: usize, entry: &LeaderboardEntry) -> [String; LEADERBOARD_COLUMNS.len()] {
+    [
+        rank.to_string(),
+        entry.name.clone(),
+        entry.games_played.to_string(),
+        entry.total_score.to_string(),
+        format_signed_hits(entry.best_score),
+        format!("{:.0}%", entry.accuracy() * 100.0),
+    ]
+}
+
+fn spawn_stats_row(
+    commands: &mut Commands,
+    cells: &[String],
+    columns: &[(&str, f32)],
+    text_color: Color,
+    background_color: Color,
+) -> Entity {
+    commands
+        .spawn((
+            Node {
+                flex_direction: FlexDirection::Row,
+                padding: UiRect::all(Val::Px(2.0)),
+                ..default()
+            },
+            BackgroundColor(background_color),
+        ))
+        .with_children(|row| {
+            for (cell, (_, width)) in cells.iter().zip(columns) {
+                row.spawn((
+                    Text::new(cell.clone()),
+                    TextFont {
+                        font_size: 18.0,
+                        ..default()
+                    },
+                    TextColor(text_color),
+                    Node {
+                        width: Val::Px(*width),
+                        ..default()
+                    },
+                ));
+            }
+        })
+        .id()
+}
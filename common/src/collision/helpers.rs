@@ -12,6 +12,16 @@ pub struct Collision {
     pub t: f32,
 }
 
+// Apply constant downward gravity to a vertical velocity (shared by projectiles and players).
+#[must_use]
+pub fn apply_gravity(vel_y: f32, gravity: f32, delta: f32) -> f32 {
+    if gravity > 0.0 {
+        gravity.mul_add(-delta, vel_y)
+    } else {
+        vel_y
+    }
+}
+
 // Check if two 1D ranges overlap.
 #[must_use]
 pub fn ranges_overlap_1d(a_min: f32, a_max: f32, b_min: f32, b_max: f32) -> bool {
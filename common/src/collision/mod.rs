@@ -5,8 +5,10 @@ pub mod projectiles;
 pub mod sentries;
 
 pub use players::{
-    slide_player_along_obstacles, sweep_player_vs_player, sweep_player_vs_ramp_edges, sweep_player_vs_roof,
-    sweep_player_vs_wall,
+    jump_pad_launch, player_is_grounded, slide_player_along_obstacles, step_player_vertical, sweep_player_vs_player,
+    sweep_player_vs_ramp_edges, sweep_player_vs_roof, sweep_player_vs_wall,
+};
+pub use projectiles::{
+    HitDirection, Projectile, explosion_knockback, projectile_hits_sentry, sweep_projectile_vs_player,
 };
-pub use projectiles::{Projectile, projectile_hits_sentry, sweep_projectile_vs_player};
 pub use sentries::{overlap_sentry_vs_player, slide_sentry_along_obstacles};
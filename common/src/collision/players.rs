@@ -1,8 +1,13 @@
-use super::helpers::{slide_along_axes, sweep_aabb_vs_aabb, sweep_aabb_vs_wall, sweep_ramp_edges, sweep_ramp_high_cap};
+use super::helpers::{
+    apply_gravity, slide_along_axes, sweep_aabb_vs_aabb, sweep_aabb_vs_wall, sweep_ramp_edges, sweep_ramp_high_cap,
+};
 use crate::{
-    constants::{PLAYER_DEPTH, PLAYER_HEIGHT, PLAYER_WIDTH, ROOF_HEIGHT, WALL_THICKNESS},
-    map::height_on_ramp,
-    protocol::{Position, Ramp, Roof, Wall},
+    constants::{
+        LEVEL_HEIGHT_TOLERANCE, PHYSICS_EPSILON, PLAYER_DEPTH, PLAYER_GRAVITY, PLAYER_HEIGHT, PLAYER_WIDTH,
+        WALL_THICKNESS,
+    },
+    map::floor_height_at,
+    protocol::{JumpPad, MapLayout, Position, Ramp, Roof, Velocity, Wall},
 };
 
 #[must_use]
@@ -22,8 +27,8 @@ pub fn sweep_player_vs_roof(start: &Position, end: &Position, roof: &Roof, radiu
         return false;
     }
 
-    let slab_bottom = ROOF_HEIGHT - roof.thickness;
-    let slab_top = ROOF_HEIGHT;
+    let slab_bottom = roof.y - roof.thickness;
+    let slab_top = roof.y;
 
     let seg_min_y = start.y.min(end.y);
     let seg_max_y = start.y.max(end.y);
@@ -45,10 +50,15 @@ pub fn sweep_player_vs_ramp_edges(start_pos: &Position, end_pos: &Position, ramp
     let half_z = PLAYER_DEPTH / 2.0;
     let edge_half = WALL_THICKNESS / 2.0;
 
-    let on_ground = start_pos.y <= 0.1;
+    // Ramps on other tiers (e.g. an upper ramp above a player on the ground) don't block
+    if !ramp.spans_height(start_pos.y) {
+        return false;
+    }
+
+    let on_ramp_floor = start_pos.y <= ramp.bounds_y().0 + 0.1;
 
     sweep_ramp_edges(start_pos, end_pos, ramp, half_x, half_z, edge_half)
-        || (on_ground && sweep_ramp_high_cap(start_pos, end_pos, ramp, half_x, half_z, edge_half))
+        || (on_ramp_floor && sweep_ramp_high_cap(start_pos, end_pos, ramp, half_x, half_z, edge_half))
 }
 
 #[must_use]
@@ -65,21 +75,16 @@ pub fn slide_player_along_obstacles(
         velocity_x,
         velocity_z,
         delta,
-        |dt| {
-            let x = velocity_x.mul_add(dt, current_pos.x);
-            Position {
-                x,
-                y: height_on_ramp(ramps, x, current_pos.z),
-                z: current_pos.z,
-            }
+        // Height is kept here and resolved afterwards by `step_player_vertical`
+        |dt| Position {
+            x: velocity_x.mul_add(dt, current_pos.x),
+            y: current_pos.y,
+            z: current_pos.z,
         },
-        |dt| {
-            let z = velocity_z.mul_add(dt, current_pos.z);
-            Position {
-                x: current_pos.x,
-                y: height_on_ramp(ramps, current_pos.x, z),
-                z,
-            }
+        |dt| Position {
+            x: current_pos.x,
+            y: current_pos.y,
+            z: velocity_z.mul_add(dt, current_pos.z),
         },
         |candidate| {
             walls.iter().any(|w| sweep_player_vs_wall(current_pos, candidate, w))
@@ -100,3 +105,44 @@ pub fn slide_player_along_obstacles(
 pub fn sweep_player_vs_player(start1: &Position, end1: &Position, start2: &Position, end2: &Position) -> bool {
     sweep_aabb_vs_aabb(start1, end1, start2, end2, PLAYER_WIDTH, PLAYER_DEPTH, PLAYER_HEIGHT)
 }
+
+// Whether a player at `pos` with the given vertical velocity is standing on the floor below them (and
+// therefore may jump).
+#[must_use]
+pub fn player_is_grounded(map_layout: &MapLayout, pos: &Position, vel_y: f32) -> bool {
+    let floor = floor_height_at(map_layout, pos.x, pos.z, pos.y);
+    vel_y.abs() < PHYSICS_EPSILON && (pos.y - floor).abs() < PHYSICS_EPSILON
+}
+
+// Advance a player's vertical motion by one step at the (already collision-checked) horizontal position of
+// `pos`. Grounded players follow the floor below them (e.g. down a ramp) unless it drops away, e.g. when
+// walking off a roof edge. Airborne players fall under gravity until they land on the highest floor below.
+// Returns the new height and vertical velocity.
+#[must_use]
+pub fn step_player_vertical(map_layout: &MapLayout, pos: &Position, vel_y: f32, delta: f32) -> (f32, f32) {
+    let floor = floor_height_at(map_layout, pos.x, pos.z, pos.y);
+
+    if vel_y.abs() < PHYSICS_EPSILON && pos.y - floor < LEVEL_HEIGHT_TOLERANCE {
+        return (floor, 0.0);
+    }
+
+    let vel_y = apply_gravity(vel_y, PLAYER_GRAVITY, delta);
+    let y = vel_y.mul_add(delta, pos.y);
+
+    if y <= floor { (floor, 0.0) } else { (y, vel_y) }
+}
+
+// Returns the take-off velocity if a grounded player at `pos` stands on a jump pad. The launched player follows
+// the pad's ballistic arc: horizontally at the returned (x, z) velocity, vertically via `step_player_vertical`.
+#[must_use]
+pub fn jump_pad_launch(map_layout: &MapLayout, pos: &Position, vel_y: f32) -> Option<Velocity> {
+    if !player_is_grounded(map_layout, pos, vel_y) || pos.y > LEVEL_HEIGHT_TOLERANCE {
+        return None;
+    }
+
+    map_layout
+        .jump_pads
+        .iter()
+        .find(|pad| pad.contains(pos.x, pos.z))
+        .map(JumpPad::launch_velocity)
+}
@@ -2,10 +2,11 @@ use bevy_ecs::prelude::*;
 use bevy_math::Vec3;
 use bevy_time::{Timer, TimerMode};
 
-use super::helpers::{Collision, sweep_point_vs_cuboid, sweep_slab_interval};
+use super::helpers::{Collision, apply_gravity, sweep_aabb_vs_wall, sweep_point_vs_cuboid, sweep_slab_interval};
 use crate::{
     constants::*,
-    protocol::{Position, Ramp, Roof, Wall},
+    protocol::{Knockback, Position, Ramp, Roof, Wall},
+    weapons::{ExplosionDef, WeaponId},
 };
 
 // Direction of a projectile hit (normalized XZ vector).
@@ -20,40 +21,54 @@ pub struct HitDirection {
 pub struct Projectile {
     pub velocity: Vec3,
     pub lifetime: Timer,
+    pub weapon: WeaponId,
 }
 
 impl Projectile {
     #[must_use]
-    pub fn new(face_dir: f32, face_pitch: f32) -> Self {
+    pub fn new(face_dir: f32, face_pitch: f32, weapon: WeaponId) -> Self {
+        let speed = weapon.def().speed;
         let pitch_sin = face_pitch.sin();
         let pitch_cos = face_pitch.cos();
         let velocity = Vec3::new(
-            face_dir.sin() * pitch_cos * PROJECTILE_SPEED,
-            pitch_sin * PROJECTILE_SPEED,
-            face_dir.cos() * pitch_cos * PROJECTILE_SPEED,
+            face_dir.sin() * pitch_cos * speed,
+            pitch_sin * speed,
+            face_dir.cos() * pitch_cos * speed,
         );
 
+        Self::with_velocity(velocity, weapon)
+    }
+
+    // Projectile with a known initial velocity (e.g. replicated from the server)
+    #[must_use]
+    pub fn with_velocity(velocity: Vec3, weapon: WeaponId) -> Self {
         Self {
             velocity,
-            lifetime: Timer::from_seconds(PROJECTILE_LIFETIME, TimerMode::Once),
+            lifetime: Timer::from_seconds(weapon.def().lifetime, TimerMode::Once),
+            weapon,
         }
     }
 
+    // Whether the projectile bounces off geometry (otherwise it is removed on the first contact).
+    #[must_use]
+    pub const fn bounces(&self) -> bool {
+        self.weapon.def().bounces
+    }
+
     // Applies gravity to the projectile's velocity.
     pub fn apply_gravity(&mut self, delta: f32) {
-        if PROJECTILE_GRAVITY > 0.0 {
-            self.velocity.y -= PROJECTILE_GRAVITY * delta;
-        }
+        self.velocity.y = apply_gravity(self.velocity.y, self.weapon.def().gravity, delta);
     }
 
     // Applies air resistance (drag) to the projectile's velocity.
     // Drag force opposes motion and is proportional to velocity squared.
     pub fn apply_drag(&mut self, delta: f32) {
-        if PROJECTILE_DRAG_FACTOR > 0.0 {
+        let drag = self.weapon.def().drag;
+        if drag > 0.0 {
             let speed = self.velocity.length();
             if speed > PHYSICS_EPSILON {
                 // Deceleration magnitude = drag_factor * v^2
-                let deceleration = PROJECTILE_DRAG_FACTOR * speed * speed;
+                let deceleration = drag * speed * speed;
                 // Apply deceleration opposite to velocity direction
                 let speed_reduction = deceleration * delta;
                 // Don't reduce speed below zero
@@ -106,9 +121,9 @@ impl Projectile {
         self.velocity -= 2.0 * dot * collision.normal;
 
         // Apply energy loss based on impact angle:
-        // - Head-on (cos=1): full energy loss (use PROJECTILE_BOUNCE_RETENTION)
+        // - Head-on (cos=1): full energy loss (use the weapon's bounce retention)
         // - Glancing (cos=0): minimal energy loss (retention = 1.0)
-        let retention = 1.0 - cos_impact * (1.0 - PROJECTILE_BOUNCE_RETENTION);
+        let retention = 1.0 - cos_impact * (1.0 - self.weapon.def().bounce_retention);
         self.velocity *= retention;
 
         // Separate from surface and continue with remaining time
@@ -120,6 +135,48 @@ impl Projectile {
     }
 }
 
+// Knockback of an explosion on a player standing at `player_pos`, or `None` if the player is outside the blast
+// radius or a wall blocks the line from the explosion to the player's center. The push points away from the
+// explosion and fades linearly to zero at the edge of the radius.
+#[must_use]
+pub fn explosion_knockback(
+    center: &Position,
+    player_pos: &Position,
+    explosion: &ExplosionDef,
+    walls: &[Wall],
+) -> Option<Knockback> {
+    let player_center = Position {
+        x: player_pos.x,
+        y: player_pos.y + PLAYER_HEIGHT / 2.0,
+        z: player_pos.z,
+    };
+    let offset = Vec3::from(player_center) - Vec3::from(*center);
+    let distance = offset.length();
+    if distance > explosion.radius {
+        return None;
+    }
+
+    // Walls only shield the part of the line below their top
+    let low = center.y.min(player_center.y);
+    let high = center.y.max(player_center.y);
+    let blocked = walls.iter().any(|wall| {
+        low < wall.y + WALL_HEIGHT && high > wall.y && sweep_aabb_vs_wall(center, &player_center, wall, 0.0, 0.0)
+    });
+    if blocked {
+        return None;
+    }
+
+    let horizontal = offset.x.hypot(offset.z);
+    if horizontal < PHYSICS_EPSILON {
+        return Some(Knockback { vel_x: 0.0, vel_z: 0.0 });
+    }
+    let strength = explosion.knockback * (1.0 - distance / explosion.radius);
+    Some(Knockback {
+        vel_x: offset.x / horizontal * strength,
+        vel_z: offset.z / horizontal * strength,
+    })
+}
+
 // === Projectile sweep helpers ===
 
 fn sweep_projectile_vs_ground(
@@ -455,7 +512,7 @@ fn sweep_projectile_vs_wall(
 ) -> Option<Collision> {
     let wall_center_x = f32::midpoint(wall.x1, wall.x2);
     let wall_center_z = f32::midpoint(wall.z1, wall.z2);
-    let wall_center_y = WALL_HEIGHT / 2.0;
+    let wall_center_y = wall.y + WALL_HEIGHT / 2.0;
 
     let dx = (wall.x2 - wall.x1).abs();
     let dz = (wall.z2 - wall.z1).abs();
@@ -494,7 +551,7 @@ fn sweep_projectile_vs_roof(
 
     let center_x = f32::midpoint(min_x, max_x);
     let center_z = f32::midpoint(min_z, max_z);
-    let center_y = ROOF_HEIGHT - roof.thickness / 2.0;
+    let center_y = roof.y - roof.thickness / 2.0;
 
     let half_x = (max_x - min_x) / 2.0 + PROJECTILE_RADIUS;
     let half_z = (max_z - min_z) / 2.0 + PROJECTILE_RADIUS;
@@ -521,7 +578,7 @@ pub fn projectile_hits_ramp(proj_pos: &Position, projectile_velocity: &Vec3, del
         let sample_z = (projectile_velocity.z * delta).mul_add(t, proj_pos.z);
 
         if sample_x >= min_x && sample_x <= max_x && sample_z >= min_z && sample_z <= max_z {
-            let ramp_height = crate::map::height_on_ramp(&[*ramp], sample_x, sample_z);
+            let ramp_height = crate::map::height_on_ramp(&[*ramp], sample_x, sample_z, sample_y);
 
             if (sample_y - ramp_height).abs() < PROJECTILE_RADIUS * 2.0 {
                 return true;
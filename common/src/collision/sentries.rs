@@ -1,6 +1,6 @@
-use super::helpers::{ranges_overlap_1d, slide_along_axes, sweep_aabb_vs_wall, sweep_slab_interval};
+use super::helpers::{ranges_overlap_1d, slide_along_axes, sweep_aabb_vs_wall, sweep_ramp_edges, sweep_ramp_high_cap};
 use crate::{
-    constants::{PLAYER_DEPTH, PLAYER_HEIGHT, PLAYER_WIDTH, SENTRY_DEPTH, SENTRY_HEIGHT, SENTRY_WIDTH},
+    constants::{PLAYER_DEPTH, PLAYER_HEIGHT, PLAYER_WIDTH, SENTRY_DEPTH, SENTRY_HEIGHT, SENTRY_WIDTH, WALL_THICKNESS},
     protocol::{Position, Ramp, Wall},
 };
 
@@ -10,42 +10,21 @@ fn sweep_sentry_vs_wall(start_pos: &Position, end_pos: &Position, wall: &Wall) -
 }
 
 #[must_use]
-fn sweep_sentry_vs_ramp_footprint(start_pos: &Position, end_pos: &Position, ramp: &Ramp) -> bool {
-    // Swept AABB against the ramp footprint expanded by sentry half extents.
+fn sweep_sentry_vs_ramp_edges(start_pos: &Position, end_pos: &Position, ramp: &Ramp) -> bool {
     let half_x = SENTRY_WIDTH / 2.0;
     let half_z = SENTRY_DEPTH / 2.0;
+    let edge_half = WALL_THICKNESS / 2.0;
 
-    let (min_x, max_x, min_z, max_z) = ramp.bounds_xz();
-
-    let center_x = f32::midpoint(min_x, max_x);
-    let center_z = f32::midpoint(min_z, max_z);
-    let expanded_half_x = (max_x - min_x) / 2.0 + half_x;
-    let expanded_half_z = (max_z - min_z) / 2.0 + half_z;
-
-    let dir_x = end_pos.x - start_pos.x;
-    let dir_z = end_pos.z - start_pos.z;
-
-    let local_x = start_pos.x - center_x;
-    let local_z = start_pos.z - center_z;
-
-    let mut t_min = 0.0_f32;
-    let mut t_max = 1.0_f32;
-
-    if let Some((new_min, new_max)) = sweep_slab_interval(local_x, dir_x, expanded_half_x, t_min, t_max) {
-        t_min = new_min;
-        t_max = new_max;
-    } else {
+    // Ramps on other tiers (upper ramps on roofs) don't block
+    if !ramp.spans_height(start_pos.y) {
         return false;
     }
 
-    if let Some((new_min, new_max)) = sweep_slab_interval(local_z, dir_z, expanded_half_z, t_min, t_max) {
-        t_min = new_min;
-        t_max = new_max;
-    } else {
-        return false;
-    }
+    // Like players, sentries walk up and down ramps but can't enter through the sides or the tall face
+    let on_ramp_floor = start_pos.y <= ramp.bounds_y().0 + 0.1;
 
-    t_min <= t_max && t_max >= 0.0 && t_min <= 1.0
+    sweep_ramp_edges(start_pos, end_pos, ramp, half_x, half_z, edge_half)
+        || (on_ramp_floor && sweep_ramp_high_cap(start_pos, end_pos, ramp, half_x, half_z, edge_half))
 }
 
 #[must_use]
@@ -62,10 +41,10 @@ pub fn slide_sentry_along_obstacles(
 
 #[must_use]
 pub fn overlap_sentry_vs_player(sentry_pos: &Position, player_pos: &Position) -> bool {
-    // Check Y-axis overlap: sentry is at ground level (0 to SENTRY_HEIGHT)
+    // Check Y-axis overlap: sentry is at sentry_pos.y to (sentry_pos.y + SENTRY_HEIGHT)
     // player is at player_pos.y to (player_pos.y + PLAYER_HEIGHT)
-    let sentry_min_y = 0.0;
-    let sentry_max_y = SENTRY_HEIGHT;
+    let sentry_min_y = sentry_pos.y;
+    let sentry_max_y = sentry_pos.y + SENTRY_HEIGHT;
     let player_min_y = player_pos.y;
     let player_max_y = player_pos.y + PLAYER_HEIGHT;
 
@@ -120,13 +99,13 @@ fn slide_sentry(
             walls.iter().any(|w| sweep_sentry_vs_wall(current_pos, candidate, w))
                 || ramps
                     .iter()
-                    .any(|r| sweep_sentry_vs_ramp_footprint(current_pos, candidate, r))
+                    .any(|r| sweep_sentry_vs_ramp_edges(current_pos, candidate, r))
         },
         |candidate| {
             walls.iter().any(|w| sweep_sentry_vs_wall(current_pos, candidate, w))
                 || ramps
                     .iter()
-                    .any(|r| sweep_sentry_vs_ramp_footprint(current_pos, candidate, r))
+                    .any(|r| sweep_sentry_vs_ramp_edges(current_pos, candidate, r))
         },
     )
 }
@@ -3,6 +3,8 @@
 // ============================================================================
 
 pub const UPDATE_BROADCAST_INTERVAL: f32 = 0.25; // seconds
+pub const SERVER_TICKS_PER_SECOND: u64 = 30; // Server loop frequency, also the unit of replicated timers
+pub const RECONNECT_GRACE_PERIOD: f32 = 30.0; // seconds the server keeps a dropped player's state for them to resume
 
 // ============================================================================
 // Floating-Point Comparisons
@@ -35,6 +37,13 @@ pub const PLAYER_EYE_HEIGHT_RATIO: f32 = 0.9; // Eye/camera height as ratio of p
 pub const SPEED_WALK: f32 = 9.0;
 pub const SPEED_RUN: f32 = 9.0;
 
+// Jumping and falling
+pub const PLAYER_JUMP_SPEED: f32 = 5.0; // m/s upwards at take-off (apex ~1.3m, head stays below roofs)
+pub const PLAYER_GRAVITY: f32 = 9.81; // meters per second squared
+pub const KNOCKBACK_DECAY: f32 = 5.0; // per second - exponential decay rate of a knockback push
+pub const KNOCKBACK_MIN_SPEED: f32 = 0.3; // m/s - a weaker knockback push ends
+pub const HIT_KNOCKBACK_SPEED: f32 = 4.0; // m/s - push of a projectile hit in its direction of flight
+
 // ============================================================================
 // Projectiles
 // ============================================================================
@@ -81,8 +90,14 @@ pub const WALL_LENGTH: f32 = 8.2; // Slightly longer than grid to avoid corner g
 
 // Roofs
 pub const ROOF_THICKNESS: f32 = 0.4;
-pub const ROOF_HEIGHT: f32 = WALL_HEIGHT + ROOF_THICKNESS; // Top of roof
-pub const ROOF_WALL_THICKNESS: f32 = 0.1; // Roof-edge collision barrier
+
+// Levels (0 = ground, 1 = roof, 2 = upper deck)
+pub const LEVEL_HEIGHT: f32 = WALL_HEIGHT + ROOF_THICKNESS; // Height of one storey (top of a roof above the level below)
+pub const LEVEL_HEIGHT_TOLERANCE: f32 = 0.5; // Max height difference to a floor that still counts as standing on it
+
+// Jump pads
+pub const JUMP_PAD_SIZE: f32 = 2.0; // Side length of the square pad (meters)
+pub const JUMP_PAD_FLIGHT_TIME: f32 = 1.6; // seconds from take-off to landing (high enough to clear the roof edge)
 
 // ============================================================================
 // Power-Ups
@@ -100,3 +115,5 @@ pub const ALWAYS_SPEED: bool = false;
 pub const ALWAYS_MULTI_SHOT: bool = false;
 pub const ALWAYS_PHASING: bool = false;
 pub const ALWAYS_SENTRY_HUNT: bool = false;
+pub const ALWAYS_SHIELD: bool = false;
+pub const ALWAYS_CLOAK: bool = false;
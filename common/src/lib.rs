@@ -5,5 +5,8 @@ pub mod map;
 pub mod markers;
 pub mod net;
 pub mod players;
+pub mod power_ups;
 pub mod protocol;
 pub mod spawning;
+pub mod stats;
+pub mod weapons;
@@ -1,41 +1,71 @@
 use crate::{
-    constants::{PHYSICS_EPSILON, ROOF_HEIGHT},
-    protocol::{Ramp, Roof},
+    constants::{LEVEL_HEIGHT, LEVEL_HEIGHT_TOLERANCE, PHYSICS_EPSILON, PLAYER_HEIGHT, WALL_HEIGHT},
+    protocol::{MapLayout, Ramp, Wall},
 };
 
+// Returns the floor height of the given level (0 = ground).
+#[must_use]
+pub fn level_height(level: u8) -> f32 {
+    f32::from(level) * LEVEL_HEIGHT
+}
+
+// Calculate the surface height of a ramp at (x, z), if the position is within the ramp's footprint.
+#[must_use]
+pub fn ramp_surface_at(ramp: &Ramp, x: f32, z: f32) -> Option<f32> {
+    let (min_x, max_x, min_z, max_z) = ramp.bounds_xz();
+
+    if x < min_x || x > max_x || z < min_z || z > max_z {
+        return None;
+    }
+
+    let dx = (ramp.x2 - ramp.x1).abs();
+    let dz = (ramp.z2 - ramp.z1).abs();
+
+    let progress = if dx >= dz {
+        if (max_x - min_x).abs() < PHYSICS_EPSILON {
+            0.0
+        } else {
+            ((x - ramp.x1) / (ramp.x2 - ramp.x1)).clamp(0.0, 1.0)
+        }
+    } else if (max_z - min_z).abs() < PHYSICS_EPSILON {
+        0.0
+    } else {
+        ((z - ramp.z1) / (ramp.z2 - ramp.z1)).clamp(0.0, 1.0)
+    };
+
+    Some(ramp.y1 + progress * (ramp.y2 - ramp.y1))
+}
+
 // Calculate the Y position (height) for a given (x, z) position based on ramps.
+// Only ramps on the tier of the current height `y` are considered, so stacked ramps don't interfere.
 // Returns the interpolated Y value if the position is on a ramp, otherwise returns 0.0.
 #[must_use]
-pub fn height_on_ramp(ramps: &[Ramp], x: f32, z: f32) -> f32 {
+pub fn height_on_ramp(ramps: &[Ramp], x: f32, z: f32, y: f32) -> f32 {
     ramps
         .iter()
-        .find_map(|ramp| {
-            let (min_x, max_x, min_z, max_z) = ramp.bounds_xz();
-
-            if x < min_x || x > max_x || z < min_z || z > max_z {
-                return None;
-            }
-
-            let dx = (ramp.x2 - ramp.x1).abs();
-            let dz = (ramp.z2 - ramp.z1).abs();
-
-            let progress = if dx >= dz {
-                if (max_x - min_x).abs() < PHYSICS_EPSILON {
-                    0.0
-                } else {
-                    ((x - ramp.x1) / (ramp.x2 - ramp.x1)).clamp(0.0, 1.0)
-                }
-            } else if (max_z - min_z).abs() < PHYSICS_EPSILON {
-                0.0
-            } else {
-                ((z - ramp.z1) / (ramp.z2 - ramp.z1)).clamp(0.0, 1.0)
-            };
-
-            Some(ramp.y1 + progress * (ramp.y2 - ramp.y1))
-        })
+        .filter(|ramp| ramp.spans_height(y))
+        .find_map(|ramp| ramp_surface_at(ramp, x, z))
         .unwrap_or(0.0)
 }
 
+// Calculate the highest walkable surface (ground, ramp or roof) at (x, z) that is not above height `y`.
+// Surfaces slightly above `y` still count, so players can walk up ramps and onto roofs.
+#[must_use]
+pub fn floor_height_at(map_layout: &MapLayout, x: f32, z: f32, y: f32) -> f32 {
+    let max_floor = y + LEVEL_HEIGHT_TOLERANCE;
+
+    let ramp_floors = map_layout.ramps.iter().filter_map(|ramp| ramp_surface_at(ramp, x, z));
+    let roof_floors = map_layout.roofs.iter().filter_map(|roof| {
+        let (min_x, max_x, min_z, max_z) = roof.bounds_xz();
+        (x >= min_x && x <= max_x && z >= min_z && z <= max_z).then_some(roof.y)
+    });
+
+    ramp_floors
+        .chain(roof_floors)
+        .filter(|floor| *floor <= max_floor)
+        .fold(0.0, f32::max)
+}
+
 // Check if a position (x, z) is currently on any ramp.
 #[must_use]
 pub fn is_on_ramp(ramps: &[Ramp], x: f32, z: f32) -> bool {
@@ -45,18 +75,20 @@ pub fn is_on_ramp(ramps: &[Ramp], x: f32, z: f32) -> bool {
     })
 }
 
-// Check if a player is on a roof based on their Y position.
+// Collect the walls a player at height `y` can collide with, i.e. all walls overlapping the player's body
+// vertically (lower and upper walls). While phasing, only boundary walls block on the ground.
 #[must_use]
-pub fn close_to_roof(y: f32) -> bool {
-    const HEIGHT_TOLERANCE: f32 = 0.5;
-    (y - ROOF_HEIGHT).abs() < HEIGHT_TOLERANCE
-}
+pub fn walls_at_height(map_layout: &MapLayout, y: f32, phasing: bool) -> Vec<Wall> {
+    let ground_walls = if phasing {
+        &map_layout.boundary_walls
+    } else {
+        &map_layout.lower_walls
+    };
 
-// Returns true if the point (x, z) lies within any roof rectangle.
-#[must_use]
-pub fn has_roof(roofs: &[Roof], x: f32, z: f32) -> bool {
-    roofs.iter().any(|roof| {
-        let (min_x, max_x, min_z, max_z) = roof.bounds_xz();
-        x >= min_x && x <= max_x && z >= min_z && z <= max_z
-    })
+    ground_walls
+        .iter()
+        .chain(&map_layout.upper_walls)
+        .filter(|wall| wall.y < y + PLAYER_HEIGHT && y < wall.y + WALL_HEIGHT)
+        .copied()
+        .collect()
 }
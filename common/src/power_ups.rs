@@ -0,0 +1,207 @@
+use bincode::{Decode, Encode};
+use std::time::Duration;
+
+use crate::constants::{
+    ALWAYS_CLOAK, ALWAYS_MULTI_SHOT, ALWAYS_PHASING, ALWAYS_SENTRY_HUNT, ALWAYS_SHIELD, ALWAYS_SPEED,
+    SERVER_TICKS_PER_SECOND,
+};
+
+// ============================================================================
+// Effects
+// ============================================================================
+
+// Effect of an active power-up
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Encode, Decode)]
+pub enum EffectKind {
+    Speed,      // Faster movement
+    MultiShot,  // Several projectiles per shot
+    Phasing,    // Walk through interior walls
+    SentryHunt, // Sentries flee and can be shot for points
+    Shield,     // Absorbs projectile hits or one sentry stun
+    Cloak,      // Hidden from other players (except up close or after firing) and sentries
+}
+
+impl EffectKind {
+    pub const ALL: [Self; 6] = [
+        Self::Speed,
+        Self::MultiShot,
+        Self::Phasing,
+        Self::SentryHunt,
+        Self::Shield,
+        Self::Cloak,
+    ];
+
+    // Check if the effect is always active (debug flags)
+    #[must_use]
+    pub const fn is_always_active(self) -> bool {
+        match self {
+            Self::Speed => ALWAYS_SPEED,
+            Self::MultiShot => ALWAYS_MULTI_SHOT,
+            Self::Phasing => ALWAYS_PHASING,
+            Self::SentryHunt => ALWAYS_SENTRY_HUNT,
+            Self::Shield => ALWAYS_SHIELD,
+            Self::Cloak => ALWAYS_CLOAK,
+        }
+    }
+
+    // Longest duration of the power-ups granting this effect (seconds), e.g. to scale countdown bars
+    #[must_use]
+    pub fn max_duration(self) -> f32 {
+        POWER_UPS
+            .iter()
+            .filter(|def| def.effect == self)
+            .map(|def| def.duration)
+            .fold(0.0, f32::max)
+    }
+
+    const fn bit(self) -> u8 {
+        1 << self as u8
+    }
+}
+
+// Set of active effects, sent as a bitset
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default, Encode, Decode)]
+pub struct ActiveEffects(pub u8);
+
+impl ActiveEffects {
+    #[must_use]
+    pub const fn contains(self, effect: EffectKind) -> bool {
+        self.0 & effect.bit() != 0
+    }
+
+    pub const fn insert(&mut self, effect: EffectKind) {
+        self.0 |= effect.bit();
+    }
+
+    pub const fn remove(&mut self, effect: EffectKind) {
+        self.0 &= !effect.bit();
+    }
+
+    // Effects in the set, in the order of `EffectKind::ALL`
+    pub fn iter(self) -> impl Iterator<Item = EffectKind> {
+        EffectKind::ALL.into_iter().filter(move |effect| self.contains(*effect))
+    }
+}
+
+impl FromIterator<EffectKind> for ActiveEffects {
+    fn from_iter<I: IntoIterator<Item = EffectKind>>(iter: I) -> Self {
+        let mut effects = Self::default();
+        for effect in iter {
+            effects.insert(effect);
+        }
+        effects
+    }
+}
+
+// Server tick at the given time since the server started. Replicated timers are absolute ticks of this clock.
+#[must_use]
+pub fn server_tick(elapsed: Duration) -> u32 {
+    (elapsed.as_secs_f64() * SERVER_TICKS_PER_SECOND as f64) as u32
+}
+
+// Convert a remaining duration to server ticks for replication, rounding up so an effect never appears
+// to end early
+#[must_use]
+pub fn seconds_to_ticks(seconds: f32) -> u32 {
+    (seconds.max(0.0) * SERVER_TICKS_PER_SECOND as f32).ceil() as u32
+}
+
+// Convert server ticks back to seconds (on the server's clock for absolute ticks)
+#[must_use]
+pub fn ticks_to_seconds(ticks: u32) -> f64 {
+    f64::from(ticks) / SERVER_TICKS_PER_SECOND as f64
+}
+
+// ============================================================================
+// Power-Up Table
+// ============================================================================
+
+// Power-up ID - index into `POWER_UPS`
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Encode, Decode)]
+pub struct PowerUpId(pub u8);
+
+// What picking up a power-up does while its effect is still active
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum StackingRule {
+    Refresh,           // Restart the duration and charges
+    Extend,            // Add the duration to the remaining time
+    Stack { max: u8 }, // Restart the duration and add the charges, up to `max` pickups worth
+}
+
+// Definition of a power-up item
+#[derive(Debug)]
+pub struct PowerUpDef {
+    pub name: &'static str,
+    pub weight: u32,   // Relative chance to be picked when an item spawns
+    pub duration: f32, // seconds
+    pub effect: EffectKind,
+    pub stacking: StackingRule,
+    pub charges: u8, // Hits absorbed before the effect ends early (0 = not absorbing)
+}
+
+// Number of power-ups in `POWER_UPS`
+pub const POWER_UP_COUNT: usize = 6;
+
+pub static POWER_UPS: [PowerUpDef; POWER_UP_COUNT] = [
+    PowerUpDef {
+        name: "Speed",
+        weight: 1,
+        duration: 20.0,
+        effect: EffectKind::Speed,
+        stacking: StackingRule::Refresh,
+        charges: 0,
+    },
+    PowerUpDef {
+        name: "Multi-Shot",
+        weight: 1,
+        duration: 20.0,
+        effect: EffectKind::MultiShot,
+        stacking: StackingRule::Refresh,
+        charges: 0,
+    },
+    PowerUpDef {
+        name: "Phasing",
+        weight: 1,
+        duration: 15.0,
+        effect: EffectKind::Phasing,
+        stacking: StackingRule::Extend,
+        charges: 0,
+    },
+    PowerUpDef {
+        name: "Sentry Hunt",
+        weight: 1,
+        duration: 15.0,
+        effect: EffectKind::SentryHunt,
+        stacking: StackingRule::Refresh,
+        charges: 0,
+    },
+    PowerUpDef {
+        name: "Shield",
+        weight: 1,
+        duration: 30.0,
+        effect: EffectKind::Shield,
+        stacking: StackingRule::Stack { max: 2 },
+        charges: 3,
+    },
+    PowerUpDef {
+        name: "Cloak",
+        weight: 1,
+        duration: 20.0,
+        effect: EffectKind::Cloak,
+        stacking: StackingRule::Extend,
+        charges: 0,
+    },
+];
+
+impl PowerUpId {
+    // All power-ups in the table
+    pub fn all() -> impl Iterator<Item = Self> {
+        (0..POWER_UPS.len()).map(|index| Self(index as u8))
+    }
+
+    // Definition of the power-up. Panics for IDs outside the table.
+    #[must_use]
+    pub fn def(self) -> &'static PowerUpDef {
+        &POWER_UPS[self.0 as usize]
+    }
+}
@@ -2,7 +2,15 @@ use bevy_ecs::prelude::*;
 use bevy_math::Vec3;
 use bincode::{Decode, Encode};
 
-use crate::constants::{SPEED_RUN, SPEED_WALK};
+use crate::{
+    constants::{
+        HIT_KNOCKBACK_SPEED, JUMP_PAD_FLIGHT_TIME, JUMP_PAD_SIZE, KNOCKBACK_DECAY, KNOCKBACK_MIN_SPEED,
+        LEVEL_HEIGHT_TOLERANCE, PLAYER_GRAVITY, SPEED_RUN, SPEED_WALK,
+    },
+    power_ups::{ActiveEffects, EffectKind, PowerUpId},
+    stats::{LeaderboardEntry, ScoreboardEntry},
+    weapons::WeaponId,
+};
 
 // ============================================================================
 // Common Data Types
@@ -64,7 +72,7 @@ impl Speed {
 #[derive(Debug, Copy, Clone, Component, PartialEq, Default, Encode, Decode)]
 pub struct Velocity {
     pub x: f32, // m/s
-    pub y: f32, // m/s (up/down - always 0 for now)
+    pub y: f32, // m/s (up/down - always 0 for players, see `VerticalVelocity`)
     pub z: f32, // m/s
 }
 
@@ -80,6 +88,59 @@ impl Velocity {
     }
 }
 
+impl From<Vec3> for Velocity {
+    fn from(v: Vec3) -> Self {
+        Self { x: v.x, y: v.y, z: v.z }
+    }
+}
+
+impl From<Velocity> for Vec3 {
+    fn from(v: Velocity) -> Self {
+        Self::new(v.x, v.y, v.z)
+    }
+}
+
+// VerticalVelocity component - vertical speed of a jumping or falling player (0 when grounded).
+// Kept apart from `Velocity`, which is rebuilt from `Speed` whenever the movement input changes.
+#[derive(Debug, Copy, Clone, Component, PartialEq, Default)]
+pub struct VerticalVelocity(pub f32); // m/s (positive = up)
+
+// JumpPadLaunch component - horizontal velocity of a player flying off a jump pad.
+// Overrides the movement input until the player lands.
+#[derive(Debug, Copy, Clone, Component, PartialEq)]
+pub struct JumpPadLaunch {
+    pub vel_x: f32, // m/s
+    pub vel_z: f32, // m/s
+}
+
+// Knockback component - decaying horizontal push of a player hit by a projectile or an explosion.
+// Added to the player's own movement (also while stunned or launched) until it dies out.
+#[derive(Debug, Copy, Clone, Component, PartialEq, Encode, Decode)]
+pub struct Knockback {
+    pub vel_x: f32, // m/s
+    pub vel_z: f32, // m/s
+}
+
+impl Knockback {
+    // Push of a projectile hit, from the hit direction sent in `SHit`
+    #[must_use]
+    pub fn from_hit(hit_dir_x: f32, hit_dir_z: f32) -> Self {
+        Self {
+            vel_x: hit_dir_x * HIT_KNOCKBACK_SPEED,
+            vel_z: hit_dir_z * HIT_KNOCKBACK_SPEED,
+        }
+    }
+
+    // Decay the push over one step. Returns whether it is still strong enough to keep pushing.
+    #[must_use]
+    pub fn decay(&mut self, delta: f32) -> bool {
+        let factor = (-KNOCKBACK_DECAY * delta).exp();
+        self.vel_x *= factor;
+        self.vel_z *= factor;
+        self.vel_x.hypot(self.vel_z) >= KNOCKBACK_MIN_SPEED
+    }
+}
+
 // Player ID component - identifies which player an entity represents.
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Component, Encode, Decode)]
 pub struct PlayerId(pub u32);
@@ -92,6 +153,14 @@ pub struct ItemId(pub u32);
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Component, Encode, Decode)]
 pub struct SentryId(pub u32);
 
+// Wall ID component - identifies which destructible wall an entity represents.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Component, Encode, Decode)]
+pub struct WallId(pub u32);
+
+// Projectile ID component - identifies which projectile an entity represents.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Component, Encode, Decode)]
+pub struct ProjectileId(pub u32);
+
 // FaceDirection component - direction player is facing (for rotation/aiming).
 #[derive(Component, Default)]
 pub struct FaceDirection(pub f32); // radians
@@ -104,15 +173,14 @@ pub struct Player {
     pub speed: Speed,
     pub face_dir: f32,
     pub hits: i32,
-    pub speed_power_up: bool,
-    pub multi_shot_power_up: bool,
-    pub phasing_power_up: bool,
-    pub sentry_hunt_power_up: bool,
+    pub effects: ActiveEffects,                // Active power-up effects
+    pub effect_timers: Vec<(EffectKind, u32)>, // Server tick at which each timed effect expires
+    pub shield_charges: u8,                    // Hits the shield can still absorb
     pub stunned: bool,
 }
 
 impl Player {
-    // Creates a new player with the given core fields and all status flags set to `false`.
+    // Creates a new player with the given core fields, no active effects and not stunned.
     #[must_use]
     pub const fn new(name: String, pos: Position, speed: Speed, face_dir: f32, hits: i32) -> Self {
         Self {
@@ -121,23 +189,23 @@ impl Player {
             speed,
             face_dir,
             hits,
-            speed_power_up: false,
-            multi_shot_power_up: false,
-            phasing_power_up: false,
-            sentry_hunt_power_up: false,
+            effects: ActiveEffects(0),
+            effect_timers: Vec::new(),
+            shield_charges: 0,
             stunned: false,
         }
     }
 }
 
 // Wall - a wall segment on the grid.
-#[derive(Debug, Clone, Encode, Decode, Copy)]
+#[derive(Debug, Clone, Encode, Decode, Copy, PartialEq)]
 pub struct Wall {
     pub x1: f32,
     pub z1: f32,
     pub x2: f32,
     pub z2: f32,
     pub width: f32,
+    pub y: f32, // Base height (0.0 for ground walls)
 }
 
 impl Wall {
@@ -161,6 +229,7 @@ pub struct Roof {
     pub x2: f32,
     pub z2: f32,
     pub thickness: f32,
+    pub y: f32, // Height of the top surface
 }
 
 impl Roof {
@@ -209,13 +278,108 @@ impl Ramp {
     pub const fn bounds_y(&self) -> (f32, f32) {
         (self.y1.min(self.y2), self.y1.max(self.y2))
     }
+
+    // Returns true if an entity at height `y` is on this ramp's tier (between its low and high level).
+    #[must_use]
+    pub fn spans_height(&self, y: f32) -> bool {
+        let (min_y, max_y) = self.bounds_y();
+        y >= min_y - LEVEL_HEIGHT_TOLERANCE && y <= max_y + LEVEL_HEIGHT_TOLERANCE
+    }
+}
+
+// Jump pad on the ground that launches players onto an adjacent roof.
+// - (x, z) is the center of the square pad, which has side length `JUMP_PAD_SIZE`.
+// - (target_x, target_y, target_z) is the landing point on the roof.
+#[derive(Debug, Clone, Encode, Decode, Copy)]
+pub struct JumpPad {
+    pub x: f32,
+    pub z: f32,
+    pub target_x: f32,
+    pub target_y: f32,
+    pub target_z: f32,
+}
+
+impl JumpPad {
+    // Returns true if (x, z) lies on the pad.
+    #[must_use]
+    pub fn contains(&self, x: f32, z: f32) -> bool {
+        (x - self.x).abs() <= JUMP_PAD_SIZE / 2.0 && (z - self.z).abs() <= JUMP_PAD_SIZE / 2.0
+    }
+
+    // Returns the take-off velocity of the ballistic arc from the pad center to the landing point, taking
+    // `JUMP_PAD_FLIGHT_TIME` under player gravity.
+    #[must_use]
+    pub fn launch_velocity(&self) -> Velocity {
+        let t = JUMP_PAD_FLIGHT_TIME;
+        Velocity {
+            x: (self.target_x - self.x) / t,
+            y: (0.5 * PLAYER_GRAVITY).mul_add(t * t, self.target_y) / t,
+            z: (self.target_z - self.z) / t,
+        }
+    }
 }
 
 // Precomputed wall light placement sent from server to client.
+// The light state is owned by the server and updated through `SLightState`.
 #[derive(Debug, Clone, Encode, Decode, Copy)]
 pub struct WallLight {
     pub pos: Position,
     pub yaw: f32,
+    pub state: LightState,
+}
+
+// Wall light mode
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Encode, Decode)]
+pub enum LightMode {
+    #[default]
+    On,
+    Off,
+    Flicker, // Animated on the client
+}
+
+// Wall light color (linear RGB, 0.0 - 1.0)
+#[derive(Debug, Clone, Copy, PartialEq, Encode, Decode)]
+pub struct LightColor {
+    pub r: f32,
+    pub g: f32,
+    pub b: f32,
+}
+
+impl LightColor {
+    pub const WARM: Self = Self {
+        r: 1.0,
+        g: 0.95,
+        b: 0.85,
+    };
+    pub const EMERGENCY: Self = Self {
+        r: 1.0,
+        g: 0.15,
+        b: 0.1,
+    };
+}
+
+// Wall light state
+#[derive(Debug, Clone, Copy, PartialEq, Encode, Decode)]
+pub struct LightState {
+    pub mode: LightMode,
+    pub color: LightColor,
+}
+
+impl Default for LightState {
+    fn default() -> Self {
+        Self {
+            mode: LightMode::On,
+            color: LightColor::WARM,
+        }
+    }
+}
+
+// Destructible wall - an interior wall segment that can be destroyed by projectiles.
+// The wall itself is also part of `interior_walls` and `lower_walls` until destroyed.
+#[derive(Debug, Clone, Encode, Decode, Copy)]
+pub struct DestructibleWall {
+    pub id: WallId,
+    pub wall: Wall,
 }
 
 // Full grid configuration sent once on connect.
@@ -224,19 +388,43 @@ pub struct MapLayout {
     pub boundary_walls: Vec<Wall>,
     pub interior_walls: Vec<Wall>,
     pub lower_walls: Vec<Wall>, // Boundary walls + interior walls
-    pub roof_walls: Vec<Wall>,  // Invisible roof walls for collision
+    pub upper_walls: Vec<Wall>, // Walls standing on roofs, supporting upper decks
     pub roofs: Vec<Roof>,
     pub ramps: Vec<Ramp>,
     pub wall_lights: Vec<WallLight>,
+    pub destructible_walls: Vec<DestructibleWall>, // Subset of interior walls that can be destroyed
+    pub jump_pads: Vec<JumpPad>,
+}
+
+impl MapLayout {
+    // Returns all visible walls (lower walls and upper walls), which are the walls projectiles bounce off.
+    pub fn visible_walls(&self) -> impl Iterator<Item = &Wall> {
+        self.lower_walls.iter().chain(&self.upper_walls)
+    }
+
+    // Returns the ID of the destructible wall matching the given wall segment, if any.
+    #[must_use]
+    pub fn destructible_wall_id(&self, wall: &Wall) -> Option<WallId> {
+        self.destructible_walls
+            .iter()
+            .find(|dw| dw.wall == *wall)
+            .map(|dw| dw.id)
+    }
+
+    // Removes a destructible wall from all wall lists. Returns the removed wall, if it existed.
+    pub fn remove_destructible_wall(&mut self, id: WallId) -> Option<Wall> {
+        let index = self.destructible_walls.iter().position(|dw| dw.id == id)?;
+        let wall = self.destructible_walls.remove(index).wall;
+        self.interior_walls.retain(|w| *w != wall);
+        self.lower_walls.retain(|w| *w != wall);
+        Some(wall)
+    }
 }
 
 // Item type - different types of items.
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Encode, Decode)]
 pub enum ItemType {
-    SpeedPowerUp,
-    MultiShotPowerUp,
-    PhasingPowerUp,
-    SentryHunterPowerUp,
+    PowerUp(PowerUpId), // See `power_ups::POWER_UPS`
     Cookie,
 }
 
@@ -247,11 +435,22 @@ pub struct Item {
     pub pos: Position,
 }
 
+// SentryArchetype - kind of sentry, determines its behavior on the server and its looks on the client.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Default, Encode, Decode)]
+pub enum SentryArchetype {
+    #[default]
+    Standard,
+    Heavy,  // Slow, only affected by shots of hunters
+    Scout,  // Fast, alerts nearby sentries
+    Turret, // Stationary, shoots at its target
+}
+
 // Sentry - a sentry moving around the map.
 #[derive(Debug, Clone, Encode, Decode, Copy)]
 pub struct Sentry {
     pub pos: Position,
     pub vel: Velocity,
+    pub archetype: SentryArchetype,
 }
 
 // ============================================================================
@@ -262,6 +461,17 @@ pub struct Sentry {
 #[derive(Debug, Clone, Encode, Decode)]
 pub struct CLogin {
     pub name: String,
+    pub auth: Option<LoginAuth>, // None = play as a guest (under a name no account is registered with)
+    pub resume_token: Option<String>, // Resume the session of a dropped connection (`SInit::resume_token`)
+    pub spectate: bool,          // Watch without playing (no player is spawned)
+}
+
+// Authentication of a login to an account named like the player
+#[derive(Debug, Clone, Encode, Decode)]
+pub enum LoginAuth {
+    Register { password: String }, // Create the account
+    Password { password: String },
+    Token { token: String }, // Session token from an earlier login (`SInit::session_token`)
 }
 
 // Client to Server: Graceful disconnect notification.
@@ -280,13 +490,23 @@ pub struct CFace {
     pub dir: f32, // radians - direction player is facing
 }
 
+// Client to Server: Jump (ignored by the server unless the player is standing on a floor).
+#[derive(Debug, Clone, Encode, Decode)]
+pub struct CJump;
+
 // Client to Server: Shot fired.
 #[derive(Debug, Clone, Encode, Decode)]
 pub struct CShot {
     pub face_dir: f32,   // radians - yaw direction player is facing when shooting
     pub face_pitch: f32, // radians - pitch (up/down) when shooting
+    pub weapon: WeaponId,
+    pub seq: u32, // Client's shot counter, echoed in `SShot` to match the predicted projectiles
 }
 
+// Client to Server: Request the all-time leaderboard.
+#[derive(Debug, Clone, Encode, Decode)]
+pub struct CLeaderboard {}
+
 // Client to Server: Echo request with timestamp (Duration since app start, serialized as nanoseconds).
 #[derive(Debug, Clone, Encode, Decode)]
 pub struct CEcho {
@@ -301,7 +521,16 @@ pub struct CEcho {
 #[derive(Debug, Clone, Encode, Decode)]
 pub struct SInit {
     pub id: PlayerId,
-    pub map_layout: MapLayout,
+    pub map_layout: Box<MapLayout>,    // Boxed to keep `ServerMessage` small
+    pub session_token: Option<String>, // Account logins only - log in with it next time instead of the password
+    pub resume_token: String,          // Reclaim this player after the connection drops (`CLogin::resume_token`)
+    pub tick: u32,                     // Current server tick, to convert replicated timers to local time
+}
+
+// Server to Client: Login refused (the connection stays open for another attempt).
+#[derive(Debug, Clone, Encode, Decode)]
+pub struct SLoginRejected {
+    pub reason: String,
 }
 
 // Server to Client: Another player connected.
@@ -318,6 +547,12 @@ pub struct SLogoff {
     pub graceful: bool,
 }
 
+// Server to Client: Spectators watching the game (sent whenever one joins or leaves).
+#[derive(Debug, Clone, Encode, Decode)]
+pub struct SSpectators {
+    pub spectators: Vec<(PlayerId, String)>, // Sorted by ID
+}
+
 // Server to Client: Player speed update with position for reconciliation.
 #[derive(Debug, Clone, Encode, Decode)]
 pub struct SSpeed {
@@ -333,23 +568,57 @@ pub struct SFace {
     pub dir: f32, // radians - direction player is facing
 }
 
+// Server to Client: Player jumped.
+#[derive(Debug, Clone, Encode, Decode)]
+pub struct SJump {
+    pub id: PlayerId,
+}
+
 // Server to Client: Player shot fired.
 #[derive(Debug, Clone, Encode, Decode)]
 pub struct SShot {
     pub id: PlayerId,
     pub face_dir: f32,   // radians - yaw direction player is facing when shooting
     pub face_pitch: f32, // radians - pitch (up/down) when shooting
+    pub weapon: WeaponId,
+    pub seq: u32, // Shot counter from the shooter's `CShot`
+    pub projectiles: Vec<ProjectileSpawn>,
 }
 
 // Server to Client: Periodic game state update for all players.
 #[derive(Debug, Clone, Encode, Decode)]
 pub struct SUpdate {
     pub seq: u32,
+    pub tick: u32, // Current server tick, to convert replicated timers to local time
     pub players: Vec<(PlayerId, Player)>,
     pub items: Vec<(ItemId, Item)>,
     pub sentries: Vec<(SentryId, Sentry)>,
 }
 
+// Initial state of a projectile spawned by the server
+#[derive(Debug, Clone, Copy, Encode, Decode)]
+pub struct ProjectileSpawn {
+    pub id: ProjectileId,
+    pub position: Position,
+    pub velocity: Velocity,
+}
+
+// What a projectile hit when it was removed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Encode, Decode)]
+pub enum ProjectileHit {
+    Nothing,                               // Expired, stopped by an obstacle, detonated or hit its shooter
+    Player(PlayerId),                      // Hit a player
+    Shield(PlayerId),                      // Hit a player whose shield absorbed it
+    Sentry { id: SentryId, scored: bool }, // Hit a sentry (scored with the sentry hunt power-up)
+}
+
+// Server to Client: Projectile was removed.
+#[derive(Debug, Clone, Encode, Decode)]
+pub struct SProjectileDespawn {
+    pub id: ProjectileId,
+    pub hit: ProjectileHit,
+}
+
 // Server to Client: Player was hit by a projectile.
 #[derive(Debug, Clone, Encode, Decode)]
 pub struct SHit {
@@ -362,10 +631,9 @@ pub struct SHit {
 #[derive(Debug, Clone, PartialEq, Eq, Encode, Decode)]
 pub struct SPlayerStatus {
     pub id: PlayerId,
-    pub speed_power_up: bool,
-    pub multi_shot_power_up: bool,
-    pub phasing_power_up: bool,
-    pub sentry_hunt_power_up: bool,
+    pub effects: ActiveEffects,                // Active power-up effects
+    pub effect_timers: Vec<(EffectKind, u32)>, // Server tick at which each timed effect expires
+    pub shield_charges: u8,                    // Hits the shield can still absorb
     pub stunned: bool,
 }
 
@@ -375,13 +643,40 @@ pub struct SEcho {
     pub timestamp_nanos: u64,
 }
 
-// Server to Client: Sentry direction changed.
+// Server to Client: Sentry spawned or direction changed.
 #[derive(Debug, Clone, Encode, Decode)]
 pub struct SSentry {
     pub id: SentryId,
     pub sentry: Sentry,
 }
 
+// Server to Client: Sentry was removed from the game.
+#[derive(Debug, Clone, Encode, Decode)]
+pub struct SSentryDespawn {
+    pub id: SentryId,
+}
+
+// Server to Client: Turret sentry shot fired.
+#[derive(Debug, Clone, Encode, Decode)]
+pub struct SSentryShot {
+    pub id: SentryId,
+    pub face_dir: f32,   // radians - yaw direction of the shot
+    pub face_pitch: f32, // radians - pitch (up/down) of the shot
+    pub projectiles: Vec<ProjectileSpawn>,
+}
+
+// Server to Client: Destructible wall was destroyed.
+#[derive(Debug, Clone, Encode, Decode)]
+pub struct SWallDestroyed {
+    pub id: WallId,
+}
+
+// Server to Client: Wall light states changed.
+#[derive(Debug, Clone, Encode, Decode)]
+pub struct SLightState {
+    pub lights: Vec<(u32, LightState)>, // Index into `MapLayout::wall_lights` and its new state
+}
+
 // Server to Client: Player collected a cookie.
 #[derive(Debug, Clone, Encode, Decode)]
 pub struct SCookieCollected {}
@@ -390,6 +685,40 @@ pub struct SCookieCollected {}
 #[derive(Debug, Clone, Encode, Decode)]
 pub struct SSentryHit {}
 
+// Server to Client: A player's shield absorbed a projectile hit or sentry stun.
+#[derive(Debug, Clone, Encode, Decode)]
+pub struct SShieldAbsorb {
+    pub id: PlayerId, // Player whose shield absorbed the hit
+}
+
+// Server to Client: An explosive projectile detonated.
+#[derive(Debug, Clone, Encode, Decode)]
+pub struct SExplosion {
+    pub shooter: PlayerId,
+    pub weapon: WeaponId,
+    pub position: Position,
+    pub knockbacks: Vec<(PlayerId, Knockback)>, // Players caught in the blast and their push
+}
+
+// Server to Client: Periodic scoreboard with the statistics of all players in the current match.
+#[derive(Debug, Clone, Encode, Decode)]
+pub struct SScoreboard {
+    pub remaining: f32, // seconds until the match ends
+    pub entries: Vec<ScoreboardEntry>,
+}
+
+// Server to Client: Match ended, with the final scoreboard. Scores and statistics start over.
+#[derive(Debug, Clone, Encode, Decode)]
+pub struct SMatchEnd {
+    pub entries: Vec<ScoreboardEntry>,
+}
+
+// Server to Client: All-time leaderboard (reply to `CLeaderboard`).
+#[derive(Debug, Clone, Encode, Decode)]
+pub struct SLeaderboard {
+    pub entries: Vec<LeaderboardEntry>, // Highest total score first
+}
+
 // ============================================================================
 // Message Envelopes
 // ============================================================================
@@ -401,24 +730,39 @@ pub enum ClientMessage {
     Logoff(CLogoff),
     Speed(CSpeed),
     Face(CFace),
+    Jump(CJump),
     Shot(CShot),
     Echo(CEcho),
+    Leaderboard(CLeaderboard),
 }
 
 // All server to client messages
 #[derive(Debug, Clone, Message, Encode, Decode)]
 pub enum ServerMessage {
     Init(SInit),
+    LoginRejected(SLoginRejected),
     Login(SLogin),
     Logoff(SLogoff),
+    Spectators(SSpectators),
     Speed(SSpeed),
     Face(SFace),
+    Jump(SJump),
     Shot(SShot),
     Update(SUpdate),
     Hit(SHit),
     PlayerStatus(SPlayerStatus),
     Echo(SEcho),
     Sentry(SSentry),
+    SentryDespawn(SSentryDespawn),
+    SentryShot(SSentryShot),
+    ProjectileDespawn(SProjectileDespawn),
     CookieCollected(SCookieCollected),
     SentryHit(SSentryHit),
+    ShieldAbsorb(SShieldAbsorb),
+    Explosion(SExplosion),
+    WallDestroyed(SWallDestroyed),
+    LightState(SLightState),
+    Scoreboard(SScoreboard),
+    MatchEnd(SMatchEnd),
+    Leaderboard(SLeaderboard),
 }
@@ -3,6 +3,7 @@ use crate::{
     constants::*,
     map::height_on_ramp,
     protocol::{Position, Ramp, Roof, Wall},
+    weapons::WeaponId,
 };
 use bevy_math::Vec3;
 
@@ -27,6 +28,7 @@ pub fn calculate_projectile_spawns(
     shooter_pos: &Position,
     face_dir: f32,
     face_pitch: f32,
+    weapon: WeaponId,
     has_multi_shot: bool,
     walls: &[Wall],
     ramps: &[Ramp],
@@ -45,12 +47,15 @@ pub fn calculate_projectile_spawns(
     let angle_step = POWER_UP_MULTI_SHOT_ANGLE.to_radians();
     let start_offset = -(num_shots - 1) as f32 * angle_step / 2.0;
 
-    for i in 0..num_shots {
+    let shots = (0..num_shots).flat_map(|i| {
         let angle_offset = (i as f32).mul_add(angle_step, start_offset);
-        let shot_yaw = face_dir + angle_offset;
+        pellet_offsets(weapon)
+            .map(move |(yaw_offset, pitch_offset)| (face_dir + angle_offset + yaw_offset, face_pitch + pitch_offset))
+    });
 
-        let pitch_sin = face_pitch.sin();
-        let pitch_cos = face_pitch.cos();
+    for (shot_yaw, shot_pitch) in shots {
+        let pitch_sin = shot_pitch.sin();
+        let pitch_cos = shot_pitch.cos();
 
         // Aim direction vector using yaw + pitch (unit length)
         let dir_x = shot_yaw.sin() * pitch_cos;
@@ -81,20 +86,33 @@ pub fn calculate_projectile_spawns(
         spawns.push(ProjectileSpawnInfo {
             position: spawn_position,
             direction_yaw: shot_yaw,
-            direction_pitch: face_pitch,
+            direction_pitch: shot_pitch,
         });
     }
 
     spawns
 }
 
+// Yaw and pitch offsets (radians) of the pellets of one shot. The first pellet flies straight, the others are
+// spread evenly on a ring at the edge of the weapon's cone, so every client computes the same pattern.
+fn pellet_offsets(weapon: WeaponId) -> impl Iterator<Item = (f32, f32)> {
+    let def = weapon.def();
+    let ring_pellets = def.pellets.saturating_sub(1);
+    let half_spread = def.spread.to_radians() / 2.0;
+
+    std::iter::once((0.0, 0.0)).chain((0..ring_pellets).map(move |k| {
+        let angle = std::f32::consts::TAU * f32::from(k) / f32::from(ring_pellets);
+        (half_spread * angle.cos(), half_spread * angle.sin())
+    }))
+}
+
 fn is_blocked_by_wall(camera_pos: &Position, spawn_position: &Position, walls: &[Wall]) -> bool {
-    // If the spawn height sits above the top of ground walls, skip wall blocking (roof-edge shots, ramps)
-    let spawn_above_walls = spawn_position.y - PROJECTILE_RADIUS >= WALL_HEIGHT;
-    !spawn_above_walls
-        && walls
-            .iter()
-            .any(|wall| sweep_player_vs_wall(camera_pos, spawn_position, wall))
+    // Only walls at the spawn height can block (shots above ground walls from roofs and ramps pass)
+    walls.iter().any(|wall| {
+        let spawn_within_wall_height =
+            spawn_position.y - PROJECTILE_RADIUS < wall.y + WALL_HEIGHT && spawn_position.y > wall.y;
+        spawn_within_wall_height && sweep_player_vs_wall(camera_pos, spawn_position, wall)
+    })
 }
 
 fn is_blocked_by_ramp(spawn_position: &Position, ramps: &[Ramp]) -> bool {
@@ -107,7 +125,7 @@ fn is_blocked_by_ramp(spawn_position: &Position, ramps: &[Ramp]) -> bool {
             return false;
         }
 
-        let ramp_height = height_on_ramp(&[*ramp], spawn_position.x, spawn_position.z);
+        let ramp_height = height_on_ramp(&[*ramp], spawn_position.x, spawn_position.z, spawn_position.y);
         ramp_height > 0.0 && spawn_position.y - PROJECTILE_RADIUS <= ramp_height
     })
 }
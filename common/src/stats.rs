@@ -0,0 +1,106 @@
+use bincode::{Decode, Encode};
+
+use crate::{
+    power_ups::{POWER_UP_COUNT, PowerUpId},
+    protocol::PlayerId,
+};
+
+// ============================================================================
+// Player Statistics
+// ============================================================================
+
+// Statistics of a player for the current match
+#[derive(Debug, Copy, Clone, PartialEq, Default, Encode, Decode)]
+pub struct PlayerStats {
+    pub shots_fired: u32,                 // Projectiles fired (every pellet of a shot counts)
+    pub hits_landed: u32,                 // Projectile hits scored on other players
+    pub hits_taken: u32,                  // Projectile hits suffered (hits absorbed by a shield don't count)
+    pub cookies_eaten: u32,               // Cookies collected
+    pub power_ups: [u16; POWER_UP_COUNT], // Power-ups collected, indexed by `PowerUpId`
+    pub sentry_stuns: u32,                // Times stunned by a sentry
+    pub sentries_hunted: u32,             // Sentries hit with the sentry hunt power-up
+    pub distance: f32,                    // meters travelled
+}
+
+impl PlayerStats {
+    // Fraction of fired projectiles that hit another player (explosions hitting several players count once per
+    // player, so this is capped at 1.0)
+    #[must_use]
+    pub fn accuracy(&self) -> f32 {
+        if self.shots_fired == 0 {
+            return 0.0;
+        }
+        (self.hits_landed as f32 / self.shots_fired as f32).min(1.0)
+    }
+
+    // Count a collected power-up
+    pub fn record_power_up(&mut self, power_up: PowerUpId) {
+        if let Some(count) = self.power_ups.get_mut(power_up.0 as usize) {
+            *count = count.saturating_add(1);
+        }
+    }
+
+    // Total number of power-ups collected
+    #[must_use]
+    pub fn power_ups_collected(&self) -> u32 {
+        self.power_ups.iter().map(|count| u32::from(*count)).sum()
+    }
+}
+
+// Scoreboard row of a player
+#[derive(Debug, Clone, PartialEq, Encode, Decode)]
+pub struct ScoreboardEntry {
+    pub id: PlayerId,
+    pub name: String,
+    pub score: i32,
+    pub stats: PlayerStats,
+}
+
+// ============================================================================
+// Leaderboard
+// ============================================================================
+
+// All-time aggregate of a player name over all finished matches
+#[derive(Debug, Clone, PartialEq, Eq, Default, Encode, Decode)]
+pub struct LeaderboardEntry {
+    pub name: String,
+    pub games_played: u32,
+    pub total_score: i64,
+    pub best_score: i32,
+    pub shots_fired: u64, // Projectiles fired over all matches
+    pub hits_landed: u64, // Projectile hits scored over all matches
+}
+
+impl LeaderboardEntry {
+    // Empty aggregate for a name that has not finished a match yet
+    #[must_use]
+    pub const fn new(name: String) -> Self {
+        Self {
+            name,
+            games_played: 0,
+            total_score: 0,
+            best_score: i32::MIN,
+            shots_fired: 0,
+            hits_landed: 0,
+        }
+    }
+
+    // Add the result of a finished match
+    pub fn record_match(&mut self, score: i32, stats: &PlayerStats) {
+        self.games_played += 1;
+        self.total_score += i64::from(score);
+        self.best_score = self.best_score.max(score);
+        self.shots_fired += u64::from(stats.shots_fired);
+        self.hits_landed += u64::from(stats.hits_landed);
+    }
+
+    // Fraction of fired projectiles that hit another player over all matches (capped at 1.0 like
+    // `PlayerStats::accuracy`)
+    #[must_use]
+    pub fn accuracy(&self) -> f32 {
+        if self.shots_fired == 0 {
+            return 0.0;
+        }
+        (self.hits_landed as f32 / self.shots_fired as f32).min(1.0)
+    }
+}
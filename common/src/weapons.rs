@@ -0,0 +1,137 @@
+use bincode::{Decode, Encode};
+
+use crate::constants::{
+    PROJECTILE_BOUNCE_RETENTION, PROJECTILE_COOLDOWN_TIME, PROJECTILE_DRAG_FACTOR, PROJECTILE_GRAVITY,
+    PROJECTILE_LIFETIME, PROJECTILE_SPEED,
+};
+
+// ============================================================================
+// Weapon Table
+// ============================================================================
+
+// Weapon ID - index into `WEAPONS`
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Encode, Decode)]
+pub enum WeaponId {
+    Ball,    // Bouncing ball (also fired by turret sentries)
+    Rail,    // Fast, straight shot that stops at the first obstacle
+    Grenade, // Lobbed, bouncing shot that explodes when its fuse runs out or on a direct hit
+    Shotgun, // Short-lived cone of pellets
+    Rocket,  // Slow, straight shot that explodes on impact
+}
+
+// Explosion of an explosive weapon's projectile
+#[derive(Debug)]
+pub struct ExplosionDef {
+    pub radius: f32,     // meters - players this close to the detonation (with no wall in between) are hit
+    pub knockback: f32,  // m/s - push at the center of the explosion, fading to zero at the radius
+    pub on_impact: bool, // Detonate on walls, roofs, ramps and ground (otherwise only on a direct hit or timeout)
+}
+
+// Definition of a weapon
+#[derive(Debug)]
+pub struct WeaponDef {
+    pub name: &'static str,
+    pub fire_interval: f32,              // seconds between shots
+    pub speed: f32,                      // meters per second
+    pub gravity: f32,                    // meters per second squared
+    pub drag: f32,                       // deceleration factor, multiplied by speed squared
+    pub bounces: bool,                   // Bounce off walls, roofs, ramps and ground (otherwise stop there)
+    pub bounce_retention: f32,           // fraction of speed retained after a head-on bounce (0.0-1.0)
+    pub lifetime: f32,                   // seconds
+    pub pellets: u8,                     // Projectiles per shot
+    pub spread: f32,                     // degrees - cone width the pellets are spread over
+    pub explosion: Option<ExplosionDef>, // Detonate on a direct hit or when the lifetime runs out
+}
+
+pub static WEAPONS: [WeaponDef; 5] = [
+    WeaponDef {
+        name: "Ball",
+        fire_interval: PROJECTILE_COOLDOWN_TIME,
+        speed: PROJECTILE_SPEED,
+        gravity: PROJECTILE_GRAVITY,
+        drag: PROJECTILE_DRAG_FACTOR,
+        bounces: true,
+        bounce_retention: PROJECTILE_BOUNCE_RETENTION,
+        lifetime: PROJECTILE_LIFETIME,
+        pellets: 1,
+        spread: 0.0,
+        explosion: None,
+    },
+    WeaponDef {
+        name: "Rail",
+        fire_interval: 0.8,
+        speed: 150.0,
+        gravity: 0.0,
+        drag: 0.0,
+        bounces: false,
+        bounce_retention: 0.0,
+        lifetime: 1.0,
+        pellets: 1,
+        spread: 0.0,
+        explosion: None,
+    },
+    WeaponDef {
+        name: "Grenade",
+        fire_interval: 1.2,
+        speed: 18.0,
+        gravity: 14.0,
+        drag: 0.005,
+        bounces: true,
+        bounce_retention: 0.4,
+        lifetime: 2.0,
+        pellets: 1,
+        spread: 0.0,
+        explosion: Some(ExplosionDef {
+            radius: 4.0,
+            knockback: 10.0,
+            on_impact: false,
+        }),
+    },
+    WeaponDef {
+        name: "Shotgun",
+        fire_interval: 0.7,
+        speed: 45.0,
+        gravity: PROJECTILE_GRAVITY,
+        drag: 0.02,
+        bounces: true,
+        bounce_retention: 0.5,
+        lifetime: 1.5,
+        pellets: 7,
+        spread: 8.0,
+        explosion: None,
+    },
+    WeaponDef {
+        name: "Rocket",
+        fire_interval: 1.5,
+        speed: 30.0,
+        gravity: 0.0,
+        drag: 0.0,
+        bounces: false,
+        bounce_retention: 0.0,
+        lifetime: 3.0,
+        pellets: 1,
+        spread: 0.0,
+        explosion: Some(ExplosionDef {
+            radius: 3.5,
+            knockback: 14.0,
+            on_impact: true,
+        }),
+    },
+];
+
+impl WeaponId {
+    pub const ALL: [Self; 5] = [Self::Ball, Self::Rail, Self::Grenade, Self::Shotgun, Self::Rocket];
+
+    // Definition of the weapon
+    #[must_use]
+    pub const fn def(self) -> &'static WeaponDef {
+        &WEAPONS[self as usize]
+    }
+
+    // Next weapon in `ALL`, wrapping around (negative steps go backwards)
+    #[must_use]
+    pub const fn cycle(self, step: i32) -> Self {
+        let count = Self::ALL.len() as i32;
+        Self::ALL[(self as i32 + step).rem_euclid(count) as usize]
+    }
+}
@@ -0,0 +1,129 @@
+use argon2::{
+    Argon2, PasswordHash, PasswordHasher, PasswordVerifier,
+    password_hash::{Error, SaltString},
+};
+use bincode::{Decode, Encode};
+use blake2::{Blake2b512, Digest};
+use std::{
+    fmt::Write,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use crate::constants::{SESSION_TOKEN_LIFETIME, TOKEN_BYTES};
+
+// ============================================================================
+// Accounts
+// ============================================================================
+
+// Registered player account, persisted in the accounts store
+#[derive(Debug, Clone, Encode, Decode)]
+pub struct Account {
+    pub name: String,
+    pub password_hash: String, // Argon2 PHC string, including the salt
+    pub session: Option<Session>,
+}
+
+// Session of an account, resumed with its token instead of the password
+#[derive(Debug, Clone, Encode, Decode)]
+pub struct Session {
+    pub token_hash: String, // Hex-encoded BLAKE2b hash of the token, so a leaked store can't resume sessions
+    pub expires_at: u64,    // Unix time (seconds)
+}
+
+impl Account {
+    // Start a new session, replacing any earlier one, and return its token
+    pub fn start_session(&mut self) -> String {
+        let token = generate_token();
+        self.session = Some(Session {
+            token_hash: hash_token(&token),
+            expires_at: unix_now() + SESSION_TOKEN_LIFETIME,
+        });
+        token
+    }
+
+    // Resume the session if the token matches and has not expired, extending its lifetime
+    pub fn resume_session(&mut self, token: &str) -> bool {
+        let now = unix_now();
+        match &mut self.session {
+            Some(session) if session.expires_at > now && constant_time_eq(&session.token_hash, &hash_token(token)) => {
+                session.expires_at = now + SESSION_TOKEN_LIFETIME;
+                true
+            }
+            _ => false,
+        }
+    }
+}
+
+// ============================================================================
+// Password Hashing
+// ============================================================================
+
+// Hash a password with a fresh random salt. Slow by design - call off the ECS tick.
+pub fn hash_password(password: &str) -> Result<String, Error> {
+    let salt = SaltString::encode_b64(&rand::random::<[u8; 16]>())?;
+    Ok(Argon2::default().hash_password(password.as_bytes(), &salt)?.to_string())
+}
+
+// Check a password against a stored hash. Slow by design - call off the ECS tick.
+#[must_use]
+pub fn verify_password(password: &str, password_hash: &str) -> bool {
+    PasswordHash::new(password_hash)
+        .is_ok_and(|hash| Argon2::default().verify_password(password.as_bytes(), &hash).is_ok())
+}
+
+// ============================================================================
+// Helper Functions
+// ============================================================================
+
+// Random token, hex-encoded (session and resume tokens)
+#[must_use]
+pub fn generate_token() -> String {
+    hex_encode(&rand::random::<[u8; TOKEN_BYTES]>())
+}
+
+// Hash of a session token as stored. Tokens are random, so a fast unsalted hash is enough.
+fn hash_token(token: &str) -> String {
+    hex_encode(&Blake2b512::digest(token.as_bytes()))
+}
+
+fn hex_encode(bytes: &[u8]) -> String {
+    bytes
+        .iter()
+        .fold(String::with_capacity(bytes.len() * 2), |mut hex, byte| {
+            let _ = write!(hex, "{byte:02x}");
+            hex
+        })
+}
+
+// Compare without an early exit, so response times don't leak how much of a token matched
+fn constant_time_eq(a: &str, b: &str) -> bool {
+    a.len() == b.len() && a.bytes().zip(b.bytes()).fold(0, |diff, (x, y)| diff | (x ^ y)) == 0
+}
+
+fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |duration| duration.as_secs())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sessions_store_only_the_token_hash() {
+        let mut account = Account {
+            name: "Alice".to_string(),
+            password_hash: String::new(),
+            session: None,
+        };
+        let token = account.start_session();
+        assert_ne!(
+            account.session.as_ref().map(|session| &session.token_hash),
+            Some(&token)
+        );
+
+        assert!(account.resume_session(&token));
+        assert!(!account.resume_session(&generate_token()));
+    }
+}
@@ -8,6 +8,8 @@ pub const WALL_2ND_PROBABILITY_RATIO: f64 = 5.0; // Probability of 2nd wall rela
 pub const WALL_3RD_PROBABILITY_RATIO: f64 = 0.2; // Probability of 3rd wall relative to 1st
 pub const WALL_OVERLAP: bool = false; // Non-overlapping mode by default
 pub const WALL_MERGE_SEGMENTS: bool = true; // Reduce draw calls
+pub const WALL_DESTRUCTIBLE_SEGMENTS: usize = 8; // Interior walls that can be destroyed
+pub const WALL_DESTRUCTIBLE_HIT_POINTS: i32 = 10; // Projectile hits until a destructible wall breaks
 
 // Roofs
 pub const ROOF_NUM_SEGMENTS: usize = 45;
@@ -21,11 +23,22 @@ pub const RAMP_LENGTH_CELLS: i32 = 2; // Run length in grid cells
 pub const RAMP_WIDTH_CELLS: i32 = 1; // Footprint width in grid cells
 pub const RAMP_MIN_SEPARATION_CELLS: i32 = 3; // Minimum empty cells between ramps
 
+// Upper tier (ramps from roofs to upper decks)
+pub const UPPER_RAMP_COUNT: usize = 2; // Max number of upper ramps
+pub const UPPER_ROOF_NUM_SEGMENTS: usize = 8; // Max number of upper deck cells
+
+// Jump pads
+pub const JUMP_PAD_COUNT: usize = 4; // Max number of jump pads
+
 // ============================================================================
 // Lighting
 // ============================================================================
 
 pub const WALL_LIGHT_HEIGHT: f32 = 2.5; // meters above ground
+pub const LIGHT_SENTRY_DARK_RADIUS: f32 = 12.0; // meters around a targeting sentry where lights go dark
+pub const LIGHT_BLACKOUT_MIN_INTERVAL: f32 = 60.0; // seconds between blackouts
+pub const LIGHT_BLACKOUT_MAX_INTERVAL: f32 = 120.0; // seconds between blackouts
+pub const LIGHT_BLACKOUT_DURATION: f32 = 8.0; // seconds
 
 // ============================================================================
 // Sentries
@@ -37,10 +50,59 @@ pub const SENTRY_FOLLOW_SPEED: f32 = 8.0; // m/s (follow mode)
 pub const SENTRY_RANDOM_TURN_PROBABILITY: f64 = 0.3;
 pub const SENTRY_TARGET_DURATION: f32 = 10.0; // seconds
 pub const SENTRY_COOLDOWN_DURATION: f32 = 15.0; // seconds
-pub const SENTRY_VISION_RANGE: f32 = 64.0; // meters (whole map)
+pub const SENTRY_VISION_RANGE: f32 = 30.0; // meters
+pub const SENTRY_VISION_HALF_ANGLE_DEGREES: f32 = 60.0; // Half of the forward vision cone
+pub const SENTRY_VISION_SUSPICION_RATE: f32 = 2.0; // suspicion per second for a moving player up close
+pub const SENTRY_IDLE_VISIBILITY: f32 = 0.3; // Vision stimulus of idle players relative to moving ones
+pub const SENTRY_SHOT_HEARING_RANGE: f32 = 25.0; // meters
+pub const SENTRY_SHOT_HEARING_DURATION: f32 = 0.5; // seconds a gunshot stays audible
+pub const SENTRY_SHOT_SUSPICION_RATE: f32 = 3.0; // suspicion per second for a gunshot up close
+pub const SENTRY_STEP_HEARING_RANGE: f32 = 8.0; // meters (running players)
+pub const SENTRY_STEP_SUSPICION_RATE: f32 = 1.0; // suspicion per second for a running player up close
+pub const SENTRY_SUSPICION_THRESHOLD: f32 = 1.0; // Suspicion at which a sentry commits to a target
+pub const SENTRY_SUSPICION_DECAY: f32 = 0.25; // suspicion lost per second without stimulus
+pub const SENTRY_MAX_LEVEL: u8 = 1; // Highest level sentries walk on (roofs, upper decks stay out of reach)
+pub const SENTRY_FLEE_SEARCH_CELLS: i32 = 6; // How far (in cells) a fleeing sentry looks for a safe cell
+pub const SENTRY_SIGHTING_DURATION: f32 = 5.0; // seconds a reported player position stays relevant
+pub const SENTRY_SIGHTING_RESPONSE_RANGE: f32 = 20.0; // meters around a sighting within which sentries respond
+pub const SENTRY_MAX_PURSUERS: usize = 2; // Sentries attacking the same player at once
+pub const SENTRY_INTERCEPT_LOOKAHEAD: f32 = 1.5; // seconds ahead of the player that cutting-off sentries aim
+pub const SENTRY_ROUTE_RESERVATION_CELLS: usize = 4; // Cells ahead a targeting sentry reserves on its route
+pub const SENTRY_RESERVED_CELL_COST: i32 = 4; // Extra path cost of cells reserved or occupied by other sentries
 pub const SENTRY_STUN_DURATION: f32 = 3.0; // seconds
 pub const SENTRY_HIT_PENALTY: i32 = 10; // points lost when hit
 pub const SENTRY_HIT_REWARD: i32 = 20; // points for hitting fleeing sentry
+pub const SENTRY_DIRECTOR_INTERVAL: f32 = 5.0; // seconds between director adjustments
+pub const SENTRY_DIRECTOR_SENTRIES_PER_PLAYER: usize = 1; // Extra sentries per connected player beyond the first
+pub const SENTRY_DIRECTOR_MAX_SENTRIES: usize = 16; // Upper limit when adding sentries for players
+pub const SENTRY_DIRECTOR_SPAWN_CLEARANCE: f32 = 16.0; // meters between players and newly spawned sentries
+pub const SENTRY_DIRECTOR_LEAD_DIFFICULTY: f32 = 0.01; // difficulty per point the leader is ahead
+pub const SENTRY_DIRECTOR_STUN_WINDOW: f32 = 60.0; // seconds of stuns the director looks back on
+pub const SENTRY_DIRECTOR_STUN_RELIEF: f32 = 0.1; // difficulty removed per recent stun per player
+pub const SENTRY_DIRECTOR_MIN_DIFFICULTY: f32 = 0.7;
+pub const SENTRY_DIRECTOR_MAX_DIFFICULTY: f32 = 1.4;
+
+// ============================================================================
+// Matches
+// ============================================================================
+
+pub const MATCH_DURATION: f32 = 300.0; // seconds
+pub const SCOREBOARD_INTERVAL: f32 = 1.0; // seconds between scoreboard broadcasts
+
+// ============================================================================
+// Leaderboard
+// ============================================================================
+
+pub const LEADERBOARD_FILE: &str = "leaderboard.bin"; // Store file in the data directory
+pub const LEADERBOARD_SIZE: usize = 20; // Entries sent to clients
+
+// ============================================================================
+// Accounts
+// ============================================================================
+
+pub const ACCOUNTS_FILE: &str = "accounts.bin"; // Store file in the data directory
+pub const TOKEN_BYTES: usize = 32; // Random bytes per session or resume token (sent hex-encoded)
+pub const SESSION_TOKEN_LIFETIME: u64 = 30 * 24 * 60 * 60; // seconds a session stays valid since its last use
 
 // ============================================================================
 // Cookies
@@ -58,10 +120,8 @@ pub const ITEM_LIFETIME: f32 = 60.0; // seconds
 pub const ITEM_COLLECTION_RADIUS: f32 = 1.0; // meters
 
 // ============================================================================
-// Power-Ups
+// Cloak
 // ============================================================================
 
-pub const POWER_UP_SPEED_DURATION: f32 = 20.0; // seconds
-pub const POWER_UP_MULTI_SHOT_DURATION: f32 = 20.0; // seconds
-pub const POWER_UP_PHASING_DURATION: f32 = 15.0; // seconds
-pub const POWER_UP_SENTRY_HUNT_DURATION: f32 = 15.0; // seconds
+pub const CLOAK_REVEAL_DISTANCE: f32 = 6.0; // meters within which cloaked players stay visible to others
+pub const CLOAK_REVEAL_DURATION: f32 = 1.0; // seconds cloaked players stay visible after firing
@@ -1,6 +1,8 @@
+pub mod accounts;
 pub mod config;
 pub mod constants;
 pub mod map;
 pub mod net;
 pub mod resources;
+pub mod store;
 pub mod systems;
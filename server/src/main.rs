@@ -2,23 +2,28 @@ use anyhow::Result;
 use bevy::prelude::*;
 use clap::Parser;
 use quinn::Endpoint;
-use std::net::SocketAddr;
+use std::{net::SocketAddr, path::PathBuf};
 use tokio::{
+    runtime::Handle,
     sync::mpsc::unbounded_channel,
     time::{self, Duration, Instant, MissedTickBehavior},
 };
 
-use common::constants::{GRID_COLS, GRID_ROWS};
+use common::{
+    constants::{GRID_COLS, GRID_ROWS, SERVER_TICKS_PER_SECOND},
+    stats::LeaderboardEntry,
+};
 use server::{
+    accounts::Account,
     config::configure_server,
-    constants::SENTRIES_NUM,
+    constants::{ACCOUNTS_FILE, LEADERBOARD_FILE, SENTRIES_NUM},
     map::generate_grid,
     net::accept_connections_task,
     resources::*,
-    systems::{items::*, network::*, players::*, projectiles::*, sentries::*},
+    store::{load_store, store_task},
+    systems::{items::*, lights::*, matches::*, network::*, players::*, projectiles::*, sentries::*},
 };
 
-const SERVER_LOOP_FREQUENCY: u64 = 30;
 const LOG_FILTER: &str = "wgpu=error,naga=warn";
 
 // ============================================================================
@@ -35,6 +40,10 @@ struct Args {
     // Number of sentries to spawn
     #[arg(long)]
     num_sentries: Option<u32>,
+
+    // Directory for persistent data (leaderboard, accounts)
+    #[arg(long, default_value = "data")]
+    data_dir: PathBuf,
 }
 
 // ============================================================================
@@ -56,14 +65,35 @@ async fn main() -> Result<()> {
     let (to_server, from_clients) = unbounded_channel();
 
     tokio::spawn(accept_connections_task(endpoint, to_server_from_accept, to_server));
+
+    // Load the leaderboard and accounts, and store updates from separate tasks, off the ECS tick
+    let leaderboard_path = args.data_dir.join(LEADERBOARD_FILE);
+    let leaderboard_entries: Vec<LeaderboardEntry> = load_store(&leaderboard_path)?;
+    info!(
+        "loaded {} leaderboard entries from {}",
+        leaderboard_entries.len(),
+        leaderboard_path.display()
+    );
+    let (to_leaderboard_store, from_server) = unbounded_channel();
+    tokio::spawn(store_task(leaderboard_path, from_server));
+
+    let accounts_path = args.data_dir.join(ACCOUNTS_FILE);
+    let accounts: Vec<Account> = load_store(&accounts_path)?;
+    info!("loaded {} accounts from {}", accounts.len(), accounts_path.display());
+    let (to_accounts_store, from_server) = unbounded_channel();
+    tokio::spawn(store_task(accounts_path, from_server));
+
     let mut app = App::new();
 
-    let (map_layout, grid_config) = generate_grid();
+    let (map_layout, grid_config, destructible_wall_map) = generate_grid();
     info!(
-        "generated {} wall segments, {} roofs, {} ramps",
+        "generated {} wall segments ({} destructible), {} upper wall segments, {} roofs, {} ramps, {} jump pads",
         map_layout.lower_walls.len(),
+        map_layout.destructible_walls.len(),
+        map_layout.upper_walls.len(),
         map_layout.roofs.len(),
-        map_layout.ramps.len()
+        map_layout.ramps.len(),
+        map_layout.jump_pads.len()
     );
 
     let sentry_spawn_config = SentrySpawnConfig {
@@ -78,12 +108,27 @@ async fn main() -> Result<()> {
         })
         .insert_resource(map_layout)
         .insert_resource(grid_config)
+        .insert_resource(destructible_wall_map)
         .insert_resource(sentry_spawn_config)
         .insert_resource(PlayerMap::default())
         .insert_resource(ItemMap::default())
         .insert_resource(SentryMap::default())
         .insert_resource(SentryGrid(vec![vec![None; GRID_COLS as usize]; GRID_ROWS as usize]))
+        .insert_resource(SentryBlackboard::default())
+        .insert_resource(SentryDirector::default())
         .insert_resource(ItemSpawner::default())
+        .insert_resource(NextProjectileId::default())
+        .insert_resource(MatchClock::default())
+        .insert_resource(DisconnectedPlayers::default())
+        .insert_resource(Leaderboard {
+            entries: leaderboard_entries
+                .into_iter()
+                .map(|entry| (entry.name.clone(), entry))
+                .collect(),
+            to_store: to_leaderboard_store,
+        })
+        .insert_resource(Accounts::new(accounts, to_accounts_store, Handle::current()))
+        .insert_resource(LightEventScheduler::default())
         .insert_resource(FromAcceptChannel::new(from_accept))
         .insert_resource(FromClientsChannel::new(from_clients))
         .add_systems(
@@ -104,7 +149,9 @@ async fn main() -> Result<()> {
                 // Game logic systems can run in parallel
                 players_movement_system,
                 players_timer_system,
+                players_cloak_system,
                 sentries_spawn_system,
+                sentries_director_system,
                 sentries_movement_system,
                 sentry_player_collision_system,
                 projectiles_movement_system,
@@ -113,13 +160,16 @@ async fn main() -> Result<()> {
                 item_despawn_system,
                 item_collection_system,
                 item_respawn_system,
+                lights_event_system,
+                match_clock_system,
+                network_disconnected_players_system,
             ),
         );
 
     info!("starting ECS server loop...");
 
-    // Run the app in a loop manually at LOOP_FREQUENCY Hz
-    let tick_duration = Duration::from_nanos(1_000_000_000 / SERVER_LOOP_FREQUENCY);
+    // Run the app in a loop manually at SERVER_TICKS_PER_SECOND Hz
+    let tick_duration = Duration::from_nanos(1_000_000_000 / SERVER_TICKS_PER_SECOND);
     let mut interval = time::interval(tick_duration);
     interval.set_missed_tick_behavior(MissedTickBehavior::Skip);
 
@@ -36,7 +36,7 @@ pub fn find_unoccupied_cell(rng: &mut ThreadRng, occupied_cells: &HashSet<(i32,
     None
 }
 
-// Find an unoccupied cell that's not on a ramp
+// Find an unoccupied cell that's not on a ramp or jump pad
 #[allow(clippy::implicit_hasher)]
 pub fn find_unoccupied_cell_not_ramp(
     rng: &mut ThreadRng,
@@ -47,7 +47,8 @@ pub fn find_unoccupied_cell_not_ramp(
     for _ in 0..MAX_ATTEMPTS {
         let grid_x = rng.random_range(0..GRID_COLS);
         let grid_z = rng.random_range(0..GRID_ROWS);
-        if !occupied_cells.contains(&(grid_x, grid_z)) && !grid[grid_z as usize][grid_x as usize].has_ramp {
+        let cell = grid[grid_z as usize][grid_x as usize];
+        if !occupied_cells.contains(&(grid_x, grid_z)) && !cell.has_ramp && !cell.has_jump_pad {
             return Some((grid_x, grid_z));
         }
     }
@@ -0,0 +1,71 @@
+use rand::Rng;
+
+use super::helpers::cell_center;
+use crate::{constants::JUMP_PAD_COUNT, resources::GridCell};
+use common::{map::level_height, protocol::JumpPad};
+
+// Generate jump pads on open ground cells next to a roof, as an alternative route up to ramps.
+// Each pad launches players onto the center of an adjacent roof cell without an upper deck above it.
+pub fn generate_jump_pads(grid: &mut [Vec<GridCell>], grid_cols: i32, grid_rows: i32) -> Vec<JumpPad> {
+    let mut rng = rand::rng();
+    let mut jump_pads = Vec::new();
+
+    // Collect all (pad cell, landing cell) candidates as ((row, col), (row, col))
+    let mut candidates = Vec::new();
+    for row in 0..grid_rows {
+        for col in 0..grid_cols {
+            let cell = grid[row as usize][col as usize];
+            if cell.has_roof || cell.has_ramp {
+                continue;
+            }
+
+            for (d_row, d_col) in [(-1, 0), (1, 0), (0, -1), (0, 1)] {
+                let (n_row, n_col) = (row + d_row, col + d_col);
+                if n_row < 0 || n_row >= grid_rows || n_col < 0 || n_col >= grid_cols {
+                    continue;
+                }
+
+                let neighbor = grid[n_row as usize][n_col as usize];
+                if neighbor.has_roof && !neighbor.has_ramp && !neighbor.has_upper_ramp && !neighbor.has_upper_roof {
+                    candidates.push(((row, col), (n_row, n_col)));
+                }
+            }
+        }
+    }
+
+    // Shuffle randomly
+    for i in (1..candidates.len()).rev() {
+        let j = rng.random_range(0..=i);
+        candidates.swap(i, j);
+    }
+
+    for ((row, col), (target_row, target_col)) in candidates {
+        if jump_pads.len() >= JUMP_PAD_COUNT {
+            break;
+        }
+
+        // One pad per cell, and never right next to another pad
+        let near_pad = (row - 1..=row + 1).any(|r| {
+            (col - 1..=col + 1).any(|c| {
+                (0..grid_rows).contains(&r) && (0..grid_cols).contains(&c) && grid[r as usize][c as usize].has_jump_pad
+            })
+        });
+        if near_pad {
+            continue;
+        }
+
+        grid[row as usize][col as usize].has_jump_pad = true;
+
+        let pad_center = cell_center(col, row);
+        let target_center = cell_center(target_col, target_row);
+        jump_pads.push(JumpPad {
+            x: pad_center.x,
+            z: pad_center.z,
+            target_x: target_center.x,
+            target_y: level_height(1),
+            target_z: target_center.z,
+        });
+    }
+
+    jump_pads
+}
@@ -1,7 +1,7 @@
 use crate::{constants::WALL_LIGHT_HEIGHT, resources::GridCell};
 use common::{
     constants::{FIELD_DEPTH, FIELD_WIDTH, GRID_SIZE, WALL_THICKNESS},
-    protocol::{Position, WallLight},
+    protocol::{LightState, Position, WallLight},
 };
 
 const MODEL_INSET: f32 = WALL_THICKNESS / 2.0 + 0.02; // place fixture just inside the cell away from wall surface
@@ -34,6 +34,7 @@ pub fn generate_wall_lights(grid: &[Vec<GridCell>]) -> Vec<WallLight> {
                         z: model_z,
                     },
                     yaw: 0.0,
+                    state: LightState::default(),
                 });
             }
 
@@ -48,6 +49,7 @@ pub fn generate_wall_lights(grid: &[Vec<GridCell>]) -> Vec<WallLight> {
                         z: model_z,
                     },
                     yaw: std::f32::consts::PI,
+                    state: LightState::default(),
                 });
             }
 
@@ -62,6 +64,7 @@ pub fn generate_wall_lights(grid: &[Vec<GridCell>]) -> Vec<WallLight> {
                         z: cell_center_z,
                     },
                     yaw: std::f32::consts::FRAC_PI_2,
+                    state: LightState::default(),
                 });
             }
 
@@ -76,6 +79,7 @@ pub fn generate_wall_lights(grid: &[Vec<GridCell>]) -> Vec<WallLight> {
                         z: cell_center_z,
                     },
                     yaw: -std::f32::consts::FRAC_PI_2,
+                    state: LightState::default(),
                 });
             }
         }
@@ -1,5 +1,6 @@
 mod grid;
 mod helpers;
+mod jump_pads;
 mod lights;
 mod ramps;
 mod roofs;
@@ -9,22 +10,22 @@ use rand::Rng;
 
 use crate::{
     constants::{
-        ROOF_MERGE_SEGMENTS, ROOF_OVERLAP, WALL_2ND_PROBABILITY_RATIO, WALL_3RD_PROBABILITY_RATIO, WALL_MERGE_SEGMENTS,
-        WALL_NUM_SEGMENTS, WALL_OVERLAP,
+        ROOF_MERGE_SEGMENTS, ROOF_OVERLAP, WALL_2ND_PROBABILITY_RATIO, WALL_3RD_PROBABILITY_RATIO,
+        WALL_DESTRUCTIBLE_HIT_POINTS, WALL_MERGE_SEGMENTS, WALL_NUM_SEGMENTS, WALL_OVERLAP,
     },
-    resources::{GridCell, GridConfig},
+    resources::{DestructibleWallInfo, DestructibleWallMap, GridCell, GridConfig},
 };
 use common::{
     constants::*,
-    protocol::{MapLayout, Wall},
+    protocol::{DestructibleWall, MapLayout, Wall, WallId},
 };
 use lights::generate_wall_lights;
 
 pub use helpers::{cell_center, find_unoccupied_cell, find_unoccupied_cell_not_ramp, grid_coords_from_position};
 
-// Generate a complete map grid with walls, roofs, and ramps
+// Generate a complete map grid with walls, roofs, ramps, and destructible walls
 #[must_use]
-pub fn generate_grid() -> (MapLayout, GridConfig) {
+pub fn generate_grid() -> (MapLayout, GridConfig, DestructibleWallMap) {
     let mut rng = rand::rng();
 
     // Calculate grid dimensions
@@ -130,22 +131,10 @@ pub fn generate_grid() -> (MapLayout, GridConfig) {
         // Count existing walls in both cells adjacent to this potential wall
         let cell1_walls = helpers::count_cell_walls(*cell);
         let cell2_walls = match direction {
-            0 => {
-                // South wall - check cell below
-                if row < grid_rows - 1 {
-                    helpers::count_cell_walls(grid[(row + 1) as usize][col as usize])
-                } else {
-                    0
-                }
-            }
-            1 => {
-                // East wall - check cell to the right
-                if col < grid_cols - 1 {
-                    helpers::count_cell_walls(grid[row as usize][(col + 1) as usize])
-                } else {
-                    0
-                }
-            }
+            // South wall - check cell below
+            0 if row < grid_rows - 1 => helpers::count_cell_walls(grid[(row + 1) as usize][col as usize]),
+            // East wall - check cell to the right
+            1 if col < grid_cols - 1 => helpers::count_cell_walls(grid[row as usize][(col + 1) as usize]),
             _ => 0,
         };
 
@@ -202,22 +191,57 @@ pub fn generate_grid() -> (MapLayout, GridConfig) {
         }
     }
 
-    // Build wall list from grid with individual segments
-    let mut lower_walls = walls::generate_lower_walls(&grid, grid_cols, grid_rows);
-    if WALL_MERGE_SEGMENTS && !WALL_OVERLAP {
-        lower_walls = walls::merge_walls(lower_walls);
-    }
-
     // Generate roofs based on grid
-    let (mut roofs, grid) = roofs::generate_roofs(grid, grid_cols, grid_rows);
+    let (mut roofs, mut grid) = roofs::generate_roofs(grid, grid_cols, grid_rows);
+
+    // Generate upper tier: ramps from the roof to upper decks
+    let upper_ramps = ramps::generate_upper_ramps(&mut grid, grid_cols, grid_rows);
+    roofs.extend(roofs::generate_upper_roofs(&mut grid, grid_cols, grid_rows));
     if ROOF_MERGE_SEGMENTS && !ROOF_OVERLAP {
         roofs = roofs::merge_roofs(roofs);
     }
+    let mut ramps = ramps;
+    ramps.extend(upper_ramps);
+
+    // Generate jump pads next to roofs (after upper decks, which must not be landed under)
+    let jump_pads = jump_pads::generate_jump_pads(&mut grid, grid_cols, grid_rows);
+
+    // Generate walls on roofs that support the upper decks
+    let mut upper_walls = walls::generate_upper_walls(&grid, grid_cols, grid_rows);
+    if WALL_MERGE_SEGMENTS && !WALL_OVERLAP {
+        upper_walls = walls::merge_walls(upper_walls);
+    }
 
-    // Generate walls for roof edges
-    let mut roof_walls = walls::generate_roof_walls(&grid, grid_cols, grid_rows);
+    // Pick destructible walls and leave them out of the regular wall list so they don't get merged
+    let destructible_edges = walls::select_destructible_walls(&grid, grid_cols, grid_rows);
+    let mut solid_grid = GridConfig { grid: grid.clone() };
+    for &(grid_x, grid_z, edge) in &destructible_edges {
+        solid_grid.remove_wall(grid_x, grid_z, edge);
+    }
+
+    // Build wall list from grid with individual segments
+    let mut lower_walls = walls::generate_lower_walls(&solid_grid.grid, grid_cols, grid_rows);
     if WALL_MERGE_SEGMENTS && !WALL_OVERLAP {
-        roof_walls = walls::merge_walls(roof_walls);
+        lower_walls = walls::merge_walls(lower_walls);
+    }
+
+    // Add destructible walls as separate segments
+    let mut destructible_walls = Vec::new();
+    let mut destructible_wall_map = DestructibleWallMap::default();
+    for (i, &(grid_x, grid_z, edge)) in destructible_edges.iter().enumerate() {
+        let id = WallId(i as u32);
+        let wall = walls::generate_destructible_wall(grid_x, grid_z, edge);
+        lower_walls.push(wall);
+        destructible_walls.push(DestructibleWall { id, wall });
+        destructible_wall_map.0.insert(
+            id,
+            DestructibleWallInfo {
+                grid_x,
+                grid_z,
+                edge,
+                hit_points: WALL_DESTRUCTIBLE_HIT_POINTS,
+            },
+        );
     }
 
     // Separate walls into boundary and interior
@@ -240,13 +264,15 @@ pub fn generate_grid() -> (MapLayout, GridConfig) {
         boundary_walls,
         interior_walls,
         lower_walls,
+        upper_walls,
         roofs,
         ramps,
-        roof_walls,
         wall_lights,
+        destructible_walls,
+        jump_pads,
     };
 
     let grid_config = GridConfig { grid };
 
-    (map_layout, grid_config)
+    (map_layout, grid_config, destructible_wall_map)
 }
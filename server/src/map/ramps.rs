@@ -1,10 +1,10 @@
 use rand::Rng;
 
 use crate::{
-    constants::{RAMP_COUNT, RAMP_LENGTH_CELLS, RAMP_MIN_SEPARATION_CELLS, RAMP_WIDTH_CELLS},
+    constants::{RAMP_COUNT, RAMP_LENGTH_CELLS, RAMP_MIN_SEPARATION_CELLS, RAMP_WIDTH_CELLS, UPPER_RAMP_COUNT},
     resources::GridCell,
 };
-use common::{constants::*, protocol::Ramp};
+use common::{constants::*, map::level_height, protocol::Ramp};
 
 // Generate ramps as right triangular prisms using opposite corners
 pub fn generate_ramps(grid: &mut [Vec<GridCell>], grid_cols: i32, grid_rows: i32) -> Vec<Ramp> {
@@ -128,7 +128,91 @@ pub fn generate_ramps(grid: &mut [Vec<GridCell>], grid_cols: i32, grid_rows: i32
             y1: 0.0,
             z1,
             x2,
-            y2: WALL_HEIGHT + ROOF_THICKNESS, // Ramp top goes to top of roof
+            y2: level_height(1), // Ramp top goes to top of roof
+            z2,
+        });
+    }
+
+    ramps
+}
+
+// Generate upper ramps from the roof to the upper deck.
+// Each ramp needs a straight run of free roof cells: a base cell in front of the low edge, the ramp footprint,
+// and a cell behind the high edge, which becomes the seed of an upper deck.
+pub fn generate_upper_ramps(grid: &mut [Vec<GridCell>], grid_cols: i32, grid_rows: i32) -> Vec<Ramp> {
+    let mut rng = rand::rng();
+    let mut ramps = Vec::new();
+
+    if grid_cols < RAMP_LENGTH_CELLS + 2 || grid_rows < RAMP_LENGTH_CELLS + 2 {
+        return ramps;
+    }
+
+    let mut attempts = 0;
+    const MAX_ATTEMPTS: usize = 200;
+
+    while ramps.len() < UPPER_RAMP_COUNT && attempts < MAX_ATTEMPTS {
+        attempts += 1;
+
+        // Random orientation: true = along X axis (west-east), false = along Z axis (north-south)
+        let along_x = rng.random_bool(0.5);
+        let high_at_end = rng.random_bool(0.5);
+
+        // Random position, leaving room for the base and deck cells at both ends
+        let (col0, row0, col_end, row_end) = if along_x {
+            let c0 = rng.random_range(1..=(grid_cols - RAMP_LENGTH_CELLS - 1));
+            let r0 = rng.random_range(0..grid_rows);
+            (c0, r0, c0 + RAMP_LENGTH_CELLS, r0 + 1)
+        } else {
+            let c0 = rng.random_range(0..grid_cols);
+            let r0 = rng.random_range(1..=(grid_rows - RAMP_LENGTH_CELLS - 1));
+            (c0, r0, c0 + 1, r0 + RAMP_LENGTH_CELLS)
+        };
+
+        // Cells in front of the low edge and behind the high edge, as (row, col)
+        let (before, after) = if along_x {
+            ((row0, col0 - 1), (row0, col_end))
+        } else {
+            ((row0 - 1, col0), (row_end, col0))
+        };
+        let (base, top) = if high_at_end { (before, after) } else { (after, before) };
+
+        // All cells must be plain roof cells not used by another upper ramp or deck
+        let is_free_roof = |row: i32, col: i32| {
+            let cell = grid[row as usize][col as usize];
+            cell.has_roof && !cell.has_upper_ramp && !cell.upper_ramp_base && !cell.has_upper_roof
+        };
+        let footprint_free = (row0..row_end).all(|row| (col0..col_end).all(|col| is_free_roof(row, col)));
+        if !footprint_free || !is_free_roof(base.0, base.1) || !is_free_roof(top.0, top.1) {
+            continue;
+        }
+
+        // Mark footprint, base and deck seed
+        for row in row0..row_end {
+            for col in col0..col_end {
+                grid[row as usize][col as usize].has_upper_ramp = true;
+            }
+        }
+        grid[base.0 as usize][base.1 as usize].upper_ramp_base = true;
+        grid[top.0 as usize][top.1 as usize].has_upper_roof = true;
+
+        // Create Ramp: (x1,y1,z1) = low corner, (x2,y2,z2) = high corner
+        let x_start = (col0 as f32).mul_add(GRID_SIZE, -(FIELD_WIDTH / 2.0));
+        let z_start = (row0 as f32).mul_add(GRID_SIZE, -(FIELD_DEPTH / 2.0));
+        let x_end = (col_end as f32).mul_add(GRID_SIZE, -(FIELD_WIDTH / 2.0));
+        let z_end = (row_end as f32).mul_add(GRID_SIZE, -(FIELD_DEPTH / 2.0));
+
+        let (x1, z1, x2, z2) = if high_at_end {
+            (x_start, z_start, x_end, z_end)
+        } else {
+            (x_end, z_end, x_start, z_start)
+        };
+
+        ramps.push(Ramp {
+            x1,
+            y1: level_height(1), // Ramp base sits on the roof
+            z1,
+            x2,
+            y2: level_height(2), // Ramp top goes to top of the upper deck
             z2,
         });
     }
@@ -3,10 +3,10 @@ use std::collections::{HashMap, HashSet, VecDeque};
 
 use super::helpers::count_cell_walls;
 use crate::{
-    constants::{ROOF_NEIGHBOR_PREFERENCE, ROOF_NUM_SEGMENTS, ROOF_OVERLAP},
+    constants::{ROOF_NEIGHBOR_PREFERENCE, ROOF_NUM_SEGMENTS, ROOF_OVERLAP, UPPER_ROOF_NUM_SEGMENTS},
     resources::GridCell,
 };
-use common::{constants::*, protocol::Roof};
+use common::{constants::*, map::level_height, protocol::Roof};
 
 const MERGE_EPS: f32 = 0.01;
 const CORNER_EPS: f32 = 0.01; // Small inset to avoid overlap for edge fillers
@@ -282,6 +282,7 @@ pub fn generate_roofs(mut grid: Vec<Vec<GridCell>>, grid_cols: i32, grid_rows: i
                             x2: fx2,
                             z2: z1,
                             thickness: ROOF_THICKNESS,
+                            y: level_height(1),
                         });
                     }
                 }
@@ -295,6 +296,7 @@ pub fn generate_roofs(mut grid: Vec<Vec<GridCell>>, grid_cols: i32, grid_rows: i
                             x2: fx2,
                             z2: z2 + pad,
                             thickness: ROOF_THICKNESS,
+                            y: level_height(1),
                         });
                     }
                 }
@@ -309,6 +311,7 @@ pub fn generate_roofs(mut grid: Vec<Vec<GridCell>>, grid_cols: i32, grid_rows: i
             x2: world_x2,
             z2: world_z2,
             thickness: ROOF_THICKNESS,
+            y: level_height(1),
         });
 
         roofs.extend(edge_fillers);
@@ -320,6 +323,75 @@ pub fn generate_roofs(mut grid: Vec<Vec<GridCell>>, grid_cols: i32, grid_rows: i
     (roofs, grid)
 }
 
+// Grow upper decks from the cells at the top of upper ramps into neighboring roof cells.
+// Returns one roof segment per deck cell (no merging); the grid gets has_upper_roof flags set.
+#[must_use]
+pub fn generate_upper_roofs(grid: &mut [Vec<GridCell>], grid_cols: i32, grid_rows: i32) -> Vec<Roof> {
+    let mut rng = rand::rng();
+
+    let mut deck_cells: HashSet<(i32, i32)> = HashSet::new();
+    for row in 0..grid_rows {
+        for col in 0..grid_cols {
+            if grid[row as usize][col as usize].has_upper_roof {
+                deck_cells.insert((row, col));
+            }
+        }
+    }
+
+    // No upper ramps means no way up, so no decks
+    if deck_cells.is_empty() {
+        return Vec::new();
+    }
+
+    while deck_cells.len() < UPPER_ROOF_NUM_SEGMENTS {
+        // Candidates are free roof cells next to an existing deck cell
+        let mut candidates: Vec<(i32, i32)> = Vec::new();
+        for &(row, col) in &deck_cells {
+            for (nr, nc) in [(row - 1, col), (row + 1, col), (row, col - 1), (row, col + 1)] {
+                if nr < 0 || nr >= grid_rows || nc < 0 || nc >= grid_cols || deck_cells.contains(&(nr, nc)) {
+                    continue;
+                }
+                let cell = grid[nr as usize][nc as usize];
+                if cell.has_roof && !cell.has_upper_ramp && !cell.upper_ramp_base && !candidates.contains(&(nr, nc)) {
+                    candidates.push((nr, nc));
+                }
+            }
+        }
+
+        if candidates.is_empty() {
+            break; // Decks can't grow any further
+        }
+
+        let pick = candidates[rng.random_range(0..candidates.len())];
+        deck_cells.insert(pick);
+    }
+
+    // Convert deck cells to roof segments, extending outward where there is no neighboring deck
+    let mut roofs = Vec::new();
+    for &(row, col) in &deck_cells {
+        grid[row as usize][col as usize].has_upper_roof = true;
+
+        let extend = |nr: i32, nc: i32| {
+            if deck_cells.contains(&(nr, nc)) {
+                0.0
+            } else {
+                WALL_THICKNESS / 2.0
+            }
+        };
+
+        roofs.push(Roof {
+            x1: (col as f32).mul_add(GRID_SIZE, -(FIELD_WIDTH / 2.0)) - extend(row, col - 1),
+            z1: (row as f32).mul_add(GRID_SIZE, -(FIELD_DEPTH / 2.0)) - extend(row - 1, col),
+            x2: ((col + 1) as f32).mul_add(GRID_SIZE, -(FIELD_WIDTH / 2.0)) + extend(row, col + 1),
+            z2: ((row + 1) as f32).mul_add(GRID_SIZE, -(FIELD_DEPTH / 2.0)) + extend(row + 1, col),
+            thickness: ROOF_THICKNESS,
+            y: level_height(2),
+        });
+    }
+
+    roofs
+}
+
 // Merge adjacent roofs into larger segments
 pub fn merge_roofs(mut roofs: Vec<Roof>) -> Vec<Roof> {
     // Normalize ordering
@@ -354,7 +426,8 @@ pub fn merge_roofs(mut roofs: Vec<Roof>) -> Vec<Roof> {
                     }
                     let b = roofs[j];
                     let same_thickness = (acc.thickness - b.thickness).abs() < MERGE_EPS;
-                    if !same_thickness {
+                    let same_level = (acc.y - b.y).abs() < MERGE_EPS;
+                    if !same_thickness || !same_level {
                         continue;
                     }
 
@@ -1,5 +1,10 @@
-use crate::{constants::WALL_OVERLAP, resources::GridCell};
-use common::{constants::*, protocol::Wall};
+use rand::Rng;
+
+use crate::{
+    constants::{WALL_DESTRUCTIBLE_SEGMENTS, WALL_OVERLAP},
+    resources::{CellEdge, GridCell},
+};
+use common::{constants::*, map::level_height, protocol::Wall};
 
 // Epsilon for merging adjacent walls.
 const MERGE_EPS: f32 = 0.01;
@@ -114,6 +119,7 @@ pub fn generate_lower_walls(grid: &[Vec<GridCell>], grid_cols: i32, grid_rows: i
                 x2,
                 z2: world_z,
                 width: WALL_THICKNESS,
+                y: 0.0,
             });
         }
     }
@@ -156,6 +162,7 @@ pub fn generate_lower_walls(grid: &[Vec<GridCell>], grid_cols: i32, grid_rows: i
                 x2: world_x,
                 z2,
                 width: WALL_THICKNESS,
+                y: 0.0,
             });
         }
     }
@@ -164,88 +171,112 @@ pub fn generate_lower_walls(grid: &[Vec<GridCell>], grid_cols: i32, grid_rows: i
 }
 
 // ============================================================================
-// Roof Edge Wall Generation
+// Upper Wall Generation
 // ============================================================================
 
-// Generate walls for roof edges to prevent players from falling off.
-// Only adds edges where there's no adjacent roof or no ramp connection.
+// Generate walls standing on the roof below upper decks. They mirror the lower walls around deck cells, so a
+// building with an upper deck looks like two identical storeys.
 #[must_use]
-pub fn generate_roof_walls(grid: &[Vec<GridCell>], grid_cols: i32, grid_rows: i32) -> Vec<Wall> {
-    let mut roof_edge_walls = Vec::new();
+pub fn generate_upper_walls(grid: &[Vec<GridCell>], grid_cols: i32, grid_rows: i32) -> Vec<Wall> {
+    let is_deck = |row: i32, col: i32| {
+        (0..grid_rows).contains(&row)
+            && (0..grid_cols).contains(&col)
+            && grid[row as usize][col as usize].has_upper_roof
+    };
+
+    // Keep only wall flags on edges that touch a deck cell
+    let mut deck_grid = grid.to_vec();
+    for row in 0..grid_rows {
+        for col in 0..grid_cols {
+            let deck = is_deck(row, col);
+            let cell = &mut deck_grid[row as usize][col as usize];
+            cell.has_north_wall &= deck || is_deck(row - 1, col);
+            cell.has_south_wall &= deck || is_deck(row + 1, col);
+            cell.has_west_wall &= deck || is_deck(row, col - 1);
+            cell.has_east_wall &= deck || is_deck(row, col + 1);
+        }
+    }
+
+    let y = level_height(1);
+    generate_lower_walls(&deck_grid, grid_cols, grid_rows)
+        .into_iter()
+        .map(|wall| Wall { y, ..wall })
+        .collect()
+}
+
+// ============================================================================
+// Destructible Wall Generation
+// ============================================================================
+
+// Pick random interior walls to become destructible.
+// Walls next to roofs or ramps are never picked, so destroying a wall never leaves a roof or ramp unsupported.
+#[must_use]
+pub fn select_destructible_walls(grid: &[Vec<GridCell>], grid_cols: i32, grid_rows: i32) -> Vec<(i32, i32, CellEdge)> {
+    let mut rng = rand::rng();
+
+    let is_open_cell = |row: i32, col: i32| {
+        let cell = grid[row as usize][col as usize];
+        !cell.has_roof && !cell.has_ramp
+    };
 
+    // Collect candidates as (grid_x, grid_z, edge)
+    let mut candidates = Vec::new();
     for row in 0..grid_rows {
         for col in 0..grid_cols {
             let cell = grid[row as usize][col as usize];
-            if !cell.has_roof {
+            if !is_open_cell(row, col) {
                 continue;
             }
-
-            // Calculate cell boundaries in world coordinates
-            let x1 = (col as f32).mul_add(GRID_SIZE, -(FIELD_WIDTH / 2.0));
-            let x2 = ((col + 1) as f32).mul_add(GRID_SIZE, -(FIELD_WIDTH / 2.0));
-            let z1 = (row as f32).mul_add(GRID_SIZE, -(FIELD_DEPTH / 2.0));
-            let z2 = ((row + 1) as f32).mul_add(GRID_SIZE, -(FIELD_DEPTH / 2.0));
-
-            // Check each edge - add wall if no adjacent roof and no ramp connection
-            // North edge (z1) - check if neighbor to the north has a ramp_top_south (connecting upward to this roof)
-            let has_north_neighbor_roof = row > 0 && grid[(row - 1) as usize][col as usize].has_roof;
-            let has_north_ramp = row > 0 && grid[(row - 1) as usize][col as usize].ramp_top_south;
-            if !has_north_neighbor_roof && !has_north_ramp {
-                roof_edge_walls.push(Wall {
-                    x1,
-                    z1,
-                    x2,
-                    z2: z1,
-                    width: ROOF_WALL_THICKNESS,
-                });
-            }
-
-            // South edge (z2) - check if neighbor to the south has a ramp_top_north
-            let has_south_neighbor_roof = row < grid_rows - 1 && grid[(row + 1) as usize][col as usize].has_roof;
-            let has_south_ramp = row < grid_rows - 1 && grid[(row + 1) as usize][col as usize].ramp_top_north;
-            if !has_south_neighbor_roof && !has_south_ramp {
-                roof_edge_walls.push(Wall {
-                    x1,
-                    z1: z2,
-                    x2,
-                    z2,
-                    width: ROOF_WALL_THICKNESS,
-                });
+            if row < grid_rows - 1 && cell.has_south_wall && is_open_cell(row + 1, col) {
+                candidates.push((col, row, CellEdge::South));
             }
-
-            // West edge (x1) - check if neighbor to the west has a ramp_top_east
-            let has_west_neighbor_roof = col > 0 && grid[row as usize][(col - 1) as usize].has_roof;
-            let has_west_ramp = col > 0 && grid[row as usize][(col - 1) as usize].ramp_top_east;
-            if !has_west_neighbor_roof && !has_west_ramp {
-                roof_edge_walls.push(Wall {
-                    x1,
-                    z1,
-                    x2: x1,
-                    z2,
-                    width: ROOF_WALL_THICKNESS,
-                });
-            }
-
-            // East edge (x2) - check if neighbor to the east has a ramp_top_west
-            let has_east_neighbor_roof = col < grid_cols - 1 && grid[row as usize][(col + 1) as usize].has_roof;
-            let has_east_ramp = col < grid_cols - 1 && grid[row as usize][(col + 1) as usize].ramp_top_west;
-            if !has_east_neighbor_roof && !has_east_ramp {
-                roof_edge_walls.push(Wall {
-                    x1: x2,
-                    z1,
-                    x2,
-                    z2,
-                    width: ROOF_WALL_THICKNESS,
-                });
+            if col < grid_cols - 1 && cell.has_east_wall && is_open_cell(row, col + 1) {
+                candidates.push((col, row, CellEdge::East));
             }
         }
     }
 
-    roof_edge_walls
+    // Shuffle randomly
+    for i in (1..candidates.len()).rev() {
+        let j = rng.random_range(0..=i);
+        candidates.swap(i, j);
+    }
+
+    candidates.truncate(WALL_DESTRUCTIBLE_SEGMENTS);
+    candidates
+}
+
+// Build a single, unmerged wall segment for a destructible wall.
+// Both ends are inset so the segment never overlaps the walls it connects to.
+#[must_use]
+pub fn generate_destructible_wall(grid_x: i32, grid_z: i32, edge: CellEdge) -> Wall {
+    let x1 = (grid_x as f32).mul_add(GRID_SIZE, -(FIELD_WIDTH / 2.0));
+    let x2 = ((grid_x + 1) as f32).mul_add(GRID_SIZE, -(FIELD_WIDTH / 2.0));
+    let z1 = (grid_z as f32).mul_add(GRID_SIZE, -(FIELD_DEPTH / 2.0));
+    let z2 = ((grid_z + 1) as f32).mul_add(GRID_SIZE, -(FIELD_DEPTH / 2.0));
+
+    match edge {
+        CellEdge::South => Wall {
+            x1: x1 + WALL_THICKNESS / 2.0,
+            z1: z2,
+            x2: x2 - WALL_THICKNESS / 2.0,
+            z2,
+            width: WALL_THICKNESS,
+            y: 0.0,
+        },
+        CellEdge::East => Wall {
+            x1: x2,
+            z1: z1 + WALL_THICKNESS / 2.0,
+            x2,
+            z2: z2 - WALL_THICKNESS / 2.0,
+            width: WALL_THICKNESS,
+            y: 0.0,
+        },
+    }
 }
 
 // ============================================================================
-// Wall Merging (generic - works for both lower and roof walls)
+// Wall Merging (generic - works for both lower and upper walls)
 // ============================================================================
 
 // Normalize wall coordinates so they're in consistent order
@@ -272,6 +303,7 @@ fn merge_walls_line(list: Vec<Wall>, is_horizontal: bool, out: &mut Vec<Wall>) {
             if is_horizontal {
                 if (cur.z1 - w.z1).abs() < MERGE_EPS
                     && (cur.width - w.width).abs() < MERGE_EPS
+                    && (cur.y - w.y).abs() < MERGE_EPS
                     && w.x1 <= cur.x2 + MERGE_EPS
                 {
                     cur.x2 = cur.x2.max(w.x2);
@@ -279,6 +311,7 @@ fn merge_walls_line(list: Vec<Wall>, is_horizontal: bool, out: &mut Vec<Wall>) {
                 }
             } else if (cur.x1 - w.x1).abs() < MERGE_EPS
                 && (cur.width - w.width).abs() < MERGE_EPS
+                && (cur.y - w.y).abs() < MERGE_EPS
                 && w.z1 <= cur.z2 + MERGE_EPS
             {
                 cur.z2 = cur.z2.max(w.z2);
@@ -65,11 +65,12 @@ pub enum ClientToServer {
 #[derive(Debug)]
 pub enum ServerToClient {
     Send(ServerMessage),
+    Rebind(PlayerId), // Connection resumed the session of this (dropped) player - tag its messages with the ID
     Close,
 }
 
 pub async fn per_client_network_io_task(
-    id: PlayerId,
+    mut id: PlayerId,
     connection: Connection,
     to_server: UnboundedSender<(PlayerId, ClientToServer)>,
     mut from_server: UnboundedReceiver<ServerToClient>,
@@ -85,15 +86,17 @@ pub async fn per_client_network_io_task(
             }
 
             cmd = from_server.recv() => {
-                if !handle_server_command(id, cmd, &connection, &stream).await {
+                if !handle_server_command(&mut id, cmd, &connection, &stream).await {
                     break;
                 }
             }
         }
     }
 
-    // Ensure disconnect notification is sent before task exits
+    // Ensure disconnect notification is sent before task exits. Close the channel first, so the server can tell
+    // it apart from the channel of a connection that resumed the player in the meantime.
     debug!("{:?} network task exiting", id);
+    drop(from_server);
     let _ = to_server.send((id, ClientToServer::Disconnected));
 }
 
@@ -127,7 +130,7 @@ fn handle_client_message(
 }
 
 async fn handle_server_command(
-    id: PlayerId,
+    id: &mut PlayerId,
     cmd: Option<ServerToClient>,
     connection: &Connection,
     stream: &MessageStream<'_>,
@@ -141,6 +144,11 @@ async fn handle_server_command(
                 .map_err(|e| warn!("error sending to {:?}: {e}", id))
                 .is_ok()
         }
+        Some(ServerToClient::Rebind(resumed_id)) => {
+            debug!("{:?} resumed {:?}", id, resumed_id);
+            *id = resumed_id;
+            true
+        }
         Some(ServerToClient::Close) => {
             debug!("closing connection to {:?}", id);
             connection.close(0u32.into(), b"server closing");
@@ -1,14 +1,24 @@
 use bevy::prelude::*;
-use std::collections::HashMap;
-use tokio::sync::mpsc::{UnboundedReceiver, UnboundedSender, error::TryRecvError};
+use std::collections::{HashMap, HashSet, VecDeque};
+use tokio::{
+    runtime::Handle,
+    sync::mpsc::{UnboundedReceiver, UnboundedSender, error::TryRecvError, unbounded_channel},
+};
 
-use crate::net::{ClientToServer, ServerToClient};
-use common::{
+use crate::{
+    accounts::{Account, hash_password, verify_password},
     constants::{
-        ALWAYS_MULTI_SHOT, ALWAYS_PHASING, ALWAYS_SENTRY_HUNT, ALWAYS_SPEED, FIELD_DEPTH, FIELD_WIDTH, GRID_COLS,
-        GRID_ROWS, GRID_SIZE,
+        LEADERBOARD_SIZE, LIGHT_BLACKOUT_MIN_INTERVAL, MATCH_DURATION, SENTRY_DIRECTOR_INTERVAL,
+        SENTRY_ROUTE_RESERVATION_CELLS, SENTRY_SIGHTING_DURATION,
     },
+    net::{ClientToServer, ServerToClient},
+};
+use common::{
+    constants::{FIELD_DEPTH, FIELD_WIDTH, GRID_COLS, GRID_ROWS, GRID_SIZE},
+    power_ups::{ActiveEffects, EffectKind, PowerUpId, StackingRule, seconds_to_ticks},
     protocol::*,
+    stats::{LeaderboardEntry, PlayerStats, ScoreboardEntry},
+    weapons::WeaponId,
 };
 
 // ============================================================================
@@ -34,6 +44,11 @@ pub struct GridCell {
     pub ramp_top_south: bool,
     pub ramp_top_west: bool,
     pub ramp_top_east: bool,
+    // Upper tier: ramps on the roof leading to an upper deck
+    pub has_upper_roof: bool,  // Cell has an upper deck above its roof
+    pub has_upper_ramp: bool,  // Cell roof occupied by an upper ramp footprint
+    pub upper_ramp_base: bool, // Roof cell in front of an upper ramp's low edge (kept free of decks)
+    pub has_jump_pad: bool,    // Ground cell with a jump pad
 }
 
 // Grid configuration
@@ -42,41 +57,216 @@ pub struct GridConfig {
     pub grid: Vec<Vec<GridCell>>, // [row][col] - indexed by grid_z, grid_x
 }
 
+impl GridConfig {
+    // Clear the wall flags on both sides of the given cell edge.
+    pub fn remove_wall(&mut self, grid_x: i32, grid_z: i32, edge: CellEdge) {
+        let (col, row) = (grid_x as usize, grid_z as usize);
+        match edge {
+            CellEdge::South => {
+                self.grid[row][col].has_south_wall = false;
+                if let Some(neighbor) = self.grid.get_mut(row + 1) {
+                    neighbor[col].has_north_wall = false;
+                }
+            }
+            CellEdge::East => {
+                self.grid[row][col].has_east_wall = false;
+                if let Some(neighbor) = self.grid[row].get_mut(col + 1) {
+                    neighbor.has_west_wall = false;
+                }
+            }
+        }
+    }
+}
+
+// Interior cell edge (each interior wall is stored on the cell north or west of it)
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum CellEdge {
+    South,
+    East,
+}
+
+// Destructible wall info (server-side)
+pub struct DestructibleWallInfo {
+    pub grid_x: i32,
+    pub grid_z: i32,
+    pub edge: CellEdge,
+    pub hit_points: i32, // Remaining projectile hits before the wall is destroyed
+}
+
+// Map of all destructible walls that are still standing (server-side source of truth)
+#[derive(Resource, Default)]
+pub struct DestructibleWallMap(pub HashMap<WallId, DestructibleWallInfo>);
+
 // Player information (server-side)
 pub struct PlayerInfo {
     pub entity: Entity,
     pub logged_in: bool,
+    pub spectator: bool, // Logged in to watch only - has no player entity
     pub channel: UnboundedSender<ServerToClient>,
     pub hits: i32,
     pub name: String,
-    pub speed_power_up_timer: f32, // Remaining time for speed power-up (0.0 = inactive)
-    pub multi_shot_power_up_timer: f32, // Remaining time for multi-shot power-up (0.0 = inactive)
-    pub phasing_power_up_timer: f32, // Remaining time for phasing power-up (0.0 = inactive)
-    pub sentry_hunt_power_up_timer: f32, // Remaining time for sentry hunter power-up (0.0 = inactive)
-    pub stun_timer: f32,           // Remaining time stunned (0.0 = not stunned)
-    pub last_shot_time: f32,       // Timestamp of last accepted shot (seconds)
+    pub effects: Vec<ActiveEffect>, // Active power-up effects, at most one per effect kind
+    pub stun_timer: f32,            // Remaining time stunned (0.0 = not stunned)
+    pub last_shot_time: f32,        // Timestamp of last accepted shot (seconds)
+    pub weapon_shot_times: [f32; WeaponId::ALL.len()], // Timestamp of last accepted shot per weapon (seconds)
+    pub jump_requested: bool,       // Jump received since the last movement tick
+    pub revealed_to: HashSet<PlayerId>, // Players and spectators that can see this player while cloaked
+    pub stats: PlayerStats,         // Statistics for the current match
+    pub resume_token: String,       // Resumes this player after the connection drops (empty until logged in)
+}
+
+// Power-up effect active on a player
+pub struct ActiveEffect {
+    pub power_up: PowerUpId, // Power-up that caused the effect
+    pub remaining: f32,      // seconds
+    pub charges: u8,         // Hits left to absorb (absorbing effects only)
+}
+
+impl ActiveEffect {
+    #[must_use]
+    pub fn kind(&self) -> EffectKind {
+        self.power_up.def().effect
+    }
 }
 
 impl PlayerInfo {
-    // Build status message from current power-up timers.
+    // Check if this is a logged-in player taking part in the game (not a spectator).
     #[must_use]
-    pub fn status(&self, id: PlayerId) -> SPlayerStatus {
+    pub const fn is_playing(&self) -> bool {
+        self.logged_in && !self.spectator
+    }
+
+    // Build status message from current effects and stun timer (`tick` is the current server tick).
+    #[must_use]
+    pub fn status(&self, id: PlayerId, tick: u32) -> SPlayerStatus {
         SPlayerStatus {
             id,
-            speed_power_up: ALWAYS_SPEED || self.speed_power_up_timer > 0.0,
-            multi_shot_power_up: ALWAYS_MULTI_SHOT || self.multi_shot_power_up_timer > 0.0,
-            phasing_power_up: ALWAYS_PHASING || self.phasing_power_up_timer > 0.0,
-            sentry_hunt_power_up: ALWAYS_SENTRY_HUNT || self.sentry_hunt_power_up_timer > 0.0,
+            effects: self.active_effects(),
+            effect_timers: self.effect_timers(tick),
+            shield_charges: self.shield_charges(),
             stunned: self.stun_timer > 0.0,
         }
     }
 
-    // Tick all power-up and status timers by delta, clamping to 0.
+    // Build scoreboard row from score and match statistics.
+    #[must_use]
+    pub fn scoreboard_entry(&self, id: PlayerId) -> ScoreboardEntry {
+        ScoreboardEntry {
+            id,
+            name: self.name.clone(),
+            score: self.hits,
+            stats: self.stats,
+        }
+    }
+
+    // Check if an effect is active (or always active by debug flag).
+    #[must_use]
+    pub fn has_effect(&self, effect: EffectKind) -> bool {
+        effect.is_always_active() || self.effects.iter().any(|active| active.kind() == effect)
+    }
+
+    // All active effects, as sent to the clients.
+    #[must_use]
+    pub fn active_effects(&self) -> ActiveEffects {
+        EffectKind::ALL
+            .into_iter()
+            .filter(|effect| self.has_effect(*effect))
+            .collect()
+    }
+
+    // Check if another player may see this player. Cloaked players are hidden unless revealed to the viewer
+    // (see `players_cloak_system`).
+    #[must_use]
+    pub fn is_visible_to(&self, viewer: PlayerId) -> bool {
+        !self.has_effect(EffectKind::Cloak) || self.revealed_to.contains(&viewer)
+    }
+
+    // Start the effect of a picked up power-up, following its stacking rule if the effect is already active.
+    pub fn grant_power_up(&mut self, power_up: PowerUpId) {
+        let def = power_up.def();
+        if let Some(active) = self.effects.iter_mut().find(|active| active.kind() == def.effect) {
+            active.power_up = power_up;
+            (active.remaining, active.charges) = match def.stacking {
+                StackingRule::Refresh => (def.duration, def.charges),
+                StackingRule::Extend => (active.remaining + def.duration, active.charges.max(def.charges)),
+                StackingRule::Stack { max } => (
+                    def.duration,
+                    active
+                        .charges
+                        .saturating_add(def.charges)
+                        .min(def.charges.saturating_mul(max)),
+                ),
+            };
+        } else {
+            self.effects.push(ActiveEffect {
+                power_up,
+                remaining: def.duration,
+                charges: def.charges,
+            });
+        }
+    }
+
+    // Server tick at which each active effect expires, as sent to the clients (`tick` is the current server
+    // tick). Effects that are only always active (debug flags) have no timer.
+    #[must_use]
+    pub fn effect_timers(&self, tick: u32) -> Vec<(EffectKind, u32)> {
+        self.effects
+            .iter()
+            .map(|active| (active.kind(), tick + seconds_to_ticks(active.remaining)))
+            .collect()
+    }
+
+    // Hits the shield can still absorb (0 without a shield).
+    #[must_use]
+    pub fn shield_charges(&self) -> u8 {
+        self.effects
+            .iter()
+            .find(|active| active.kind() == EffectKind::Shield)
+            .map_or(0, |shield| shield.charges)
+    }
+
+    // Let the shield absorb a projectile hit, using up one charge. The shield ends with its last charge.
+    // Returns whether the hit was absorbed.
+    pub fn absorb_hit(&mut self) -> bool {
+        if EffectKind::Shield.is_always_active() {
+            return true;
+        }
+        let Some(index) = self
+            .effects
+            .iter()
+            .position(|active| active.kind() == EffectKind::Shield)
+        else {
+            return false;
+        };
+        let shield = &mut self.effects[index];
+        shield.charges = shield.charges.saturating_sub(1);
+        if shield.charges == 0 {
+            self.effects.remove(index);
+        }
+        true
+    }
+
+    // Let the shield absorb a sentry stun, which uses it up regardless of charges left.
+    // Returns whether the stun was absorbed.
+    pub fn absorb_stun(&mut self) -> bool {
+        if !self.has_effect(EffectKind::Shield) {
+            return false;
+        }
+        self.remove_effect(EffectKind::Shield);
+        true
+    }
+
+    // End an effect early, e.g. when it is used up.
+    pub fn remove_effect(&mut self, effect: EffectKind) {
+        self.effects.retain(|active| active.kind() != effect);
+    }
+
+    // Tick all effect and status timers by delta, dropping expired effects and clamping the stun timer to 0.
     pub fn tick_timers(&mut self, delta: f32) {
-        self.speed_power_up_timer = (self.speed_power_up_timer - delta).max(0.0);
-        self.multi_shot_power_up_timer = (self.multi_shot_power_up_timer - delta).max(0.0);
-        self.phasing_power_up_timer = (self.phasing_power_up_timer - delta).max(0.0);
-        self.sentry_hunt_power_up_timer = (self.sentry_hunt_power_up_timer - delta).max(0.0);
+        for active in &mut self.effects {
+            active.remaining -= delta;
+        }
+        self.effects.retain(|active| active.remaining > 0.0);
         self.stun_timer = (self.stun_timer - delta).max(0.0);
     }
 }
@@ -117,6 +307,18 @@ pub struct SentryInfo {
     pub mode_timer: f32,                 // Time remaining in current mode
     pub follow_target: Option<PlayerId>, // Player being targeted (only in Target mode)
     pub at_intersection: bool,           // Track if currently at an intersection (for patrol mode)
+    pub path: Option<SentryPath>,        // Path through the grid (only in Target mode)
+    pub archetype: SentryArchetype,
+    pub fire_timer: f32,         // Time until the next shot (only for archetypes that shoot)
+    pub follow_speed_scale: f32, // Multiplier of the archetype's follow speed (tuned by the director)
+    pub cooldown_duration: f32,  // Patrol cooldown after giving up a target (tuned by the director)
+}
+
+// Grid path a sentry follows in target mode, planned with A*
+pub struct SentryPath {
+    pub waypoints: VecDeque<(i32, i32, u8)>, // Cells (grid_x, grid_z, level) still to walk through, in order
+    pub target_cell: (i32, i32, u8),         // Cell of the (predicted) target position when the path was planned
+    pub fleeing: bool,                       // Path leads away from the target player
 }
 
 // Map of all sentries (server-side source of truth)
@@ -125,6 +327,7 @@ pub struct SentryMap(pub HashMap<SentryId, SentryInfo>);
 
 // Grid of cells showing which sentry occupies each cell (for collision avoidance)
 // grid[z][x] = Some(SentryId) or None
+// A cell is occupied on all levels at once, so sentries never meet on a ramp between them.
 #[derive(Resource, Clone)]
 pub struct SentryGrid(pub Vec<Vec<Option<SentryId>>>);
 
@@ -157,6 +360,15 @@ impl SentryGrid {
         // If velocity is zero, sentry only occupies current cell (already cleared above)
     }
 
+    // Clear every cell occupied by the sentry, e.g. when it is removed from the game
+    pub fn remove_sentry(&mut self, sentry_id: SentryId) {
+        for cell in self.0.iter_mut().flatten() {
+            if *cell == Some(sentry_id) {
+                *cell = None;
+            }
+        }
+    }
+
     // Helper: clear a cell if it contains the specified sentry and is in bounds
     fn clear_cell_if_matches(&mut self, grid_x: i32, grid_z: i32, sentry_id: SentryId) {
         if (0..GRID_COLS).contains(&grid_x)
@@ -168,6 +380,109 @@ impl SentryGrid {
     }
 }
 
+// State of the sentry director, which adapts the number and strength of sentries to the players
+#[derive(Resource)]
+pub struct SentryDirector {
+    pub timer: f32,                  // Time until the next adjustment
+    pub recent_stuns: VecDeque<f32>, // Timestamps of players stunned by sentries
+    pub difficulty: f32,             // 1.0 = default sentry follow speed and cooldown
+}
+
+impl Default for SentryDirector {
+    fn default() -> Self {
+        Self {
+            timer: SENTRY_DIRECTOR_INTERVAL,
+            recent_stuns: VecDeque::new(),
+            difficulty: 1.0,
+        }
+    }
+}
+
+impl SentryDirector {
+    pub fn record_stun(&mut self, now: f32) {
+        self.recent_stuns.push_back(now);
+    }
+}
+
+// Last known position of a player, reported by a sentry that saw it
+#[derive(Debug, Clone, Copy)]
+pub struct PlayerSighting {
+    pub pos: Position,
+    pub time: f32, // Timestamp of the sighting (seconds)
+    pub reported_by: SentryId,
+    pub range: f32, // meters around the position within which patrolling sentries respond
+}
+
+// Knowledge shared by all sentries, so they can coordinate instead of deciding independently
+#[derive(Resource, Default)]
+pub struct SentryBlackboard {
+    pub sightings: HashMap<PlayerId, PlayerSighting>,
+    pub pursuers: HashMap<PlayerId, Vec<SentryId>>, // Sentries targeting each player, ordered by ID
+    pub reservations: HashMap<(i32, i32), SentryId>, // Cells on the planned routes of targeting sentries
+}
+
+impl SentryBlackboard {
+    // Rebuild pursuers and route reservations from the sentries and forget outdated sightings.
+    pub fn refresh(&mut self, sentries: &SentryMap, now: f32) {
+        self.sightings
+            .retain(|_, sighting| now - sighting.time < SENTRY_SIGHTING_DURATION);
+        self.pursuers.clear();
+        self.reservations.clear();
+
+        for (sentry_id, sentry_info) in &sentries.0 {
+            if sentry_info.mode != SentryMode::Target {
+                continue;
+            }
+            if let Some(target_id) = sentry_info.follow_target {
+                self.add_pursuer(target_id, *sentry_id);
+            }
+            if let Some(path) = &sentry_info.path {
+                self.reserve_route(*sentry_id, &path.waypoints);
+            }
+        }
+    }
+
+    pub fn report_sighting(&mut self, player_id: PlayerId, sighting: PlayerSighting) {
+        self.sightings.insert(player_id, sighting);
+    }
+
+    pub fn add_pursuer(&mut self, player_id: PlayerId, sentry_id: SentryId) {
+        let pursuers = self.pursuers.entry(player_id).or_default();
+        if !pursuers.contains(&sentry_id) {
+            pursuers.push(sentry_id);
+            pursuers.sort_by_key(|id| id.0);
+        }
+    }
+
+    #[must_use]
+    pub fn pursuer_count(&self, player_id: PlayerId) -> usize {
+        self.pursuers.get(&player_id).map_or(0, Vec::len)
+    }
+
+    // The lead pursuer chases the player directly, the others try to cut it off
+    #[must_use]
+    pub fn is_lead_pursuer(&self, player_id: PlayerId, sentry_id: SentryId) -> bool {
+        self.pursuers
+            .get(&player_id)
+            .and_then(|pursuers| pursuers.first())
+            .is_none_or(|lead| *lead == sentry_id)
+    }
+
+    // Reserve the next cells of a sentry's route, replacing its previous reservation. Like the sentry
+    // grid, a reservation covers all levels of a cell.
+    pub fn reserve_route<'a>(&mut self, sentry_id: SentryId, waypoints: impl IntoIterator<Item = &'a (i32, i32, u8)>) {
+        self.reservations.retain(|_, owner| *owner != sentry_id);
+        for &(grid_x, grid_z, _) in waypoints.into_iter().take(SENTRY_ROUTE_RESERVATION_CELLS) {
+            self.reservations.entry((grid_x, grid_z)).or_insert(sentry_id);
+        }
+    }
+
+    #[must_use]
+    pub fn is_reserved_by_other(&self, cell: (i32, i32), sentry_id: SentryId) -> bool {
+        self.reservations.get(&cell).is_some_and(|owner| *owner != sentry_id)
+    }
+}
+
 // Resource wrapper for the channel from the accept connections task, which gives us the channel to
 // send from thee server to the client.
 #[derive(Resource)]
@@ -211,3 +526,169 @@ impl Default for ItemSpawner {
         Self { timer: 0.0, next_id: 0 }
     }
 }
+
+// Next projectile ID to assign
+#[derive(Resource, Default)]
+pub struct NextProjectileId(pub u32);
+
+impl NextProjectileId {
+    // Take the next ID (wrapping around, long after the projectile with the same ID is gone)
+    pub const fn allocate(&mut self) -> ProjectileId {
+        let id = ProjectileId(self.0);
+        self.0 = self.0.wrapping_add(1);
+        id
+    }
+}
+
+// Players whose connection dropped, by resume token. Kept for `RECONNECT_GRACE_PERIOD` with their score and
+// (paused) power-up timers, to be resumed by a new connection.
+#[derive(Resource, Default)]
+pub struct DisconnectedPlayers(pub HashMap<String, DisconnectedPlayer>);
+
+pub struct DisconnectedPlayer {
+    pub id: PlayerId,
+    pub info: PlayerInfo, // `entity` and `channel` are stale
+    pub pos: Position,
+    pub remaining: f32, // seconds left to resume
+}
+
+// Match clock
+#[derive(Resource)]
+pub struct MatchClock {
+    pub remaining: f32,        // Time until the current match ends
+    pub scoreboard_timer: f32, // Time since the last scoreboard broadcast
+}
+
+impl Default for MatchClock {
+    fn default() -> Self {
+        Self {
+            remaining: MATCH_DURATION,
+            scoreboard_timer: 0.0,
+        }
+    }
+}
+
+// All-time leaderboard by player name, persisted by `store_task`
+#[derive(Resource)]
+pub struct Leaderboard {
+    pub entries: HashMap<String, LeaderboardEntry>,
+    pub to_store: UnboundedSender<Vec<LeaderboardEntry>>,
+}
+
+impl Leaderboard {
+    // Add the final scoreboard of a match and send the updated leaderboard to the store
+    pub fn record_match(&mut self, results: &[ScoreboardEntry]) {
+        for result in results {
+            self.entries
+                .entry(result.name.clone())
+                .or_insert_with(|| LeaderboardEntry::new(result.name.clone()))
+                .record_match(result.score, &result.stats);
+        }
+
+        if self.to_store.send(self.entries.values().cloned().collect()).is_err() {
+            error!("leaderboard store task is gone");
+        }
+    }
+
+    // Highest total scores first
+    #[must_use]
+    pub fn top(&self) -> Vec<LeaderboardEntry> {
+        let mut entries: Vec<LeaderboardEntry> = self.entries.values().cloned().collect();
+        entries.sort_by(|a, b| b.total_score.cmp(&a.total_score).then_with(|| a.name.cmp(&b.name)));
+        entries.truncate(LEADERBOARD_SIZE);
+        entries
+    }
+}
+
+// Registered accounts by name, persisted by `store_task`
+#[derive(Resource)]
+pub struct Accounts {
+    pub entries: HashMap<String, Account>,
+    pub to_store: UnboundedSender<Vec<Account>>,
+    pub pending: HashSet<PlayerId>, // Players whose password is being hashed or checked
+    runtime: Handle,                // Runs the (deliberately slow) password hashing off the ECS tick
+    to_server: UnboundedSender<PasswordCheck>,
+    from_checks: UnboundedReceiver<PasswordCheck>,
+}
+
+// Result of hashing or checking a password for a login
+pub struct PasswordCheck {
+    pub id: PlayerId,
+    pub name: String,
+    pub outcome: PasswordOutcome,
+}
+
+pub enum PasswordOutcome {
+    Registered(String), // Hash of the new account's password
+    Verified,
+    Wrong,
+    Failed, // Hashing failed
+}
+
+impl Accounts {
+    #[must_use]
+    pub fn new(accounts: Vec<Account>, to_store: UnboundedSender<Vec<Account>>, runtime: Handle) -> Self {
+        let (to_server, from_checks) = unbounded_channel();
+        Self {
+            entries: accounts
+                .into_iter()
+                .map(|account| (account.name.clone(), account))
+                .collect(),
+            to_store,
+            pending: HashSet::new(),
+            runtime,
+            to_server,
+            from_checks,
+        }
+    }
+
+    // Hash the password of a new account (`password_hash` is `None`) or check it against the stored hash. The
+    // outcome arrives through `try_recv_password_check`.
+    pub fn check_password(&mut self, id: PlayerId, name: String, password: String, password_hash: Option<String>) {
+        self.pending.insert(id);
+        let to_server = self.to_server.clone();
+        self.runtime.spawn_blocking(move || {
+            let outcome = match password_hash {
+                None => hash_password(&password).map_or_else(
+                    |e| {
+                        error!("failed to hash password for {name}: {e}");
+                        PasswordOutcome::Failed
+                    },
+                    PasswordOutcome::Registered,
+                ),
+                Some(password_hash) if verify_password(&password, &password_hash) => PasswordOutcome::Verified,
+                Some(_) => PasswordOutcome::Wrong,
+            };
+            let _ = to_server.send(PasswordCheck { id, name, outcome });
+        });
+    }
+
+    pub fn try_recv_password_check(&mut self) -> Result<PasswordCheck, TryRecvError> {
+        let check = self.from_checks.try_recv()?;
+        self.pending.remove(&check.id);
+        Ok(check)
+    }
+
+    // Send all accounts to the store
+    pub fn store(&self) {
+        if self.to_store.send(self.entries.values().cloned().collect()).is_err() {
+            error!("accounts store task is gone");
+        }
+    }
+}
+
+// Light event scheduler
+#[derive(Resource)]
+pub struct LightEventScheduler {
+    pub next_blackout_timer: f32, // Time until the next blackout starts
+    pub blackout_timer: f32,      // Time remaining in the current blackout
+}
+
+impl Default for LightEventScheduler {
+    fn default() -> Self {
+        Self {
+            next_blackout_timer: LIGHT_BLACKOUT_MIN_INTERVAL,
+            blackout_timer: 0.0,
+        }
+    }
+}
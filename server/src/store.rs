@@ -0,0 +1,53 @@
+use anyhow::{Context, Result};
+use bevy::prelude::*;
+use bincode::{Decode, Encode};
+use std::{
+    io::ErrorKind,
+    path::{Path, PathBuf},
+};
+use tokio::sync::mpsc::UnboundedReceiver;
+
+// ============================================================================
+// Persistent Stores
+// ============================================================================
+
+// Load a store (a bincode-encoded value in the data directory). A missing store is the default value.
+pub fn load_store<T: Decode<()> + Default>(path: &Path) -> Result<T> {
+    let data = match std::fs::read(path) {
+        Ok(data) => data,
+        Err(e) if e.kind() == ErrorKind::NotFound => return Ok(T::default()),
+        Err(e) => return Err(e).with_context(|| format!("failed to read {}", path.display())),
+    };
+
+    let (value, _) = bincode::decode_from_slice(&data, bincode::config::standard())
+        .with_context(|| format!("failed to decode {}", path.display()))?;
+    Ok(value)
+}
+
+// Task to write snapshots sent by the server to a store, off the ECS tick
+pub async fn store_task<T: Encode + Send + Sync>(path: PathBuf, mut from_server: UnboundedReceiver<T>) {
+    while let Some(mut value) = from_server.recv().await {
+        // Only the latest snapshot matters if several piled up
+        while let Ok(newer) = from_server.try_recv() {
+            value = newer;
+        }
+
+        match write_store(&path, &value).await {
+            Ok(()) => debug!("stored {}", path.display()),
+            Err(e) => error!("failed to store {}: {e:#}", path.display()),
+        }
+    }
+}
+
+async fn write_store<T: Encode + Sync>(path: &Path, value: &T) -> Result<()> {
+    if let Some(dir) = path.parent() {
+        tokio::fs::create_dir_all(dir).await?;
+    }
+
+    // Write a temporary file first, so a crash mid-write never leaves a truncated store behind
+    let data = bincode::encode_to_vec(value, bincode::config::standard())?;
+    let tmp_path = path.with_extension("tmp");
+    tokio::fs::write(&tmp_path, data).await?;
+    tokio::fs::rename(&tmp_path, path).await?;
+    Ok(())
+}
@@ -1,5 +1,5 @@
 use bevy::prelude::*;
-use rand::Rng as _;
+use rand::seq::IndexedRandom;
 use std::collections::HashSet;
 
 use super::network::broadcast_to_all;
@@ -13,6 +13,7 @@ use common::{
     collision::items::overlap_player_vs_item,
     constants::{GRID_COLS, GRID_ROWS},
     markers::{ItemMarker, PlayerMarker},
+    power_ups::{PowerUpId, server_tick},
     protocol::{ItemId, ItemType, PlayerId, Position, SCookieCollected, ServerMessage},
 };
 
@@ -20,17 +21,13 @@ use common::{
 // Helper Functions
 // ============================================================================
 
+// Pick a random power-up, weighted by the power-up table
 fn choose_item_type(rng: &mut rand::rngs::ThreadRng) -> ItemType {
-    let rand_val = rng.random::<f64>();
-    if rand_val < 0.25 {
-        ItemType::SpeedPowerUp
-    } else if rand_val < 0.50 {
-        ItemType::MultiShotPowerUp
-    } else if rand_val < 0.75 {
-        ItemType::PhasingPowerUp
-    } else {
-        ItemType::SentryHunterPowerUp
-    }
+    let power_ups: Vec<PowerUpId> = PowerUpId::all().collect();
+    let power_up = power_ups
+        .choose_weighted(rng, |power_up| power_up.def().weight)
+        .expect("power-up table has positive weights");
+    ItemType::PowerUp(*power_up)
 }
 
 // ============================================================================
@@ -146,6 +143,7 @@ pub fn item_despawn_system(mut commands: Commands, time: Res<Time>, mut items: R
 // System to detect player-item collisions and grant items
 pub fn item_collection_system(
     mut commands: Commands,
+    time: Res<Time>,
     mut players: ResMut<PlayerMap>,
     mut items: ResMut<ItemMap>,
     player_positions: Query<&Position, With<PlayerMarker>>,
@@ -181,6 +179,7 @@ pub fn item_collection_system(
         .collect();
 
     // Process collections
+    let tick = server_tick(time.elapsed());
     let mut power_up_messages = Vec::new();
 
     for (player_id, item_id, item_type) in items_to_collect {
@@ -189,6 +188,7 @@ pub fn item_collection_system(
             if let Some(player_info) = players.0.get_mut(&player_id) {
                 // Give points for cookie
                 player_info.hits += COOKIE_POINTS;
+                player_info.stats.cookies_eaten += 1;
 
                 // Set spawn_time to respawn countdown
                 if let Some(item_info) = items.0.get_mut(&item_id) {
@@ -210,25 +210,15 @@ pub fn item_collection_system(
             commands.entity(item_info.entity).despawn();
         }
 
-        // Update player's power-up timer
+        // Start the power-up's effect
         if let Some(player_info) = players.0.get_mut(&player_id) {
-            match item_type {
-                ItemType::SpeedPowerUp => {
-                    player_info.speed_power_up_timer = POWER_UP_SPEED_DURATION;
-                }
-                ItemType::MultiShotPowerUp => {
-                    player_info.multi_shot_power_up_timer = POWER_UP_MULTI_SHOT_DURATION;
-                }
-                ItemType::PhasingPowerUp => {
-                    player_info.phasing_power_up_timer = POWER_UP_PHASING_DURATION;
-                }
-                ItemType::SentryHunterPowerUp => {
-                    player_info.sentry_hunt_power_up_timer = POWER_UP_SENTRY_HUNT_DURATION;
-                }
-                ItemType::Cookie => unreachable!(), // Already handled above
-            }
+            let ItemType::PowerUp(power_up) = item_type else {
+                unreachable!() // Cookies already handled above
+            };
+            player_info.grant_power_up(power_up);
+            player_info.stats.record_power_up(power_up);
 
-            power_up_messages.push(player_info.status(player_id));
+            power_up_messages.push(player_info.status(player_id, tick));
         }
     }
 
@@ -0,0 +1,92 @@
+use bevy::prelude::*;
+use rand::Rng as _;
+
+use super::network::broadcast_to_all;
+use crate::{
+    constants::*,
+    resources::{LightEventScheduler, PlayerMap, SentryMap, SentryMode},
+};
+use common::{
+    markers::SentryMarker,
+    protocol::{LightColor, LightMode, LightState, MapLayout, Position, SLightState, ServerMessage},
+};
+
+// ============================================================================
+// Lights Event System
+// ============================================================================
+
+// System to drive wall light states from server events and broadcast changes:
+// - Lights around a sentry targeting a player go dark
+// - All other lights flicker in emergency red during a blackout
+pub fn lights_event_system(
+    time: Res<Time>,
+    mut map_layout: ResMut<MapLayout>,
+    mut scheduler: ResMut<LightEventScheduler>,
+    players: Res<PlayerMap>,
+    sentries: Res<SentryMap>,
+    sentry_positions: Query<&Position, With<SentryMarker>>,
+) {
+    let delta = time.delta_secs();
+
+    // Schedule blackouts
+    if scheduler.blackout_timer > 0.0 {
+        scheduler.blackout_timer = (scheduler.blackout_timer - delta).max(0.0);
+    } else {
+        scheduler.next_blackout_timer -= delta;
+        if scheduler.next_blackout_timer <= 0.0 {
+            scheduler.blackout_timer = LIGHT_BLACKOUT_DURATION;
+            scheduler.next_blackout_timer =
+                rand::rng().random_range(LIGHT_BLACKOUT_MIN_INTERVAL..LIGHT_BLACKOUT_MAX_INTERVAL);
+            debug!("blackout started");
+        }
+    }
+    let blackout = scheduler.blackout_timer > 0.0;
+
+    // Positions of sentries currently targeting a player
+    let targeting_positions: Vec<Position> = sentries
+        .0
+        .values()
+        .filter(|info| info.mode == SentryMode::Target)
+        .filter_map(|info| sentry_positions.get(info.entity).ok())
+        .copied()
+        .collect();
+
+    // Collect lights whose state changed (read only, so the layout is not marked as changed every tick)
+    let changes: Vec<(u32, LightState)> = map_layout
+        .wall_lights
+        .iter()
+        .enumerate()
+        .filter_map(|(index, light)| {
+            let near_targeting_sentry = targeting_positions
+                .iter()
+                .any(|pos| (pos.x - light.pos.x).hypot(pos.z - light.pos.z) < LIGHT_SENTRY_DARK_RADIUS);
+
+            let mode = if near_targeting_sentry {
+                LightMode::Off
+            } else if blackout {
+                LightMode::Flicker
+            } else {
+                LightMode::On
+            };
+
+            let color = if blackout {
+                LightColor::EMERGENCY
+            } else {
+                LightColor::WARM
+            };
+
+            let state = LightState { mode, color };
+            (state != light.state).then_some((index as u32, state))
+        })
+        .collect();
+
+    if changes.is_empty() {
+        return;
+    }
+
+    for (index, state) in &changes {
+        map_layout.wall_lights[*index as usize].state = *state;
+    }
+
+    broadcast_to_all(&players, ServerMessage::LightState(SLightState { lights: changes }));
+}
@@ -0,0 +1,81 @@
+use bevy::prelude::*;
+
+use super::network::broadcast_to_all;
+use crate::{
+    constants::{MATCH_DURATION, SCOREBOARD_INTERVAL},
+    resources::{DisconnectedPlayers, Leaderboard, MatchClock, PlayerMap},
+};
+use common::{
+    protocol::{SMatchEnd, SScoreboard, ServerMessage},
+    stats::{PlayerStats, ScoreboardEntry},
+};
+
+// ============================================================================
+// Helper Functions
+// ============================================================================
+
+// Scoreboard rows of all playing players, including those held after dropping (spectators are left out), best
+// score first
+fn collect_scoreboard(players: &PlayerMap, disconnected: &DisconnectedPlayers) -> Vec<ScoreboardEntry> {
+    let mut entries: Vec<ScoreboardEntry> = players
+        .0
+        .iter()
+        .filter(|(_, info)| info.is_playing())
+        .map(|(id, info)| info.scoreboard_entry(*id))
+        .chain(
+            disconnected
+                .0
+                .values()
+                .map(|player| player.info.scoreboard_entry(player.id)),
+        )
+        .collect();
+    entries.sort_by(|a, b| b.score.cmp(&a.score).then(a.id.0.cmp(&b.id.0)));
+    entries
+}
+
+// ============================================================================
+// Match Clock System
+// ============================================================================
+
+// System to run the match clock: broadcast the scoreboard every `SCOREBOARD_INTERVAL` and, when the match is
+// over, send the final scoreboard, add it to the leaderboard and start the next match with scores and statistics
+// reset.
+pub fn match_clock_system(
+    time: Res<Time>,
+    mut clock: ResMut<MatchClock>,
+    mut players: ResMut<PlayerMap>,
+    mut disconnected: ResMut<DisconnectedPlayers>,
+    mut leaderboard: ResMut<Leaderboard>,
+) {
+    let delta = time.delta_secs();
+
+    clock.remaining -= delta;
+    if clock.remaining <= 0.0 {
+        let entries = collect_scoreboard(&players, &disconnected);
+        info!("match ended, {} players", entries.len());
+        leaderboard.record_match(&entries);
+        broadcast_to_all(&players, ServerMessage::MatchEnd(SMatchEnd { entries }));
+
+        let disconnected_players = disconnected.0.values_mut().map(|player| &mut player.info);
+        for player_info in players.0.values_mut().chain(disconnected_players) {
+            player_info.hits = 0;
+            player_info.stats = PlayerStats::default();
+        }
+
+        clock.remaining = MATCH_DURATION;
+        clock.scoreboard_timer = SCOREBOARD_INTERVAL; // Show the fresh scoreboard right away
+    }
+
+    clock.scoreboard_timer += delta;
+    if clock.scoreboard_timer >= SCOREBOARD_INTERVAL {
+        clock.scoreboard_timer = 0.0;
+        let entries = collect_scoreboard(&players, &disconnected);
+        broadcast_to_all(
+            &players,
+            ServerMessage::Scoreboard(SScoreboard {
+                remaining: clock.remaining,
+                entries,
+            }),
+        );
+    }
+}
@@ -1,4 +1,6 @@
 pub mod items;
+pub mod lights;
+pub mod matches;
 pub mod network;
 pub mod players;
 pub mod projectiles;
@@ -7,10 +9,12 @@ pub mod sentries;
 pub use items::{
     item_collection_system, item_despawn_system, item_initial_spawn_system, item_respawn_system, item_spawn_system,
 };
+pub use lights::lights_event_system;
+pub use matches::match_clock_system;
 pub use network::{
-    broadcast_to_all, broadcast_to_others, network_accept_connections_system, network_broadcast_state_system,
-    network_client_message_system,
+    broadcast_to_all, broadcast_to_observers, broadcast_to_others, network_accept_connections_system,
+    network_broadcast_state_system, network_client_message_system,
 };
-pub use players::{players_movement_system, players_timer_system};
+pub use players::{players_cloak_system, players_movement_system, players_timer_system};
 pub use projectiles::projectiles_movement_system;
 pub use sentries::{sentries_movement_system, sentries_spawn_system, sentry_player_collision_system};
@@ -5,7 +5,6 @@ use crate::{
     resources::{ItemMap, PlayerMap, SentryMap},
 };
 use common::{
-    constants::*,
     markers::{ItemMarker, PlayerMarker, SentryMarker},
     protocol::*,
 };
@@ -23,6 +22,19 @@ pub fn broadcast_to_others(players: &PlayerMap, skip: PlayerId, message: ServerM
     }
 }
 
+// Broadcast `message` about `subject` (e.g. its movement) to every other logged-in player that can see the
+// subject. Players a cloaked subject is hidden from never receive it.
+pub fn broadcast_to_observers(players: &PlayerMap, subject: PlayerId, message: ServerMessage) {
+    let Some(subject_info) = players.0.get(&subject) else {
+        return;
+    };
+    for (other_id, other_info) in &players.0 {
+        if *other_id != subject && other_info.logged_in && subject_info.is_visible_to(*other_id) {
+            let _ = other_info.channel.send(ServerToClient::Send(message.clone()));
+        }
+    }
+}
+
 // Broadcast `message` to every logged-in player.
 pub fn broadcast_to_all(players: &PlayerMap, message: ServerMessage) {
     for player_info in players.0.values() {
@@ -32,21 +44,34 @@ pub fn broadcast_to_all(players: &PlayerMap, message: ServerMessage) {
     }
 }
 
+// Broadcast the spectator list to every logged-in player and spectator.
+pub fn broadcast_spectators(players: &PlayerMap) {
+    broadcast_to_all(
+        players,
+        ServerMessage::Spectators(SSpectators {
+            spectators: collect_spectators(players),
+        }),
+    );
+}
+
 // ============================================================================
 // Data Collection Functions
 // ============================================================================
 
-// Collect all logged-in players for network updates.
+// Collect all logged-in players `viewer` can see for network updates (`tick` is the current server tick).
+// Cloaked players are left out unless revealed to the viewer, so their positions never reach the other clients.
 #[must_use]
 pub fn snapshot_logged_in_players(
     players: &PlayerMap,
     player_data: &Query<(&Position, &Speed, &FaceDirection), With<PlayerMarker>>,
+    viewer: PlayerId,
+    tick: u32,
 ) -> Vec<(PlayerId, Player)> {
     players
         .0
         .iter()
         .filter_map(|(player_id, info)| {
-            if !info.logged_in {
+            if !info.is_playing() || (*player_id != viewer && !info.is_visible_to(viewer)) {
                 return None;
             }
             let (pos, speed, face_dir) = player_data.get(info.entity).ok()?;
@@ -58,10 +83,9 @@ pub fn snapshot_logged_in_players(
                     speed: *speed,
                     face_dir: face_dir.0,
                     hits: info.hits,
-                    speed_power_up: ALWAYS_SPEED || info.speed_power_up_timer > 0.0,
-                    multi_shot_power_up: ALWAYS_MULTI_SHOT || info.multi_shot_power_up_timer > 0.0,
-                    phasing_power_up: ALWAYS_PHASING || info.phasing_power_up_timer > 0.0,
-                    sentry_hunt_power_up: ALWAYS_SENTRY_HUNT || info.sentry_hunt_power_up_timer > 0.0,
+                    effects: info.active_effects(),
+                    effect_timers: info.effect_timers(tick),
+                    shield_charges: info.shield_charges(),
                     stunned: info.stun_timer > 0.0,
                 },
             ))
@@ -69,6 +93,19 @@ pub fn snapshot_logged_in_players(
         .collect()
 }
 
+// Collect the names of all logged-in spectators, sorted by ID.
+#[must_use]
+pub fn collect_spectators(players: &PlayerMap) -> Vec<(PlayerId, String)> {
+    let mut spectators: Vec<(PlayerId, String)> = players
+        .0
+        .iter()
+        .filter(|(_, info)| info.logged_in && info.spectator)
+        .map(|(id, info)| (*id, info.name.clone()))
+        .collect();
+    spectators.sort_by_key(|(id, _)| id.0);
+    spectators
+}
+
 // Build the authoritative item list that gets replicated to clients.
 #[must_use]
 pub fn collect_items(items: &ItemMap, item_positions: &Query<&Position, With<ItemMarker>>) -> Vec<(ItemId, Item)> {
@@ -109,6 +146,7 @@ pub fn collect_sentries(
                 Sentry {
                     pos: *pos_component,
                     vel: *vel_component,
+                    archetype: info.archetype,
                 },
             )
         })
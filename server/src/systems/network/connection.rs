@@ -1,13 +1,22 @@
 use bevy::prelude::*;
+use std::collections::HashSet;
 
-use crate::resources::{FromAcceptChannel, PlayerInfo, PlayerMap};
-use common::markers::PlayerMarker;
+use super::broadcast::{broadcast_spectators, broadcast_to_others};
+use crate::resources::{DisconnectedPlayer, DisconnectedPlayers, FromAcceptChannel, PlayerInfo, PlayerMap};
+use common::{
+    constants::RECONNECT_GRACE_PERIOD,
+    markers::PlayerMarker,
+    protocol::{FaceDirection, PlayerId, Position, SLogoff, ServerMessage, Speed},
+    stats::PlayerStats,
+    weapons::WeaponId,
+};
 
 // ============================================================================
 // Accept Connections System
 // ============================================================================
 
-// Drain newly accepted connections into ECS entities and tracking state.
+// Drain newly accepted connections into ECS entities and tracking state. The entities only become players (with
+// `PlayerMarker`) when logging in as one, so spectators never take part in the game.
 pub fn network_accept_connections_system(
     mut commands: Commands,
     mut from_accept: ResMut<FromAcceptChannel>,
@@ -15,22 +24,85 @@ pub fn network_accept_connections_system(
 ) {
     while let Ok((id, to_client)) = from_accept.try_recv() {
         debug!("{:?} connected", id);
-        let entity = commands.spawn((PlayerMarker, id)).id();
+        let entity = commands.spawn(id).id();
         players.0.insert(
             id,
             PlayerInfo {
                 entity,
                 logged_in: false,
+                spectator: false,
                 channel: to_client,
                 hits: 0,
                 name: String::new(),
-                speed_power_up_timer: 0.0,
-                multi_shot_power_up_timer: 0.0,
-                phasing_power_up_timer: 0.0,
-                sentry_hunt_power_up_timer: 0.0,
+                effects: Vec::new(),
                 stun_timer: 0.0,
                 last_shot_time: f32::NEG_INFINITY,
+                weapon_shot_times: [f32::NEG_INFINITY; WeaponId::ALL.len()],
+                jump_requested: false,
+                revealed_to: HashSet::new(),
+                stats: PlayerStats::default(),
+                resume_token: String::new(),
             },
         );
     }
 }
+
+// ============================================================================
+// Disconnect Handling
+// ============================================================================
+
+// Remove a player whose connection dropped. Logged-in players are kept in `DisconnectedPlayers` for
+// `RECONNECT_GRACE_PERIOD`, to be resumed with their resume token. Spectators have nothing to resume.
+pub fn handle_disconnect(
+    commands: &mut Commands,
+    id: PlayerId,
+    players: &mut PlayerMap,
+    disconnected: &mut DisconnectedPlayers,
+    player_data: &Query<(&Position, &Speed, &FaceDirection), With<PlayerMarker>>,
+) {
+    let Some(mut info) = players.0.remove(&id) else {
+        return;
+    };
+    commands.entity(info.entity).despawn();
+
+    debug!("{:?} disconnected (logged_in: {})", id, info.logged_in);
+    if !info.logged_in {
+        return;
+    }
+    if info.spectator {
+        broadcast_spectators(players);
+        return;
+    }
+
+    // Broadcast logoff to all other logged-in players
+    broadcast_to_others(players, id, ServerMessage::Logoff(SLogoff { id, graceful: false }));
+
+    let pos = player_data
+        .get(info.entity)
+        .map_or_else(|_| Position::default(), |(pos, _, _)| *pos);
+    info.logged_in = false;
+    info.revealed_to.clear();
+    disconnected.0.insert(
+        info.resume_token.clone(),
+        DisconnectedPlayer {
+            id,
+            info,
+            pos,
+            remaining: RECONNECT_GRACE_PERIOD,
+        },
+    );
+}
+
+// System to drop disconnected players who did not resume within the grace period.
+pub fn network_disconnected_players_system(time: Res<Time>, mut disconnected: ResMut<DisconnectedPlayers>) {
+    let delta = time.delta_secs();
+    disconnected.0.retain(|_, player| {
+        player.remaining -= delta;
+        if player.remaining > 0.0 {
+            true
+        } else {
+            debug!("{:?} did not resume in time", player.id);
+            false
+        }
+    });
+}
@@ -1,9 +1,14 @@
 use bevy::prelude::*;
 use rand::prelude::*;
+use std::collections::HashMap;
 
 use crate::{
+    accounts::{Account, generate_token},
     net::ServerToClient,
-    resources::{GridConfig, ItemMap, PlayerMap, SentryMap},
+    resources::{
+        Accounts, DisconnectedPlayers, GridConfig, ItemMap, PasswordCheck, PasswordOutcome, PlayerInfo, PlayerMap,
+        SentryMap,
+    },
 };
 use common::{
     constants::{FIELD_DEPTH, FIELD_WIDTH, GRID_SIZE},
@@ -11,7 +16,13 @@ use common::{
     protocol::{MapLayout, *},
 };
 
-use super::broadcast::{broadcast_to_others, collect_items, collect_sentries, snapshot_logged_in_players};
+use super::{
+    broadcast::{
+        broadcast_spectators, broadcast_to_others, collect_items, collect_sentries, collect_spectators,
+        snapshot_logged_in_players,
+    },
+    connection::handle_disconnect,
+};
 
 // ============================================================================
 // Login Flow
@@ -24,6 +35,9 @@ pub fn handle_login_message(
     id: PlayerId,
     msg: ClientMessage,
     players: &mut ResMut<PlayerMap>,
+    accounts: &mut ResMut<Accounts>,
+    disconnected: &mut ResMut<DisconnectedPlayers>,
+    tick: u32,
     map_layout: &Res<MapLayout>,
     grid_config: &Res<GridConfig>,
     items: &Res<ItemMap>,
@@ -34,81 +48,105 @@ pub fn handle_login_message(
 ) {
     match msg {
         ClientMessage::Login(login) => {
-            debug!("{:?} logged in", id);
-
-            let (channel, hits, name) = {
-                let player_info = players
-                    .0
-                    .get_mut(&id)
-                    .expect("handle_login_message called for unknown player");
-                let channel = player_info.channel.clone();
-                player_info.logged_in = true;
-
-                // Determine player name: use provided name or default to the player id
-                player_info.name = if login.name.is_empty() {
-                    format!("Player {}", id.0)
+            if accounts.pending.contains(&id) {
+                warn!("{:?} sent login while its password is being checked", id);
+                return;
+            }
+
+            // Spectators aren't held after dropping, so they simply log in again. The client may notice the drop
+            // before the server does - close the stale connection first so its name is free again.
+            if login.spectate {
+                if let Some(token) = &login.resume_token
+                    && let Some((&old_id, old_info)) = players
+                        .0
+                        .iter()
+                        .find(|(_, info)| info.logged_in && info.spectator && info.resume_token == *token)
+                {
+                    let _ = old_info.channel.send(ServerToClient::Close);
+                    handle_disconnect(commands, old_id, players, disconnected, player_data);
+                }
+
+                let name = if login.name.is_empty() {
+                    format!("Spectator {}", id.0)
                 } else {
                     login.name
                 };
-
-                (channel, player_info.hits, player_info.name.clone())
-            };
-
-            // Send Init to the connecting player (their ID and grid config)
-            let init_msg = ServerMessage::Init(SInit {
-                id,
-                map_layout: (*map_layout).clone(),
-            });
-            if let Err(e) = channel.send(ServerToClient::Send(init_msg)) {
-                warn!("failed to send init to {:?}: {}", id, e);
+                if let Err(reason) = check_guest_name(&name, players, &accounts.entries, disconnected) {
+                    reject_login(players, id, reason);
+                    return;
+                }
+                complete_spectator_login(
+                    id,
+                    name,
+                    players,
+                    tick,
+                    map_layout,
+                    items,
+                    sentries,
+                    player_data,
+                    item_positions,
+                    sentry_data,
+                );
                 return;
             }
 
-            // Generate random initial position for the new player
-            let pos = generate_player_spawn_position(grid_config, players, sentries, player_data, sentry_data);
-
-            // Calculate initial facing direction toward center
-            let face_dir = (-pos.x).atan2(-pos.z);
+            if let Some(token) = login.resume_token {
+                resume_player(
+                    commands,
+                    entity,
+                    id,
+                    &token,
+                    players,
+                    disconnected,
+                    tick,
+                    map_layout,
+                    grid_config,
+                    items,
+                    sentries,
+                    player_data,
+                    item_positions,
+                    sentry_data,
+                );
+                return;
+            }
 
-            // Initial speed for the new player
-            let speed = Speed {
-                speed_level: SpeedLevel::Idle,
-                // move_dir: 0.0,
-                move_dir: std::f32::consts::PI, // Same as face_dir - facing toward origin
+            // Determine player name: use provided name or default to the player id (guests only)
+            let name = if login.name.is_empty() && login.auth.is_none() {
+                format!("Player {}", id.0)
+            } else {
+                login.name
             };
 
-            // Construct player data
-            let player = Player::new(name, pos, speed, face_dir, hits);
-
-            // Construct the initial Update for the new player
-            let mut all_players = snapshot_logged_in_players(players, player_data)
-                .into_iter()
-                .filter(|(player_id, _)| *player_id != id)
-                .collect::<Vec<_>>();
-            // Add the new player manually with their freshly generated values
-            all_players.push((id, player.clone()));
-
-            // Collect all items for the initial update
-            let all_items = collect_items(items, item_positions);
-
-            // Collect all sentries for the initial update
-            let all_sentries = collect_sentries(sentries, sentry_data);
-
-            // Send the initial Update to the new player
-            let update_msg = ServerMessage::Update(SUpdate {
-                seq: 0,
-                players: all_players,
-                items: all_items,
-                sentries: all_sentries,
-            });
-            channel.send(ServerToClient::Send(update_msg)).ok();
-
-            // Now update entity: add Position + Speed + FaceDirection
-            commands.entity(entity).insert((pos, speed, FaceDirection(face_dir)));
+            let session_token = match authenticate(id, name.clone(), login.auth, players, accounts, disconnected) {
+                Ok(Authentication::Guest) => None,
+                Ok(Authentication::Session(token)) => Some(token),
+                Ok(Authentication::Pending) => {
+                    debug!("{:?} checking password for {name}", id);
+                    return;
+                }
+                Err(reason) => {
+                    reject_login(players, id, reason);
+                    return;
+                }
+            };
 
-            // Broadcast Login to all other logged-in players
-            let login_msg = SLogin { id, player };
-            broadcast_to_others(players, id, ServerMessage::Login(login_msg));
+            complete_login(
+                commands,
+                entity,
+                id,
+                name,
+                session_token,
+                None,
+                players,
+                tick,
+                map_layout,
+                grid_config,
+                items,
+                sentries,
+                player_data,
+                item_positions,
+                sentry_data,
+            );
         }
         _ => {
             warn!(
@@ -120,6 +158,418 @@ pub fn handle_login_message(
     }
 }
 
+// Handle a finished password check - log the player in to the (new) account or reject the login.
+pub fn handle_password_check(
+    commands: &mut Commands,
+    check: PasswordCheck,
+    players: &mut ResMut<PlayerMap>,
+    accounts: &mut ResMut<Accounts>,
+    disconnected: &DisconnectedPlayers,
+    tick: u32,
+    map_layout: &Res<MapLayout>,
+    grid_config: &Res<GridConfig>,
+    items: &Res<ItemMap>,
+    sentries: &Res<SentryMap>,
+    player_data: &Query<(&Position, &Speed, &FaceDirection), With<PlayerMarker>>,
+    item_positions: &Query<&Position, With<ItemMarker>>,
+    sentry_data: &Query<(&Position, &Velocity), With<SentryMarker>>,
+) {
+    let PasswordCheck { id, name, outcome } = check;
+    let Some(entity) = players.0.get(&id).map(|info| info.entity) else {
+        debug!("{:?} disconnected while its password was being checked", id);
+        return;
+    };
+
+    // The name may have been taken while the password was being checked
+    let token = if name_in_use(players, disconnected, &name) {
+        Err(format!("{name} is already playing"))
+    } else {
+        match outcome {
+            PasswordOutcome::Registered(password_hash) => {
+                if accounts.entries.contains_key(&name) {
+                    Err(format!("{name} is already registered"))
+                } else {
+                    info!("{:?} registered account {name}", id);
+                    let mut account = Account {
+                        name: name.clone(),
+                        password_hash,
+                        session: None,
+                    };
+                    let token = account.start_session();
+                    accounts.entries.insert(name.clone(), account);
+                    Ok(token)
+                }
+            }
+            PasswordOutcome::Verified => accounts
+                .entries
+                .get_mut(&name)
+                .map(Account::start_session)
+                .ok_or_else(|| format!("{name} is not registered")),
+            PasswordOutcome::Wrong => Err(WRONG_CREDENTIALS.to_string()),
+            PasswordOutcome::Failed => Err("failed to check password".to_string()),
+        }
+    };
+
+    match token {
+        Ok(token) => {
+            accounts.store();
+            complete_login(
+                commands,
+                entity,
+                id,
+                name,
+                Some(token),
+                None,
+                players,
+                tick,
+                map_layout,
+                grid_config,
+                items,
+                sentries,
+                player_data,
+                item_positions,
+                sentry_data,
+            );
+        }
+        Err(reason) => reject_login(players, id, reason),
+    }
+}
+
+// Log in a player whose name (and account, if any) checked out: send Init and the initial Update, spawn them
+// (at `pos` if resumed, else at a random spawn position) and announce them to everybody else.
+fn complete_login(
+    commands: &mut Commands,
+    entity: Entity,
+    id: PlayerId,
+    name: String,
+    session_token: Option<String>,
+    pos: Option<Position>,
+    players: &mut ResMut<PlayerMap>,
+    tick: u32,
+    map_layout: &Res<MapLayout>,
+    grid_config: &Res<GridConfig>,
+    items: &Res<ItemMap>,
+    sentries: &Res<SentryMap>,
+    player_data: &Query<(&Position, &Speed, &FaceDirection), With<PlayerMarker>>,
+    item_positions: &Query<&Position, With<ItemMarker>>,
+    sentry_data: &Query<(&Position, &Velocity), With<SentryMarker>>,
+) {
+    debug!("{:?} logged in as {name}", id);
+
+    let (channel, resume_token) = {
+        let player_info = players
+            .0
+            .get_mut(&id)
+            .expect("complete_login called for unknown player");
+        player_info.logged_in = true;
+        player_info.name.clone_from(&name);
+        player_info.resume_token = generate_token();
+        (player_info.channel.clone(), player_info.resume_token.clone())
+    };
+
+    // Send Init to the connecting player (their ID and grid config)
+    let init_msg = ServerMessage::Init(SInit {
+        id,
+        map_layout: Box::new((*map_layout).clone()),
+        session_token,
+        resume_token,
+        tick,
+    });
+    if let Err(e) = channel.send(ServerToClient::Send(init_msg)) {
+        warn!("failed to send init to {:?}: {}", id, e);
+        return;
+    }
+
+    // Generate random initial position for the new player
+    let pos =
+        pos.unwrap_or_else(|| generate_player_spawn_position(grid_config, players, sentries, player_data, sentry_data));
+
+    // Calculate initial facing direction toward center
+    let face_dir = (-pos.x).atan2(-pos.z);
+
+    // Initial speed for the new player
+    let speed = Speed {
+        speed_level: SpeedLevel::Idle,
+        // move_dir: 0.0,
+        move_dir: std::f32::consts::PI, // Same as face_dir - facing toward origin
+    };
+
+    // Construct player data (a resumed player keeps their score and power-ups)
+    let player = {
+        let player_info = &players.0[&id];
+        Player {
+            effects: player_info.active_effects(),
+            effect_timers: player_info.effect_timers(tick),
+            shield_charges: player_info.shield_charges(),
+            stunned: player_info.stun_timer > 0.0,
+            ..Player::new(name, pos, speed, face_dir, player_info.hits)
+        }
+    };
+
+    // Construct the initial Update for the new player (without the cloaked players hidden from them)
+    let mut all_players = snapshot_logged_in_players(players, player_data, id, tick)
+        .into_iter()
+        .filter(|(player_id, _)| *player_id != id)
+        .collect::<Vec<_>>();
+    // Add the new player manually with their freshly generated values
+    all_players.push((id, player.clone()));
+
+    // Collect all items for the initial update
+    let all_items = collect_items(items, item_positions);
+
+    // Collect all sentries for the initial update
+    let all_sentries = collect_sentries(sentries, sentry_data);
+
+    // Send the initial Update to the new player
+    let update_msg = ServerMessage::Update(SUpdate {
+        seq: 0,
+        tick,
+        players: all_players,
+        items: all_items,
+        sentries: all_sentries,
+    });
+    channel.send(ServerToClient::Send(update_msg)).ok();
+
+    // Send the spectators watching
+    let spectators_msg = ServerMessage::Spectators(SSpectators {
+        spectators: collect_spectators(players),
+    });
+    channel.send(ServerToClient::Send(spectators_msg)).ok();
+
+    // Now turn the entity into a player: add PlayerMarker + Position + Speed + VerticalVelocity + FaceDirection
+    commands.entity(entity).insert((
+        PlayerMarker,
+        pos,
+        speed,
+        VerticalVelocity::default(),
+        FaceDirection(face_dir),
+    ));
+
+    // Broadcast Login to all other logged-in players
+    let login_msg = SLogin { id, player };
+    broadcast_to_others(players, id, ServerMessage::Login(login_msg));
+}
+
+// Log in a spectator: send Init and the initial Update like to a player, but don't spawn a player. Everybody gets
+// the new spectator list.
+fn complete_spectator_login(
+    id: PlayerId,
+    name: String,
+    players: &mut ResMut<PlayerMap>,
+    tick: u32,
+    map_layout: &Res<MapLayout>,
+    items: &Res<ItemMap>,
+    sentries: &Res<SentryMap>,
+    player_data: &Query<(&Position, &Speed, &FaceDirection), With<PlayerMarker>>,
+    item_positions: &Query<&Position, With<ItemMarker>>,
+    sentry_data: &Query<(&Position, &Velocity), With<SentryMarker>>,
+) {
+    debug!("{:?} spectating as {name}", id);
+
+    let (channel, resume_token) = {
+        let player_info = players
+            .0
+            .get_mut(&id)
+            .expect("complete_spectator_login called for unknown player");
+        player_info.logged_in = true;
+        player_info.spectator = true;
+        player_info.name = name;
+        player_info.resume_token = generate_token();
+        (player_info.channel.clone(), player_info.resume_token.clone())
+    };
+
+    let init_msg = ServerMessage::Init(SInit {
+        id,
+        map_layout: Box::new((*map_layout).clone()),
+        session_token: None,
+        resume_token,
+        tick,
+    });
+    if let Err(e) = channel.send(ServerToClient::Send(init_msg)) {
+        warn!("failed to send init to {:?}: {}", id, e);
+        return;
+    }
+
+    let update_msg = ServerMessage::Update(SUpdate {
+        seq: 0,
+        tick,
+        players: snapshot_logged_in_players(players, player_data, id, tick),
+        items: collect_items(items, item_positions),
+        sentries: collect_sentries(sentries, sentry_data),
+    });
+    channel.send(ServerToClient::Send(update_msg)).ok();
+
+    broadcast_spectators(players);
+}
+
+// Resume the session of a player whose connection dropped: the new connection takes over their player ID and
+// state.
+fn resume_player(
+    commands: &mut Commands,
+    entity: Entity,
+    id: PlayerId,
+    token: &str,
+    players: &mut ResMut<PlayerMap>,
+    disconnected: &mut ResMut<DisconnectedPlayers>,
+    tick: u32,
+    map_layout: &Res<MapLayout>,
+    grid_config: &Res<GridConfig>,
+    items: &Res<ItemMap>,
+    sentries: &Res<SentryMap>,
+    player_data: &Query<(&Position, &Speed, &FaceDirection), With<PlayerMarker>>,
+    item_positions: &Query<&Position, With<ItemMarker>>,
+    sentry_data: &Query<(&Position, &Velocity), With<SentryMarker>>,
+) {
+    // The client may notice the drop before the server does - close the old connection first
+    if let Some((&old_id, old_info)) = players
+        .0
+        .iter()
+        .find(|(_, info)| info.logged_in && info.resume_token == token)
+    {
+        let _ = old_info.channel.send(ServerToClient::Close);
+        handle_disconnect(commands, old_id, players, disconnected, player_data);
+    }
+
+    let Some(held) = disconnected.0.remove(token) else {
+        reject_login(players, id, "session can't be resumed anymore".to_string());
+        return;
+    };
+    let Some(connection) = players.0.remove(&id) else {
+        return;
+    };
+    info!("{:?} resumed by {:?}", held.id, id);
+
+    // Tag the connection's messages with the resumed ID from now on
+    let _ = connection.channel.send(ServerToClient::Rebind(held.id));
+    commands.entity(entity).insert(held.id);
+
+    let name = held.info.name.clone();
+    players.0.insert(
+        held.id,
+        PlayerInfo {
+            entity,
+            channel: connection.channel,
+            ..held.info
+        },
+    );
+
+    complete_login(
+        commands,
+        entity,
+        held.id,
+        name,
+        None,
+        Some(held.pos),
+        players,
+        tick,
+        map_layout,
+        grid_config,
+        items,
+        sentries,
+        player_data,
+        item_positions,
+        sentry_data,
+    );
+}
+
+// ============================================================================
+// Authentication
+// ============================================================================
+
+// Reply to failed password logins, without telling whether the account exists
+const WRONG_CREDENTIALS: &str = "unknown account or wrong password";
+
+enum Authentication {
+    Guest,
+    Session(String), // Token of the account's session
+    Pending,         // Password is being hashed or checked, see `handle_password_check`
+}
+
+// Check the login name and authentication. Names are unique among logged-in players, and guests can't use the
+// name of an account.
+fn authenticate(
+    id: PlayerId,
+    name: String,
+    auth: Option<LoginAuth>,
+    players: &PlayerMap,
+    accounts: &mut Accounts,
+    disconnected: &DisconnectedPlayers,
+) -> Result<Authentication, String> {
+    let Some(auth) = auth else {
+        check_guest_name(&name, players, &accounts.entries, disconnected)?;
+        return Ok(Authentication::Guest);
+    };
+
+    if name_in_use(players, disconnected, &name) {
+        return Err(format!("{name} is already playing"));
+    }
+
+    if name.is_empty() {
+        return Err("accounts need a name".to_string());
+    }
+
+    match auth {
+        LoginAuth::Register { password } => {
+            if accounts.entries.contains_key(&name) {
+                return Err(format!("{name} is already registered"));
+            }
+            accounts.check_password(id, name, password, None);
+            Ok(Authentication::Pending)
+        }
+        LoginAuth::Password { password } => {
+            let Some(password_hash) = accounts.entries.get(&name).map(|account| account.password_hash.clone()) else {
+                return Err(WRONG_CREDENTIALS.to_string());
+            };
+            accounts.check_password(id, name, password, Some(password_hash));
+            Ok(Authentication::Pending)
+        }
+        LoginAuth::Token { token } => {
+            if !accounts
+                .entries
+                .get_mut(&name)
+                .is_some_and(|account| account.resume_session(&token))
+            {
+                return Err("session expired - log in with your password".to_string());
+            }
+            accounts.store(); // Store the extended session
+            Ok(Authentication::Session(token))
+        }
+    }
+}
+
+// Check the name of a guest or spectator (neither authenticates): it must be free and not belong to an account.
+fn check_guest_name(
+    name: &str,
+    players: &PlayerMap,
+    accounts: &HashMap<String, Account>,
+    disconnected: &DisconnectedPlayers,
+) -> Result<(), String> {
+    if name_in_use(players, disconnected, name) {
+        Err(format!("{name} is already playing"))
+    } else if accounts.contains_key(name) {
+        Err(format!("{name} is a registered account - log in with its password"))
+    } else {
+        Ok(())
+    }
+}
+
+// Names of players who dropped stay taken for them to resume
+fn name_in_use(players: &PlayerMap, disconnected: &DisconnectedPlayers, name: &str) -> bool {
+    players.0.values().any(|info| info.logged_in && info.name == name)
+        || disconnected.0.values().any(|player| player.info.name == name)
+}
+
+fn reject_login(players: &PlayerMap, id: PlayerId, reason: String) {
+    info!("{:?} login rejected: {reason}", id);
+    if let Some(player_info) = players.0.get(&id) {
+        let _ = player_info
+            .channel
+            .send(ServerToClient::Send(ServerMessage::LoginRejected(SLoginRejected {
+                reason,
+            })));
+    }
+}
+
 // ============================================================================
 // Spawn Position Generation
 // ============================================================================
@@ -175,7 +625,7 @@ fn generate_player_spawn_position(
         let too_close_to_player = players
             .0
             .values()
-            .filter(|p| p.logged_in)
+            .filter(|p| p.is_playing())
             .filter_map(|p| player_data.get(p.entity).ok())
             .any(|(p_pos, _, _)| {
                 let dx = pos.x - p_pos.x;
@@ -207,3 +657,61 @@ fn generate_player_spawn_position(
     );
     Position::default()
 }
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+    use tokio::sync::mpsc::unbounded_channel;
+
+    use super::*;
+    use common::{stats::PlayerStats, weapons::WeaponId};
+
+    fn account(name: &str) -> (String, Account) {
+        (
+            name.to_string(),
+            Account {
+                name: name.to_string(),
+                password_hash: String::new(),
+                session: None,
+            },
+        )
+    }
+
+    // Spectators and guests log in without authenticating
+    #[test]
+    fn account_names_are_rejected_without_authentication() {
+        let accounts = HashMap::from([account("Alice")]);
+        let players = PlayerMap::default();
+        let disconnected = DisconnectedPlayers::default();
+
+        assert!(check_guest_name("Alice", &players, &accounts, &disconnected).is_err());
+        assert!(check_guest_name("Bob", &players, &accounts, &disconnected).is_ok());
+    }
+
+    #[test]
+    fn guest_names_in_use_are_rejected() {
+        let mut players = PlayerMap::default();
+        let (channel, _) = unbounded_channel();
+        players.0.insert(
+            PlayerId(1),
+            PlayerInfo {
+                entity: Entity::PLACEHOLDER,
+                logged_in: true,
+                spectator: true,
+                channel,
+                hits: 0,
+                name: "Bob".to_string(),
+                effects: Vec::new(),
+                stun_timer: 0.0,
+                last_shot_time: f32::NEG_INFINITY,
+                weapon_shot_times: [f32::NEG_INFINITY; WeaponId::ALL.len()],
+                jump_requested: false,
+                revealed_to: HashSet::new(),
+                stats: PlayerStats::default(),
+                resume_token: String::new(),
+            },
+        );
+
+        assert!(check_guest_name("Bob", &players, &HashMap::new(), &DisconnectedPlayers::default()).is_err());
+    }
+}
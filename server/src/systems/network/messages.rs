@@ -1,11 +1,15 @@
 use bevy::prelude::*;
 
-use super::broadcast::broadcast_to_others;
-use crate::{net::ServerToClient, resources::PlayerMap};
+use super::broadcast::{broadcast_spectators, broadcast_to_all, broadcast_to_observers, broadcast_to_others};
+use crate::{
+    net::ServerToClient,
+    resources::{Leaderboard, NextProjectileId, PlayerMap},
+    systems::projectiles::spawn_projectiles,
+};
 use common::{
-    collision::Projectile,
     constants::PROJECTILE_COOLDOWN_TIME,
-    markers::{PlayerMarker, ProjectileMarker},
+    markers::PlayerMarker,
+    power_ups::EffectKind,
     protocol::{MapLayout, *},
     spawning::calculate_projectile_spawns,
 };
@@ -24,7 +28,21 @@ pub fn dispatch_message(
     time: &Res<Time>,
     player_data: &Query<(&Position, &Speed, &FaceDirection), With<PlayerMarker>>,
     map_layout: &MapLayout,
+    next_projectile_id: &mut NextProjectileId,
+    leaderboard: &Leaderboard,
 ) {
+    // Spectators have no player to move or shoot
+    let is_spectator = players.0.get(&id).is_some_and(|info| info.spectator);
+    if is_spectator
+        && matches!(
+            msg,
+            ClientMessage::Speed(_) | ClientMessage::Face(_) | ClientMessage::Jump(_) | ClientMessage::Shot(_)
+        )
+    {
+        trace!("{:?} spectator sent a player message", id);
+        return;
+    }
+
     match msg {
         ClientMessage::Login(_) => {
             warn!("{:?} sent login after already authenticated", id);
@@ -44,13 +62,30 @@ pub fn dispatch_message(
             trace!("{:?} face direction: {}", id, msg.dir);
             handle_face_message(commands, entity, id, msg, &*players);
         }
+        ClientMessage::Jump(_) => {
+            trace!("{:?} jump", id);
+            handle_jump_message(id, players);
+        }
         ClientMessage::Shot(msg) => {
             debug!("{id:?} shot");
-            handle_shot_message(commands, entity, id, msg, players, time, player_data, map_layout);
+            handle_shot_message(
+                commands,
+                entity,
+                id,
+                msg,
+                players,
+                time,
+                player_data,
+                map_layout,
+                next_projectile_id,
+            );
         }
         ClientMessage::Echo(msg) => {
             handle_echo_message(id, msg, players);
         }
+        ClientMessage::Leaderboard(msg) => {
+            handle_leaderboard_message(id, msg, players, leaderboard);
+        }
     }
 }
 
@@ -59,12 +94,28 @@ pub fn dispatch_message(
 // ============================================================================
 
 // Handle logoff message.
-fn handle_logoff_message(commands: &mut Commands, entity: Entity, id: PlayerId, _msg: CLogoff, players: &PlayerMap) {
+fn handle_logoff_message(
+    commands: &mut Commands,
+    entity: Entity,
+    id: PlayerId,
+    _msg: CLogoff,
+    players: &mut PlayerMap,
+) {
     debug!("{:?} logged off", id);
     commands.entity(entity).despawn();
 
-    // Broadcast graceful logoff to all other players
-    broadcast_to_others(players, id, ServerMessage::Logoff(SLogoff { id, graceful: true }));
+    // Not to be resumed when the connection closes
+    let Some(player_info) = players.0.get_mut(&id) else {
+        return;
+    };
+    player_info.logged_in = false;
+
+    if player_info.spectator {
+        broadcast_spectators(players);
+    } else {
+        // Broadcast graceful logoff to all other players
+        broadcast_to_others(players, id, ServerMessage::Logoff(SLogoff { id, graceful: true }));
+    }
 }
 
 // Handle speed message.
@@ -81,8 +132,8 @@ fn handle_speed_message(
 
     // Get current position for reconciliation
     if let Ok((pos, _, _)) = player_data.get(entity) {
-        // Broadcast speed update with position to all other logged-in players
-        broadcast_to_others(
+        // Broadcast speed update with position to all other logged-in players that can see this player
+        broadcast_to_observers(
             players,
             id,
             ServerMessage::Speed(SSpeed {
@@ -99,10 +150,19 @@ fn handle_face_message(commands: &mut Commands, entity: Entity, id: PlayerId, ms
     // Update the player's face direction
     commands.entity(entity).insert(FaceDirection(msg.dir));
 
-    broadcast_to_others(players, id, ServerMessage::Face(SFace { id, dir: msg.dir }));
+    broadcast_to_observers(players, id, ServerMessage::Face(SFace { id, dir: msg.dir }));
 }
 
-// Handle shot message.
+// Handle jump message. The jump itself is started by the movement system if the player stands on a floor.
+fn handle_jump_message(id: PlayerId, players: &mut PlayerMap) {
+    if let Some(player_info) = players.0.get_mut(&id) {
+        player_info.jump_requested = true;
+    }
+}
+
+// Handle shot message. Shots faster than the fired weapon allows (or than any two shots may follow each other)
+// are ignored. The spawned projectiles are sent to all players, the shooter included, so every client simulates
+// the same projectiles.
 fn handle_shot_message(
     commands: &mut Commands,
     entity: Entity,
@@ -112,9 +172,8 @@ fn handle_shot_message(
     time: &Res<Time>,
     player_data: &Query<(&Position, &Speed, &FaceDirection), With<PlayerMarker>>,
     map_layout: &MapLayout,
+    next_projectile_id: &mut NextProjectileId,
 ) {
-    use common::constants::ALWAYS_MULTI_SHOT;
-
     let now = time.elapsed_secs();
 
     let has_multi_shot = {
@@ -122,52 +181,55 @@ fn handle_shot_message(
             return;
         };
 
-        if now - player_info.last_shot_time < PROJECTILE_COOLDOWN_TIME {
+        let weapon_shot_time = &mut player_info.weapon_shot_times[msg.weapon as usize];
+        if now - player_info.last_shot_time < PROJECTILE_COOLDOWN_TIME
+            || now - *weapon_shot_time < msg.weapon.def().fire_interval
+        {
             return; // Throttled: ignore
         }
 
+        *weapon_shot_time = now;
         player_info.last_shot_time = now;
 
-        ALWAYS_MULTI_SHOT || player_info.multi_shot_power_up_timer > 0.0
+        player_info.has_effect(EffectKind::MultiShot)
     };
 
     // Update the shooter's face direction to exact facing direction
     commands.entity(entity).insert(FaceDirection(msg.face_dir));
 
-    // Spawn projectile(s) on server for hit detection
-    if let Ok((pos, _, _)) = player_data.get(entity) {
-        // Calculate valid projectile spawn positions (all_walls excludes roof-edge guards)
-        let spawns = calculate_projectile_spawns(
-            pos,
-            msg.face_dir,
-            msg.face_pitch,
-            has_multi_shot,
-            &map_layout.lower_walls,
-            &map_layout.ramps,
-            &map_layout.roofs,
-        );
+    let Ok((pos, _, _)) = player_data.get(entity) else {
+        return;
+    };
 
-        // Spawn each projectile
-        for spawn_info in spawns {
-            let projectile = Projectile::new(spawn_info.direction_yaw, spawn_info.direction_pitch);
+    // Calculate valid projectile spawn positions (visible walls exclude roof-edge guards)
+    let walls: Vec<_> = map_layout.visible_walls().copied().collect();
+    let spawns = calculate_projectile_spawns(
+        pos,
+        msg.face_dir,
+        msg.face_pitch,
+        msg.weapon,
+        has_multi_shot,
+        &walls,
+        &map_layout.ramps,
+        &map_layout.roofs,
+    );
 
-            commands.spawn((
-                ProjectileMarker,
-                id, // Tag projectile with shooter's ID
-                spawn_info.position,
-                projectile,
-            ));
-        }
+    // Spawn projectile(s) on server for hit detection, tagged with shooter's ID
+    let projectiles = spawn_projectiles(commands, next_projectile_id, &spawns, msg.weapon, id);
+    if let Some(player_info) = players.0.get_mut(&id) {
+        player_info.stats.shots_fired += projectiles.len() as u32;
     }
 
-    // Broadcast shot with face direction to all other logged-in players
-    broadcast_to_others(
+    // Broadcast shot with face direction and projectiles to all logged-in players
+    broadcast_to_all(
         players,
-        id,
         ServerMessage::Shot(SShot {
             id,
             face_dir: msg.face_dir,
             face_pitch: msg.face_pitch,
+            weapon: msg.weapon,
+            seq: msg.seq,
+            projectiles,
         }),
     );
 }
@@ -182,3 +244,14 @@ fn handle_echo_message(id: PlayerId, msg: CEcho, players: &PlayerMap) {
         let _ = player_info.channel.send(ServerToClient::Send(echo_msg));
     }
 }
+
+// Handle leaderboard request.
+fn handle_leaderboard_message(id: PlayerId, _msg: CLeaderboard, players: &PlayerMap, leaderboard: &Leaderboard) {
+    debug!("{:?} requested leaderboard", id);
+    if let Some(player_info) = players.0.get(&id) {
+        let leaderboard_msg = ServerMessage::Leaderboard(SLeaderboard {
+            entries: leaderboard.top(),
+        });
+        let _ = player_info.channel.send(ServerToClient::Send(leaderboard_msg));
+    }
+}
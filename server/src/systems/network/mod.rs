@@ -5,7 +5,8 @@ mod messages;
 mod systems;
 
 pub use broadcast::{
-    broadcast_to_all, broadcast_to_others, collect_items, collect_sentries, snapshot_logged_in_players,
+    broadcast_to_all, broadcast_to_observers, broadcast_to_others, collect_items, collect_sentries,
+    snapshot_logged_in_players,
 };
-pub use connection::network_accept_connections_system;
+pub use connection::{network_accept_connections_system, network_disconnected_players_system};
 pub use systems::{network_broadcast_state_system, network_client_message_system};
@@ -1,17 +1,22 @@
 use bevy::prelude::*;
 
 use super::{
-    broadcast::{broadcast_to_all, broadcast_to_others, collect_items, collect_sentries, snapshot_logged_in_players},
-    login::handle_login_message,
+    broadcast::{collect_items, collect_sentries, snapshot_logged_in_players},
+    connection::handle_disconnect,
+    login::{handle_login_message, handle_password_check},
     messages::dispatch_message,
 };
 use crate::{
-    net::ClientToServer,
-    resources::{FromClientsChannel, GridConfig, ItemMap, PlayerMap, SentryMap},
+    net::{ClientToServer, ServerToClient},
+    resources::{
+        Accounts, DisconnectedPlayers, FromClientsChannel, GridConfig, ItemMap, Leaderboard, NextProjectileId,
+        PlayerMap, SentryMap,
+    },
 };
 use common::{
     constants::UPDATE_BROADCAST_INTERVAL,
     markers::{ItemMarker, PlayerMarker, SentryMarker},
+    power_ups::server_tick,
     protocol::{MapLayout, *},
 };
 
@@ -31,10 +36,15 @@ pub fn network_client_message_system(
     grid_config: Res<GridConfig>,
     items: Res<ItemMap>,
     sentries: Res<SentryMap>,
+    mut next_projectile_id: ResMut<NextProjectileId>,
+    leaderboard: Res<Leaderboard>,
+    mut accounts: ResMut<Accounts>,
+    mut disconnected: ResMut<DisconnectedPlayers>,
     player_data: Query<(&Position, &Speed, &FaceDirection), With<PlayerMarker>>,
     item_positions: Query<&Position, With<ItemMarker>>,
     sentry_data: Query<(&Position, &Velocity), With<SentryMarker>>,
 ) {
+    let tick = server_tick(time.elapsed());
     while let Ok((id, event)) = from_clients.try_recv() {
         let Some(player_info) = players.0.get(&id) else {
             error!("received event for unknown {:?}", id);
@@ -43,17 +53,12 @@ pub fn network_client_message_system(
 
         match event {
             ClientToServer::Disconnected => {
-                let was_logged_in = player_info.logged_in;
-                let entity = player_info.entity;
-                players.0.remove(&id);
-                commands.entity(entity).despawn();
-
-                debug!("{:?} disconnected (logged_in: {})", id, was_logged_in);
-
-                // Broadcast logoff to all other logged-in players if they were logged in
-                if was_logged_in {
-                    broadcast_to_others(&players, id, ServerMessage::Logoff(SLogoff { id, graceful: false }));
+                // A connection that resumed the player has replaced the channel - only the old one closed
+                if !player_info.channel.is_closed() {
+                    debug!("{:?} replaced connection closed", id);
+                    continue;
                 }
+                handle_disconnect(&mut commands, id, &mut players, &mut disconnected, &player_data);
             }
             ClientToServer::Message(message) => {
                 let is_logged_in = player_info.logged_in;
@@ -67,6 +72,8 @@ pub fn network_client_message_system(
                         &time,
                         &player_data,
                         &map_layout,
+                        &mut next_projectile_id,
+                        &leaderboard,
                     );
                 } else {
                     handle_login_message(
@@ -75,6 +82,9 @@ pub fn network_client_message_system(
                         id,
                         message,
                         &mut players,
+                        &mut accounts,
+                        &mut disconnected,
+                        tick,
                         &map_layout,
                         &grid_config,
                         &items,
@@ -87,6 +97,25 @@ pub fn network_client_message_system(
             }
         }
     }
+
+    // Finish logins whose password has been checked
+    while let Ok(check) = accounts.try_recv_password_check() {
+        handle_password_check(
+            &mut commands,
+            check,
+            &mut players,
+            &mut accounts,
+            &disconnected,
+            tick,
+            &map_layout,
+            &grid_config,
+            &items,
+            &sentries,
+            &player_data,
+            &item_positions,
+            &sentry_data,
+        );
+    }
 }
 
 // ============================================================================
@@ -118,22 +147,27 @@ pub fn network_broadcast_state_system(
         return; // Nothing to broadcast yet
     }
 
-    // Collect all logged-in players
-    let all_players = snapshot_logged_in_players(&players, &player_data);
-
     // Collect all items
     let all_items = collect_items(&items, &item_positions);
 
     // Collect all sentries
     let all_sentries = collect_sentries(&sentries, &sentry_data);
 
-    // Broadcast to all logged-in clients
-    let msg = ServerMessage::Update(SUpdate {
-        seq: *seq,
-        players: all_players,
-        items: all_items,
-        sentries: all_sentries,
-    });
-    //trace!("broadcasting update: {:?}", msg);
-    broadcast_to_all(&players, msg);
+    // Send to each logged-in client, with only the players it can see
+    let tick = server_tick(time.elapsed());
+    for (viewer_id, viewer_info) in &players.0 {
+        if !viewer_info.logged_in {
+            continue;
+        }
+
+        let msg = ServerMessage::Update(SUpdate {
+            seq: *seq,
+            tick,
+            players: snapshot_logged_in_players(&players, &player_data, *viewer_id, tick),
+            items: all_items.clone(),
+            sentries: all_sentries.clone(),
+        });
+        //trace!("sending update: {:?}", msg);
+        let _ = viewer_info.channel.send(ServerToClient::Send(msg));
+    }
 }
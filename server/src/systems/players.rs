@@ -1,14 +1,24 @@
 use bevy::prelude::*;
 
-use super::network::broadcast_to_all;
-use crate::resources::PlayerMap;
+use super::network::{broadcast_to_all, broadcast_to_observers};
+use crate::{
+    constants::{CLOAK_REVEAL_DISTANCE, CLOAK_REVEAL_DURATION},
+    resources::PlayerMap,
+};
 use common::{
-    collision::{slide_player_along_obstacles, sweep_player_vs_ramp_edges, sweep_player_vs_wall},
-    constants::{ALWAYS_PHASING, ALWAYS_SPEED, PHYSICS_EPSILON, POWER_UP_SPEED_MULTIPLIER, ROOF_HEIGHT},
-    map::{close_to_roof, has_roof, height_on_ramp},
+    collision::{
+        jump_pad_launch, player_is_grounded, slide_player_along_obstacles, step_player_vertical,
+        sweep_player_vs_ramp_edges, sweep_player_vs_wall,
+    },
+    constants::{PHYSICS_EPSILON, PLAYER_JUMP_SPEED, POWER_UP_SPEED_MULTIPLIER},
+    map::walls_at_height,
     markers::PlayerMarker,
     players::{PlannedMove, overlaps_other_player},
-    protocol::{MapLayout, PlayerId, Position, ServerMessage, Speed},
+    power_ups::{EffectKind, server_tick},
+    protocol::{
+        JumpPadLaunch, Knockback, MapLayout, PlayerId, Position, SJump, ServerMessage, Speed, Velocity,
+        VerticalVelocity,
+    },
 };
 
 // ============================================================================
@@ -16,108 +26,98 @@ use common::{
 // ============================================================================
 
 pub fn players_movement_system(
+    mut commands: Commands,
     time: Res<Time>,
     map_layout: Res<MapLayout>,
-    players: Res<PlayerMap>,
-    mut query: Query<(Entity, &mut Position, &Speed, &PlayerId), With<PlayerMarker>>,
+    mut players: ResMut<PlayerMap>,
+    mut query: Query<
+        (
+            Entity,
+            &mut Position,
+            &Speed,
+            &mut VerticalVelocity,
+            Option<&JumpPadLaunch>,
+            Option<&mut Knockback>,
+            &PlayerId,
+        ),
+        With<PlayerMarker>,
+    >,
 ) {
     let delta = time.delta_secs();
 
     // Pass 1: For each player, calculate intended position, then apply wall collision logic
     let mut planned_moves: Vec<PlannedMove> = Vec::new();
 
-    for (entity, pos, speed, player_id) in query.iter() {
+    for (entity, pos, speed, mut vertical_velocity, launch, knockback, player_id) in &mut query {
         // Check if player is stunned
         let is_stunned = players.0.get(player_id).is_some_and(|info| info.stun_timer > 0.0);
 
-        if is_stunned {
-            // Stunned players cannot move
-            planned_moves.push(PlannedMove {
-                entity,
-                start: *pos,
-                target: *pos,
-                collides: false,
-            });
-            continue;
+        // Start a jump if one was requested and the player stands on a floor (requests are never buffered)
+        let jump_requested = players
+            .0
+            .get_mut(player_id)
+            .is_some_and(|info| std::mem::take(&mut info.jump_requested));
+        if jump_requested && !is_stunned && player_is_grounded(&map_layout, &pos, vertical_velocity.0) {
+            vertical_velocity.0 = PLAYER_JUMP_SPEED;
+            broadcast_to_observers(&players, *player_id, ServerMessage::Jump(SJump { id: *player_id }));
         }
 
-        // Calculate intended position from velocity
-        let multiplier = players
-            .0
-            .get(player_id)
-            .and_then(|info| (ALWAYS_SPEED || info.speed_power_up_timer > 0.0).then_some(POWER_UP_SPEED_MULTIPLIER))
-            .unwrap_or(1.0);
-        let velocity = speed.to_velocity().with_speed_multiplier(multiplier);
-
-        let velocity_sq = velocity.x.mul_add(velocity.x, velocity.z * velocity.z);
-        let is_standing_still = velocity_sq < PHYSICS_EPSILON * PHYSICS_EPSILON;
-
-        if is_standing_still {
-            planned_moves.push(PlannedMove {
-                entity,
-                start: *pos,
-                target: *pos,
-                collides: false,
-            });
-            continue;
+        // Launch players stepping onto a jump pad
+        let mut launch = launch.copied();
+        if launch.is_none()
+            && !is_stunned
+            && let Some(launch_velocity) = jump_pad_launch(&map_layout, &pos, vertical_velocity.0)
+        {
+            vertical_velocity.0 = launch_velocity.y;
+            let new_launch = JumpPadLaunch {
+                vel_x: launch_velocity.x,
+                vel_z: launch_velocity.z,
+            };
+            commands.entity(entity).insert(new_launch);
+            launch = Some(new_launch);
         }
 
-        // Calculate intended position from velocity
-        let mut target_pos = Position {
-            x: velocity.x.mul_add(delta, pos.x),
-            y: pos.y, // Keep current Y for collision detection
-            z: velocity.z.mul_add(delta, pos.z),
-        };
+        let has_phasing = players
+            .0
+            .get(player_id)
+            .is_some_and(|info| info.has_effect(EffectKind::Phasing));
 
-        // Check collision and calculate target (with sliding if collision)
-        let walls_to_check = if close_to_roof(pos.y) {
-            &map_layout.roof_walls
-        } else {
-            let has_phasing = ALWAYS_PHASING
-                || players
-                    .0
-                    .get(player_id)
-                    .is_some_and(|info| info.phasing_power_up_timer > 0.0);
-            if has_phasing {
-                &map_layout.boundary_walls
-            } else {
-                &map_layout.lower_walls
+        // Launched players follow the jump pad arc, stunned players cannot move, but both still fall
+        let mut velocity = if let Some(launch) = launch {
+            Velocity {
+                x: launch.vel_x,
+                y: 0.0,
+                z: launch.vel_z,
             }
+        } else if is_stunned {
+            Velocity::default()
+        } else {
+            let multiplier = players
+                .0
+                .get(player_id)
+                .and_then(|info| info.has_effect(EffectKind::Speed).then_some(POWER_UP_SPEED_MULTIPLIER))
+                .unwrap_or(1.0);
+            speed.to_velocity().with_speed_multiplier(multiplier)
         };
 
-        // Check collision and calculate target (with sliding if collision)
-        let mut collides = false;
-
-        for wall in walls_to_check {
-            if sweep_player_vs_wall(pos, &target_pos, wall) {
-                collides = true;
-                break;
-            }
-        }
-
-        if !collides {
-            for ramp in &map_layout.ramps {
-                if sweep_player_vs_ramp_edges(pos, &target_pos, ramp) {
-                    collides = true;
-                    break;
-                }
+        // Explosions push players on top of their own movement until the push dies out
+        if let Some(mut knockback) = knockback {
+            velocity.x += knockback.vel_x;
+            velocity.z += knockback.vel_z;
+            if !knockback.decay(delta) {
+                commands.entity(entity).remove::<Knockback>();
             }
         }
 
-        if collides {
-            target_pos =
-                slide_player_along_obstacles(walls_to_check, &map_layout.ramps, pos, velocity.x, velocity.z, delta);
-        }
+        let (mut target_pos, collides) = plan_horizontal_move(&map_layout, &pos, &velocity, has_phasing, delta);
 
-        let target_height_on_ramp = height_on_ramp(&map_layout.ramps, target_pos.x, target_pos.z);
-        let target_has_roof = has_roof(&map_layout.roofs, target_pos.x, target_pos.z);
+        // Apply gravity and land on ramps, roofs or the ground
+        (target_pos.y, vertical_velocity.0) =
+            step_player_vertical(&map_layout, &target_pos, vertical_velocity.0, delta);
 
-        if target_height_on_ramp > 0.0 {
-            target_pos.y = target_height_on_ramp;
-        } else if target_has_roof && close_to_roof(pos.y) {
-            target_pos.y = ROOF_HEIGHT;
-        } else {
-            target_pos.y = 0.0;
+        // The jump pad flight ends on landing
+        if launch.is_some() && player_is_grounded(&map_layout, &target_pos, vertical_velocity.0) {
+            commands.entity(entity).remove::<JumpPadLaunch>();
         }
 
         planned_moves.push(PlannedMove {
@@ -130,13 +130,63 @@ pub fn players_movement_system(
 
     // Pass 2: Check player-player collisions and apply final positions
     for planned_move in &planned_moves {
+        let Ok((_, mut pos, _, mut vertical_velocity, _, _, player_id)) = query.get_mut(planned_move.entity) else {
+            continue;
+        };
+
         if overlaps_other_player(planned_move, &planned_moves) {
+            // Blocked by another player, e.g. landed on their head
+            vertical_velocity.0 = 0.0;
             continue;
         }
 
-        if let Ok((_, mut pos, _, _)) = query.get_mut(planned_move.entity) {
-            *pos = planned_move.target;
+        if let Some(player_info) = players.0.get_mut(player_id) {
+            let (start, target) = (planned_move.start, planned_move.target);
+            player_info.stats.distance += (target.x - start.x).hypot(target.z - start.z).hypot(target.y - start.y);
         }
+        *pos = planned_move.target;
+    }
+}
+
+// Calculate the horizontal target position of a player from its velocity, sliding along walls and ramp edges.
+// Returns the target (at the current height) and whether the player collided.
+fn plan_horizontal_move(
+    map_layout: &MapLayout,
+    pos: &Position,
+    velocity: &Velocity,
+    has_phasing: bool,
+    delta: f32,
+) -> (Position, bool) {
+    let velocity_sq = velocity.x.mul_add(velocity.x, velocity.z * velocity.z);
+    if velocity_sq < PHYSICS_EPSILON * PHYSICS_EPSILON {
+        return (*pos, false);
+    }
+
+    // Calculate intended position from velocity
+    let target_pos = Position {
+        x: velocity.x.mul_add(delta, pos.x),
+        y: pos.y, // Keep current Y for collision detection
+        z: velocity.z.mul_add(delta, pos.z),
+    };
+
+    // Walls that vertically overlap the player at the current height
+    let walls_to_check = walls_at_height(map_layout, pos.y, has_phasing);
+
+    // Check collision and calculate target (with sliding if collision)
+    let collides = walls_to_check
+        .iter()
+        .any(|wall| sweep_player_vs_wall(pos, &target_pos, wall))
+        || map_layout
+            .ramps
+            .iter()
+            .any(|ramp| sweep_player_vs_ramp_edges(pos, &target_pos, ramp));
+
+    if collides {
+        let slid_pos =
+            slide_player_along_obstacles(&walls_to_check, &map_layout.ramps, pos, velocity.x, velocity.z, delta);
+        (slid_pos, true)
+    } else {
+        (target_pos, false)
     }
 }
 
@@ -147,17 +197,19 @@ pub fn players_movement_system(
 // System to count down player power-up and stun timers
 pub fn players_timer_system(time: Res<Time>, mut players: ResMut<PlayerMap>) {
     let delta = time.delta_secs();
+    let tick = server_tick(time.elapsed());
 
     let mut status_messages = Vec::new();
 
     for (player_id, player_info) in &mut players.0 {
-        let old_status = player_info.status(*player_id);
+        let old_status = player_info.status(*player_id, tick);
 
         player_info.tick_timers(delta);
 
-        let new_status = player_info.status(*player_id);
+        let new_status = player_info.status(*player_id, tick);
 
-        if old_status != new_status {
+        // Expiry ticks stay put while the effects run down, the clients count them down on their own
+        if old_status.effects != new_status.effects || old_status.stunned != new_status.stunned {
             status_messages.push(new_status);
         }
     }
@@ -167,3 +219,53 @@ pub fn players_timer_system(time: Res<Time>, mut players: ResMut<PlayerMap>) {
         broadcast_to_all(&players, ServerMessage::PlayerStatus(msg));
     }
 }
+
+// ============================================================================
+// Players Cloak System
+// ============================================================================
+
+// System to decide which players can see each cloaked player: everyone shortly after the cloaked player
+// fired, otherwise only players within `CLOAK_REVEAL_DISTANCE`. Spectators always see cloaked players.
+pub fn players_cloak_system(
+    time: Res<Time>,
+    mut players: ResMut<PlayerMap>,
+    query: Query<(&PlayerId, &Position), With<PlayerMarker>>,
+) {
+    let now = time.elapsed_secs();
+    let positions: Vec<(PlayerId, Position)> = query
+        .iter()
+        .filter(|(player_id, _)| players.0.get(player_id).is_some_and(|info| info.logged_in))
+        .map(|(player_id, pos)| (*player_id, *pos))
+        .collect();
+    let spectators: Vec<PlayerId> = players
+        .0
+        .iter()
+        .filter(|(_, info)| info.logged_in && info.spectator)
+        .map(|(player_id, _)| *player_id)
+        .collect();
+
+    for (player_id, pos) in &positions {
+        let Some(player_info) = players.0.get_mut(player_id) else {
+            continue;
+        };
+
+        player_info.revealed_to.clear();
+        if !player_info.has_effect(EffectKind::Cloak) {
+            continue;
+        }
+
+        let just_fired = now - player_info.last_shot_time < CLOAK_REVEAL_DURATION;
+        player_info.revealed_to.extend(
+            positions
+                .iter()
+                .filter(|(other_id, other_pos)| {
+                    let distance = (other_pos.x - pos.x)
+                        .hypot(other_pos.z - pos.z)
+                        .hypot(other_pos.y - pos.y);
+                    other_id != player_id && (just_fired || distance <= CLOAK_REVEAL_DISTANCE)
+                })
+                .map(|(other_id, _)| *other_id),
+        );
+        player_info.revealed_to.extend(spectators.iter().copied());
+    }
+}
@@ -3,15 +3,249 @@ use bevy::prelude::*;
 use super::network::broadcast_to_all;
 use crate::{
     constants::{SENTRY_HIT_REWARD, SENTRY_TARGET_DURATION},
-    resources::{PlayerMap, SentryMap, SentryMode},
+    resources::{DestructibleWallMap, GridConfig, NextProjectileId, PlayerMap, SentryMap, SentryMode},
+    systems::sentries::archetypes::archetype_stats,
 };
 use common::{
-    collision::{Projectile, projectile_hits_sentry, sweep_projectile_vs_player},
-    constants::ALWAYS_SENTRY_HUNT,
+    collision::{HitDirection, Projectile, explosion_knockback, projectile_hits_sentry, sweep_projectile_vs_player},
+    constants::PHYSICS_EPSILON,
     markers::{PlayerMarker, ProjectileMarker, SentryMarker},
+    power_ups::{EffectKind, server_tick},
     protocol::{MapLayout, *},
+    spawning::ProjectileSpawnInfo,
+    weapons::WeaponId,
 };
 
+// ============================================================================
+// Helper Functions
+// ============================================================================
+
+// Spawn the projectiles of a shot, tagged with the shooter's ID (a player's or a sentry's). Each projectile
+// gets a fresh ID. Returns the spawn states to send to clients.
+pub fn spawn_projectiles<S: Component + Copy>(
+    commands: &mut Commands,
+    next_projectile_id: &mut NextProjectileId,
+    spawns: &[ProjectileSpawnInfo],
+    weapon: WeaponId,
+    shooter_id: S,
+) -> Vec<ProjectileSpawn> {
+    spawns
+        .iter()
+        .map(|spawn_info| {
+            let id = next_projectile_id.allocate();
+            let projectile = Projectile::new(spawn_info.direction_yaw, spawn_info.direction_pitch, weapon);
+            let velocity = projectile.velocity.into();
+            commands.spawn((ProjectileMarker, id, shooter_id, spawn_info.position, projectile));
+
+            ProjectileSpawn {
+                id,
+                position: spawn_info.position,
+                velocity,
+            }
+        })
+        .collect()
+}
+
+// Despawn a projectile and tell all clients what it hit, so they remove their copy at the same time
+fn despawn_projectile(
+    commands: &mut Commands,
+    entity: Entity,
+    id: ProjectileId,
+    hit: ProjectileHit,
+    players: &PlayerMap,
+) {
+    commands.entity(entity).despawn();
+    broadcast_to_all(
+        players,
+        ServerMessage::ProjectileDespawn(SProjectileDespawn { id, hit }),
+    );
+}
+
+// Apply one projectile hit to a destructible wall. Once its hit points are used up, the wall is removed from
+// the map layout and the grid (so sentries can path through the gap) and all clients are notified.
+fn damage_destructible_wall(
+    wall_id: WallId,
+    map_layout: &mut MapLayout,
+    grid_config: &mut GridConfig,
+    destructible_walls: &mut DestructibleWallMap,
+    players: &PlayerMap,
+) {
+    let Some(wall_info) = destructible_walls.0.get_mut(&wall_id) else {
+        return;
+    };
+
+    wall_info.hit_points -= 1;
+    if wall_info.hit_points > 0 {
+        return;
+    }
+
+    info!("{:?} destroyed", wall_id);
+
+    grid_config.remove_wall(wall_info.grid_x, wall_info.grid_z, wall_info.edge);
+    map_layout.remove_destructible_wall(wall_id);
+    destructible_walls.0.remove(&wall_id);
+
+    broadcast_to_all(players, ServerMessage::WallDestroyed(SWallDestroyed { id: wall_id }));
+}
+
+// Let the hit player's shield absorb a projectile. If it does, all clients are notified of the absorb and
+// the player's remaining shield charges. Returns whether the hit was absorbed.
+fn shield_absorbs_hit(players: &mut PlayerMap, player_id: PlayerId, tick: u32) -> bool {
+    let Some(target_info) = players.0.get_mut(&player_id) else {
+        return false;
+    };
+    if !target_info.absorb_hit() {
+        return false;
+    }
+
+    let status = target_info.status(player_id, tick);
+    broadcast_to_all(players, ServerMessage::ShieldAbsorb(SShieldAbsorb { id: player_id }));
+    broadcast_to_all(players, ServerMessage::PlayerStatus(status));
+    true
+}
+
+// Check a turret projectile against all players. The hit player loses a point and is knocked back (unless
+// their shield absorbs the hit) and all clients are notified. Returns what was hit, if anything.
+fn turret_projectile_hits_player(
+    commands: &mut Commands,
+    proj_pos: &Position,
+    projectile: &Projectile,
+    delta: f32,
+    player_query: &Query<(&Position, &FaceDirection, &PlayerId), (With<PlayerMarker>, Without<ProjectileMarker>)>,
+    players: &mut PlayerMap,
+    tick: u32,
+) -> Option<ProjectileHit> {
+    let (hit_dir, player_id) = player_query.iter().find_map(|(position, face_direction, player_id)| {
+        sweep_projectile_vs_player(proj_pos, projectile, delta, position, face_direction.0)
+            .map(|hit_dir| (hit_dir, *player_id))
+    })?;
+
+    if shield_absorbs_hit(players, player_id, tick) {
+        return Some(ProjectileHit::Shield(player_id));
+    }
+
+    if let Some(target_info) = players.0.get_mut(&player_id) {
+        target_info.hits -= 1;
+        target_info.stats.hits_taken += 1;
+    }
+    knock_back_hit_player(commands, players, player_id, hit_dir);
+
+    broadcast_to_all(
+        players,
+        ServerMessage::Hit(SHit {
+            id: player_id,
+            hit_dir_x: hit_dir.x,
+            hit_dir_z: hit_dir.z,
+        }),
+    );
+
+    Some(ProjectileHit::Player(player_id))
+}
+
+// Push a hit player in the direction of the hit. Clients apply the same push when they receive the `SHit`.
+fn knock_back_hit_player(commands: &mut Commands, players: &PlayerMap, player_id: PlayerId, hit_dir: HitDirection) {
+    if let Some(target_info) = players.0.get(&player_id) {
+        commands
+            .entity(target_info.entity)
+            .insert(Knockback::from_hit(hit_dir.x, hit_dir.z));
+    }
+}
+
+// Score a hit of a player's projectile on another player. The shooter gains a point, the hit player loses one
+// and is knocked back (unless their shield absorbs the hit, which gives no points) and all clients are notified.
+// Returns what was hit.
+fn score_player_hit(
+    commands: &mut Commands,
+    players: &mut PlayerMap,
+    shooter_id: PlayerId,
+    player_id: PlayerId,
+    hit_dir: HitDirection,
+    tick: u32,
+) -> ProjectileHit {
+    // A shield absorbs the hit: no points for either player
+    if shield_absorbs_hit(players, player_id, tick) {
+        info!("{:?} hits shield of {:?}", shooter_id, player_id);
+        return ProjectileHit::Shield(player_id);
+    }
+
+    info!("{:?} hits {:?}", shooter_id, player_id);
+
+    if let Some(shooter_info) = players.0.get_mut(&shooter_id) {
+        shooter_info.hits += 1;
+        shooter_info.stats.hits_landed += 1;
+    }
+    if let Some(target_info) = players.0.get_mut(&player_id) {
+        target_info.hits -= 1;
+        target_info.stats.hits_taken += 1;
+    }
+    knock_back_hit_player(commands, players, player_id, hit_dir);
+
+    broadcast_to_all(
+        players,
+        ServerMessage::Hit(SHit {
+            id: player_id,
+            hit_dir_x: hit_dir.x,
+            hit_dir_z: hit_dir.z,
+        }),
+    );
+
+    ProjectileHit::Player(player_id)
+}
+
+// Detonate an explosive projectile. Every player except the shooter caught in the blast (within its radius and
+// with no wall in between) is hit and pushed away, and all clients are notified to show the explosion.
+fn detonate_projectile(
+    commands: &mut Commands,
+    proj_pos: &Position,
+    projectile: &Projectile,
+    shooter_id: PlayerId,
+    player_query: &Query<(&Position, &FaceDirection, &PlayerId), (With<PlayerMarker>, Without<ProjectileMarker>)>,
+    map_layout: &MapLayout,
+    players: &mut PlayerMap,
+    tick: u32,
+) {
+    let Some(explosion) = &projectile.weapon.def().explosion else {
+        return;
+    };
+
+    let mut knockbacks = Vec::new();
+    for (position, _, player_id) in player_query.iter() {
+        if *player_id == shooter_id {
+            continue;
+        }
+        let Some(knockback) = explosion_knockback(proj_pos, position, explosion, &map_layout.lower_walls) else {
+            continue;
+        };
+
+        let strength = knockback.vel_x.hypot(knockback.vel_z);
+        let hit_dir = if strength > PHYSICS_EPSILON {
+            HitDirection {
+                x: knockback.vel_x / strength,
+                z: knockback.vel_z / strength,
+            }
+        } else {
+            HitDirection { x: 0.0, z: 0.0 }
+        };
+        score_player_hit(commands, players, shooter_id, *player_id, hit_dir, tick);
+
+        // The blast push replaces the push of the hit (clients receive the `SExplosion` after the `SHit` too)
+        if let Some(target_info) = players.0.get(player_id) {
+            commands.entity(target_info.entity).insert(knockback);
+        }
+        knockbacks.push((*player_id, knockback));
+    }
+
+    broadcast_to_all(
+        players,
+        ServerMessage::Explosion(SExplosion {
+            shooter: shooter_id,
+            weapon: projectile.weapon,
+            position: *proj_pos,
+            knockbacks,
+        }),
+    );
+}
+
 // ============================================================================
 // Projectiles Movement System
 // ============================================================================
@@ -19,20 +253,38 @@ use common::{
 pub fn projectiles_movement_system(
     mut commands: Commands,
     time: Res<Time>,
-    mut projectile_query: Query<(Entity, &mut Position, &mut Projectile, &PlayerId), With<ProjectileMarker>>,
+    mut projectile_query: Query<
+        (Entity, &ProjectileId, &mut Position, &mut Projectile, Option<&PlayerId>),
+        With<ProjectileMarker>,
+    >,
     player_query: Query<(&Position, &FaceDirection, &PlayerId), (With<PlayerMarker>, Without<ProjectileMarker>)>,
     sentry_query: Query<(&SentryId, &Position, &FaceDirection), (With<SentryMarker>, Without<ProjectileMarker>)>,
-    map_layout: Res<MapLayout>,
+    mut map_layout: ResMut<MapLayout>,
+    mut grid_config: ResMut<GridConfig>,
+    mut destructible_walls: ResMut<DestructibleWallMap>,
     mut players: ResMut<PlayerMap>,
     mut sentries: ResMut<SentryMap>,
 ) {
     let delta = time.delta_secs();
+    let tick = server_tick(time.elapsed());
 
-    for (proj_entity, mut proj_pos, mut projectile, shooter_id) in &mut projectile_query {
-        // Check lifetime and despawn if expired
+    for (proj_entity, proj_id, mut proj_pos, mut projectile, shooter_id) in &mut projectile_query {
+        // Check lifetime and despawn if expired (explosive projectiles detonate when their fuse runs out)
         projectile.lifetime.tick(time.delta());
         if projectile.lifetime.is_finished() {
-            commands.entity(proj_entity).despawn();
+            if let Some(shooter_id) = shooter_id {
+                detonate_projectile(
+                    &mut commands,
+                    &proj_pos,
+                    &projectile,
+                    *shooter_id,
+                    &player_query,
+                    &map_layout,
+                    &mut players,
+                    tick,
+                );
+            }
+            despawn_projectile(&mut commands, proj_entity, *proj_id, ProjectileHit::Nothing, &players);
             continue;
         }
 
@@ -42,14 +294,27 @@ pub fn projectiles_movement_system(
 
         // Check wall collisions
         let mut bounced = false;
-        for wall in &map_layout.lower_walls {
+        let mut damaged_wall = None;
+        for wall in map_layout.visible_walls() {
             if let Some(new_pos) = projectile.handle_wall_bounce(&proj_pos, delta, wall) {
                 *proj_pos = new_pos;
                 bounced = true;
+                damaged_wall = map_layout.destructible_wall_id(wall);
                 break;
             }
         }
 
+        // Projectiles still bounce off destructible walls, but damage them
+        if let Some(wall_id) = damaged_wall {
+            damage_destructible_wall(
+                wall_id,
+                &mut map_layout,
+                &mut grid_config,
+                &mut destructible_walls,
+                &players,
+            );
+        }
+
         // Check roof collisions
         if !bounced {
             for roof in &map_layout.roofs {
@@ -78,13 +343,53 @@ pub fn projectiles_movement_system(
             bounced = true;
         }
 
-        // If we bounced off something, skip entity collision checks this frame
+        // If we bounced off something, skip entity collision checks this frame. Non-bouncing projectiles
+        // stop at the first obstacle, impact-fused ones detonate there.
+        let explosion = projectile.weapon.def().explosion.as_ref();
         if bounced {
+            if let Some(shooter_id) = shooter_id
+                && explosion.is_some_and(|explosion| explosion.on_impact)
+            {
+                detonate_projectile(
+                    &mut commands,
+                    &proj_pos,
+                    &projectile,
+                    *shooter_id,
+                    &player_query,
+                    &map_layout,
+                    &mut players,
+                    tick,
+                );
+                despawn_projectile(&mut commands, proj_entity, *proj_id, ProjectileHit::Nothing, &players);
+            } else if !projectile.bounces() {
+                despawn_projectile(&mut commands, proj_entity, *proj_id, ProjectileHit::Nothing, &players);
+            }
             continue;
         }
 
         let mut hit_something = false;
 
+        // Projectiles without a shooting player were fired by a turret sentry
+        let Some(shooter_id) = shooter_id else {
+            if let Some(hit) = turret_projectile_hits_player(
+                &mut commands,
+                &proj_pos,
+                &projectile,
+                delta,
+                &player_query,
+                &mut players,
+                tick,
+            ) {
+                info!("turret hits {:?}", hit);
+                despawn_projectile(&mut commands, proj_entity, *proj_id, hit, &players);
+            } else {
+                proj_pos.x += projectile.velocity.x * delta;
+                proj_pos.y += projectile.velocity.y * delta;
+                proj_pos.z += projectile.velocity.z * delta;
+            }
+            continue;
+        };
+
         // Check sentry collisions
         for (sentry_id, sentry_pos, sentry_face_dir) in sentry_query.iter() {
             // Check collision
@@ -94,38 +399,59 @@ pub fn projectiles_movement_system(
                 };
 
                 // Check if shooter has sentry hunt power-up
-                let shooter_has_sentry_hunt = ALWAYS_SENTRY_HUNT
-                    || players
-                        .0
-                        .get(shooter_id)
-                        .is_some_and(|info| info.sentry_hunt_power_up_timer > 0.0);
+                let shooter_has_sentry_hunt = players
+                    .0
+                    .get(shooter_id)
+                    .is_some_and(|info| info.has_effect(EffectKind::SentryHunt));
 
                 if shooter_has_sentry_hunt {
                     // With hunt power-up: give points and remove power-up
                     // Update shooter
                     if let Some(shooter_info) = players.0.get_mut(shooter_id) {
                         shooter_info.hits += SENTRY_HIT_REWARD;
-                        shooter_info.sentry_hunt_power_up_timer = 0.0;
+                        shooter_info.stats.sentries_hunted += 1;
+                        shooter_info.remove_effect(EffectKind::SentryHunt);
                     }
 
                     // Broadcast power-up removal to all clients
                     // Note: sentry_hunt is explicitly false (not using status()) because hitting
                     // a sentry removes the power-up even when ALWAYS_SENTRY_HUNT debug flag is on
                     if let Some(shooter_info) = players.0.get(shooter_id) {
-                        let mut status = shooter_info.status(*shooter_id);
-                        status.sentry_hunt_power_up = false;
+                        let mut status = shooter_info.status(*shooter_id, tick);
+                        status.effects.remove(EffectKind::SentryHunt);
                         broadcast_to_all(&players, ServerMessage::PlayerStatus(status));
                     }
                 }
-                // Without hunt power-up: no points, just make sentry attack
+                // Without hunt power-up: no points, just make sentry attack (unless it ignores such shots)
+                let ignores_shot =
+                    !shooter_has_sentry_hunt && archetype_stats(sentry_info.archetype).immune_to_regular_shots;
 
-                // Always make sentry target the shooter (attack behavior)
-                sentry_info.mode = SentryMode::Target;
-                sentry_info.mode_timer = SENTRY_TARGET_DURATION;
-                sentry_info.follow_target = Some(*shooter_id);
+                // Make sentry target the shooter (attack behavior)
+                if !ignores_shot {
+                    sentry_info.mode = SentryMode::Target;
+                    sentry_info.mode_timer = SENTRY_TARGET_DURATION;
+                    sentry_info.follow_target = Some(*shooter_id);
+                    sentry_info.path = None;
+                }
 
-                // Always despawn the projectile
-                commands.entity(proj_entity).despawn();
+                // Always despawn the projectile (explosive ones detonate on the direct hit)
+                if explosion.is_some() {
+                    detonate_projectile(
+                        &mut commands,
+                        &proj_pos,
+                        &projectile,
+                        *shooter_id,
+                        &player_query,
+                        &map_layout,
+                        &mut players,
+                        tick,
+                    );
+                }
+                let hit = ProjectileHit::Sentry {
+                    id: *sentry_id,
+                    scored: shooter_has_sentry_hunt,
+                };
+                despawn_projectile(&mut commands, proj_entity, *proj_id, hit, &players);
 
                 hit_something = true;
                 break;
@@ -142,39 +468,28 @@ pub fn projectiles_movement_system(
             // Use common hit detection logic
             if let Some(hit_dir) = sweep_projectile_vs_player(&proj_pos, &projectile, delta, position, face_direction.0)
             {
-                // Self-hit: despawn without scoring to match client expectations
-                if shooter_id == player_id {
-                    commands.entity(proj_entity).despawn();
-                    hit_something = true;
-                    break;
-                }
-
-                info!("{:?} hits {:?}", shooter_id, player_id);
-
-                // Update hit counters in separate scopes to avoid borrow conflicts
-                {
-                    if let Some(shooter_info) = players.0.get_mut(shooter_id) {
-                        shooter_info.hits += 1;
-                    }
-                }
-                {
-                    if let Some(target_info) = players.0.get_mut(player_id) {
-                        target_info.hits -= 1;
-                    }
-                }
-
-                // Broadcast hit message to all clients
-                broadcast_to_all(
-                    &players,
-                    ServerMessage::Hit(SHit {
-                        id: *player_id,
-                        hit_dir_x: hit_dir.x,
-                        hit_dir_z: hit_dir.z,
-                    }),
-                );
+                let hit = if explosion.is_some() {
+                    // Explosive projectiles detonate on the direct hit, which also hits everyone around
+                    detonate_projectile(
+                        &mut commands,
+                        &proj_pos,
+                        &projectile,
+                        *shooter_id,
+                        &player_query,
+                        &map_layout,
+                        &mut players,
+                        tick,
+                    );
+                    ProjectileHit::Nothing
+                } else if shooter_id == player_id {
+                    // Self-hits only despawn the projectile, without scoring
+                    ProjectileHit::Nothing
+                } else {
+                    score_player_hit(&mut commands, &mut players, *shooter_id, *player_id, hit_dir, tick)
+                };
 
                 // Despawn the projectile
-                commands.entity(proj_entity).despawn();
+                despawn_projectile(&mut commands, proj_entity, *proj_id, hit, &players);
 
                 hit_something = true;
                 break; // Projectile can only hit one player
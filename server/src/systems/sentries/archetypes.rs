@@ -0,0 +1,118 @@
+use rand::Rng;
+
+use crate::constants::*;
+use common::protocol::SentryArchetype;
+
+// ============================================================================
+// Archetype Table
+// ============================================================================
+
+// Behavior parameters of a sentry archetype
+#[derive(Debug, Clone, Copy)]
+pub struct SentryArchetypeStats {
+    pub spawn_weight: u32,              // Relative probability of spawning this archetype
+    pub patrol_speed: f32,              // m/s (0.0 = stationary)
+    pub follow_speed: f32,              // m/s (target mode)
+    pub vision_range: f32,              // meters
+    pub vision_half_angle_degrees: f32, // Half of the forward vision cone (180.0 = all around)
+    pub stun_duration: f32,             // seconds a caught player is stunned
+    pub hit_penalty: i32,               // points a caught player loses
+    pub immune_to_regular_shots: bool,  // Only shots of players with sentry hunt power-up affect it
+    pub alert_range: f32,               // meters around its sightings within which other sentries respond
+    pub fire_interval: f32,             // seconds between shots at its target (0.0 = never shoots)
+}
+
+impl SentryArchetypeStats {
+    #[must_use]
+    pub fn is_stationary(&self) -> bool {
+        self.patrol_speed <= 0.0
+    }
+}
+
+const ARCHETYPES: [(SentryArchetype, SentryArchetypeStats); 4] = [
+    (
+        SentryArchetype::Standard,
+        SentryArchetypeStats {
+            spawn_weight: 4,
+            patrol_speed: SENTRY_SPEED,
+            follow_speed: SENTRY_FOLLOW_SPEED,
+            vision_range: SENTRY_VISION_RANGE,
+            vision_half_angle_degrees: SENTRY_VISION_HALF_ANGLE_DEGREES,
+            stun_duration: SENTRY_STUN_DURATION,
+            hit_penalty: SENTRY_HIT_PENALTY,
+            immune_to_regular_shots: false,
+            alert_range: SENTRY_SIGHTING_RESPONSE_RANGE,
+            fire_interval: 0.0,
+        },
+    ),
+    (
+        SentryArchetype::Heavy,
+        SentryArchetypeStats {
+            spawn_weight: 2,
+            patrol_speed: 4.0,
+            follow_speed: 5.5,
+            vision_range: SENTRY_VISION_RANGE,
+            vision_half_angle_degrees: 45.0,
+            stun_duration: 5.0,
+            hit_penalty: 15,
+            immune_to_regular_shots: true,
+            alert_range: SENTRY_SIGHTING_RESPONSE_RANGE,
+            fire_interval: 0.0,
+        },
+    ),
+    (
+        SentryArchetype::Scout,
+        SentryArchetypeStats {
+            spawn_weight: 2,
+            patrol_speed: 9.0,
+            follow_speed: 11.0,
+            vision_range: 40.0,
+            vision_half_angle_degrees: 75.0,
+            stun_duration: 2.0,
+            hit_penalty: 5,
+            immune_to_regular_shots: false,
+            alert_range: 35.0,
+            fire_interval: 0.0,
+        },
+    ),
+    (
+        SentryArchetype::Turret,
+        SentryArchetypeStats {
+            spawn_weight: 1,
+            patrol_speed: 0.0,
+            follow_speed: 0.0,
+            vision_range: 25.0,
+            vision_half_angle_degrees: 180.0,
+            stun_duration: SENTRY_STUN_DURATION,
+            hit_penalty: SENTRY_HIT_PENALTY,
+            immune_to_regular_shots: false,
+            alert_range: SENTRY_SIGHTING_RESPONSE_RANGE,
+            fire_interval: 1.5,
+        },
+    ),
+];
+
+// Look up the behavior parameters of an archetype
+#[must_use]
+pub fn archetype_stats(archetype: SentryArchetype) -> &'static SentryArchetypeStats {
+    ARCHETYPES
+        .iter()
+        .find(|(entry, _)| *entry == archetype)
+        .map(|(_, stats)| stats)
+        .expect("every archetype has an entry in the archetype table")
+}
+
+// Pick a random archetype according to the spawn weights
+pub fn random_archetype(rng: &mut impl Rng) -> SentryArchetype {
+    let total_weight: u32 = ARCHETYPES.iter().map(|(_, stats)| stats.spawn_weight).sum();
+    let mut roll = rng.random_range(0..total_weight);
+
+    for (archetype, stats) in &ARCHETYPES {
+        if roll < stats.spawn_weight {
+            return *archetype;
+        }
+        roll -= stats.spawn_weight;
+    }
+
+    SentryArchetype::Standard
+}
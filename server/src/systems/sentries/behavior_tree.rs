@@ -0,0 +1,180 @@
+// ============================================================================
+// Behavior Tree
+// ============================================================================
+
+// Result of ticking a node
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Status {
+    Success,
+    Failure,
+    Running, // Still busy, try again next tick
+}
+
+// The context a tree is ticked with and how its leaves are called. Contexts may borrow for the duration of a
+// tick, so they are generic over that lifetime and a tree built once can be ticked with contexts of any lifetime.
+pub trait TreeContext {
+    type Context<'a>;
+    type Condition; // Usually `fn(&Context) -> bool`
+    type Action; // Usually `fn(&mut Context) -> Status`
+
+    fn check(condition: &Self::Condition, ctx: &Self::Context<'_>) -> bool;
+    fn act(action: &Self::Action, ctx: &mut Self::Context<'_>) -> Status;
+}
+
+// A node of a behavior tree over the context of `T`. Trees are stateless: they are evaluated from the
+// root every tick, long-lived state (e.g. the sentry mode) lives in the context.
+pub enum Node<T: TreeContext> {
+    Sequence(Vec<Self>), // Ticks children in order as long as they succeed
+    Selector(Vec<Self>), // Ticks children in order until one does not fail
+    Inverter(Box<Self>), // Swaps success and failure of its child
+    Condition(T::Condition),
+    Action(T::Action),
+}
+
+impl<T: TreeContext> Node<T> {
+    pub fn tick(&self, ctx: &mut T::Context<'_>) -> Status {
+        match self {
+            Self::Sequence(children) => children
+                .iter()
+                .map(|child| child.tick(ctx))
+                .find(|status| *status != Status::Success)
+                .unwrap_or(Status::Success),
+            Self::Selector(children) => children
+                .iter()
+                .map(|child| child.tick(ctx))
+                .find(|status| *status != Status::Failure)
+                .unwrap_or(Status::Failure),
+            Self::Inverter(child) => match child.tick(ctx) {
+                Status::Success => Status::Failure,
+                Status::Failure => Status::Success,
+                Status::Running => Status::Running,
+            },
+            Self::Condition(condition) => {
+                if T::check(condition, ctx) {
+                    Status::Success
+                } else {
+                    Status::Failure
+                }
+            }
+            Self::Action(action) => T::act(action, ctx),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Records which leaves were ticked
+    #[derive(Default)]
+    struct Trace(Vec<&'static str>);
+
+    type TraceNode = Node<Trace>;
+
+    impl TreeContext for Trace {
+        type Context<'a> = Self;
+        type Condition = fn(&Self) -> bool;
+        type Action = fn(&mut Self) -> Status;
+
+        fn check(condition: &Self::Condition, trace: &Self) -> bool {
+            condition(trace)
+        }
+
+        fn act(action: &Self::Action, trace: &mut Self) -> Status {
+            action(trace)
+        }
+    }
+
+    fn succeed(trace: &mut Trace) -> Status {
+        trace.0.push("succeed");
+        Status::Success
+    }
+
+    fn fail(trace: &mut Trace) -> Status {
+        trace.0.push("fail");
+        Status::Failure
+    }
+
+    fn run(trace: &mut Trace) -> Status {
+        trace.0.push("run");
+        Status::Running
+    }
+
+    fn tick(node: &TraceNode) -> (Status, Vec<&'static str>) {
+        let mut trace = Trace::default();
+        let status = node.tick(&mut trace);
+        (status, trace.0)
+    }
+
+    #[test]
+    fn sequence_succeeds_when_all_children_succeed() {
+        let node = TraceNode::Sequence(vec![TraceNode::Action(succeed), TraceNode::Action(succeed)]);
+        assert_eq!(tick(&node), (Status::Success, vec!["succeed", "succeed"]));
+    }
+
+    #[test]
+    fn sequence_stops_at_first_failing_or_running_child() {
+        let node = TraceNode::Sequence(vec![
+            TraceNode::Action(succeed),
+            TraceNode::Action(fail),
+            TraceNode::Action(succeed),
+        ]);
+        assert_eq!(tick(&node), (Status::Failure, vec!["succeed", "fail"]));
+
+        let node = TraceNode::Sequence(vec![TraceNode::Action(run), TraceNode::Action(succeed)]);
+        assert_eq!(tick(&node), (Status::Running, vec!["run"]));
+    }
+
+    #[test]
+    fn selector_stops_at_first_succeeding_or_running_child() {
+        let node = TraceNode::Selector(vec![
+            TraceNode::Action(fail),
+            TraceNode::Action(succeed),
+            TraceNode::Action(run),
+        ]);
+        assert_eq!(tick(&node), (Status::Success, vec!["fail", "succeed"]));
+
+        let node = TraceNode::Selector(vec![TraceNode::Action(run), TraceNode::Action(succeed)]);
+        assert_eq!(tick(&node), (Status::Running, vec!["run"]));
+    }
+
+    #[test]
+    fn selector_fails_when_all_children_fail() {
+        let node = TraceNode::Selector(vec![TraceNode::Action(fail), TraceNode::Action(fail)]);
+        assert_eq!(tick(&node), (Status::Failure, vec!["fail", "fail"]));
+    }
+
+    #[test]
+    fn inverter_swaps_success_and_failure() {
+        assert_eq!(
+            tick(&TraceNode::Inverter(Box::new(TraceNode::Action(succeed)))).0,
+            Status::Failure
+        );
+        assert_eq!(
+            tick(&TraceNode::Inverter(Box::new(TraceNode::Action(fail)))).0,
+            Status::Success
+        );
+        assert_eq!(
+            tick(&TraceNode::Inverter(Box::new(TraceNode::Action(run)))).0,
+            Status::Running
+        );
+    }
+
+    #[test]
+    fn condition_maps_to_success_or_failure() {
+        let node = TraceNode::Condition(|trace: &Trace| trace.0.is_empty());
+        assert_eq!(tick(&node).0, Status::Success);
+
+        let node = TraceNode::Sequence(vec![
+            TraceNode::Action(succeed),
+            TraceNode::Condition(|trace: &Trace| trace.0.is_empty()),
+        ]);
+        assert_eq!(tick(&node).0, Status::Failure);
+    }
+
+    #[test]
+    fn action_returns_its_status() {
+        assert_eq!(tick(&TraceNode::Action(succeed)), (Status::Success, vec!["succeed"]));
+        assert_eq!(tick(&TraceNode::Action(run)), (Status::Running, vec!["run"]));
+    }
+}
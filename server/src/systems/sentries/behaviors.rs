@@ -0,0 +1,881 @@
+use rand::rngs::ThreadRng;
+use std::sync::LazyLock;
+
+use super::{
+    archetypes::archetype_stats,
+    behavior_tree::{Node, Status, TreeContext},
+    movement::{patrol_movement, pre_patrol_movement, target_movement, turret_aim},
+    navigation::is_within_sentry_reach,
+    perception::{SentryPerception, has_line_of_sight},
+};
+use crate::{
+    constants::*,
+    resources::{GridConfig, PlayerMap, PlayerSighting, SentryBlackboard, SentryGrid, SentryInfo, SentryMode},
+};
+use common::{power_ups::EffectKind, protocol::*};
+
+// ============================================================================
+// Sentry Context
+// ============================================================================
+
+// Everything the behavior nodes of one sentry read and write during a tick. The sentry's components
+// are copied in and written back by the movement system afterwards, together with the requests.
+pub struct SentryContext<'a> {
+    pub sentry_id: SentryId,
+    pub pos: Position,
+    pub vel: Velocity,
+    pub face_dir: f32,
+    pub perception: SentryPerception,
+    pub info: &'a mut SentryInfo,
+    pub player_data: &'a [(PlayerId, Position, Speed)], // Players that are not stunned
+    pub players: &'a PlayerMap,
+    pub grid_config: &'a GridConfig,
+    pub map_layout: &'a MapLayout,
+    pub sentry_grid: &'a mut SentryGrid,
+    pub blackboard: &'a mut SentryBlackboard,
+    pub rng: &'a mut ThreadRng,
+    pub now: f32,
+    pub delta: f32,
+    pub fire_pitch: Option<f32>, // Shoot in the facing direction at this pitch after the tick
+}
+
+// Behavior trees ticked with a `SentryContext`
+pub struct SentryTree;
+
+// Names the tree type, so leaf functions coerce to its `fn` pointers
+pub type SentryNode = Node<SentryTree>;
+
+impl TreeContext for SentryTree {
+    type Context<'a> = SentryContext<'a>;
+    type Condition = fn(&SentryContext) -> bool;
+    type Action = fn(&mut SentryContext) -> Status;
+
+    fn check(condition: &Self::Condition, ctx: &SentryContext) -> bool {
+        condition(ctx)
+    }
+
+    fn act(action: &Self::Action, ctx: &mut SentryContext) -> Status {
+        action(ctx)
+    }
+}
+
+// ============================================================================
+// Behavior Tree
+// ============================================================================
+
+// Build the sentry behavior tree:
+// - Target mode: keep after the target until the target timer expires or the target becomes invalid
+// - Patrol mode: engage a player once suspicious enough (alerting others) or join other sentries on a
+//   reported player, otherwise patrol
+// - Otherwise (pre-patrol, or right after giving up a target): return to the grid
+//
+// The tree borrows nothing, so it is built once and shared by all sentries.
+pub static SENTRY_BEHAVIOR_TREE: LazyLock<SentryNode> = LazyLock::new(sentry_behavior_tree);
+
+fn sentry_behavior_tree() -> SentryNode {
+    SentryNode::Selector(vec![
+        SentryNode::Sequence(vec![
+            SentryNode::Condition(in_target_mode),
+            SentryNode::Action(keep_target),
+            pursue_tree(),
+        ]),
+        SentryNode::Sequence(vec![
+            SentryNode::Condition(in_patrol_mode),
+            SentryNode::Selector(vec![
+                SentryNode::Sequence(vec![
+                    SentryNode::Action(investigate),
+                    SentryNode::Action(alert_others),
+                    pursue_tree(),
+                ]),
+                SentryNode::Sequence(vec![
+                    SentryNode::Inverter(Box::new(SentryNode::Condition(is_stationary))),
+                    SentryNode::Action(respond_to_sighting),
+                    pursue_tree(),
+                ]),
+                SentryNode::Sequence(vec![
+                    SentryNode::Condition(is_stationary),
+                    SentryNode::Action(hold_position),
+                ]),
+                SentryNode::Action(patrol),
+            ]),
+        ]),
+        SentryNode::Action(return_to_post),
+    ])
+}
+
+// Go after the target player: stationary sentries shoot, others flee from hunters or chase (the lead
+// pursuer directly, the others cutting the player off)
+fn pursue_tree() -> SentryNode {
+    SentryNode::Selector(vec![
+        SentryNode::Sequence(vec![SentryNode::Condition(is_stationary), SentryNode::Action(shoot)]),
+        SentryNode::Sequence(vec![
+            SentryNode::Inverter(Box::new(SentryNode::Condition(is_stationary))),
+            SentryNode::Condition(target_has_sentry_hunt),
+            SentryNode::Action(flee),
+        ]),
+        SentryNode::Action(chase),
+    ])
+}
+
+// ============================================================================
+// Conditions
+// ============================================================================
+
+fn in_target_mode(ctx: &SentryContext) -> bool {
+    ctx.info.mode == SentryMode::Target
+}
+
+fn in_patrol_mode(ctx: &SentryContext) -> bool {
+    ctx.info.mode == SentryMode::Patrol
+}
+
+fn is_stationary(ctx: &SentryContext) -> bool {
+    archetype_stats(ctx.info.archetype).is_stationary()
+}
+
+fn target_has_sentry_hunt(ctx: &SentryContext) -> bool {
+    ctx.info
+        .follow_target
+        .is_some_and(|target_id| has_sentry_hunt(ctx.players, target_id))
+}
+
+fn has_sentry_hunt(players: &PlayerMap, player_id: PlayerId) -> bool {
+    players
+        .0
+        .get(&player_id)
+        .is_some_and(|info| info.has_effect(EffectKind::SentryHunt))
+}
+
+// Cloaked players are invisible to sentries
+fn is_cloaked(players: &PlayerMap, player_id: PlayerId) -> bool {
+    players
+        .0
+        .get(&player_id)
+        .is_some_and(|info| info.has_effect(EffectKind::Cloak))
+}
+
+// ============================================================================
+// Actions
+// ============================================================================
+
+// Count down the target timer (paused while fleeing) and give up the target once the timer expires or
+// the target disconnects, gets stunned, cloaks or climbs out of reach (onto an upper deck). Fails after
+// giving up, starting the cooldown.
+fn keep_target(ctx: &mut SentryContext) -> Status {
+    if target_has_sentry_hunt(ctx) {
+        // If a sentry was attacking and is now fleeing, the timer has been decremented previously, so
+        // we reset it every time we are fleeing
+        ctx.info.mode_timer = SENTRY_TARGET_DURATION;
+    } else {
+        ctx.info.mode_timer -= ctx.delta;
+    }
+
+    let target_valid = ctx.info.follow_target.is_none_or(|target_id| {
+        let target_ready = ctx
+            .players
+            .0
+            .get(&target_id)
+            .is_some_and(|info| info.logged_in && info.stun_timer <= 0.0);
+        let target_out_of_reach = ctx
+            .player_data
+            .iter()
+            .find(|(id, _, _)| *id == target_id)
+            .is_some_and(|(_, pos, _)| !is_within_sentry_reach(pos.y));
+        target_ready && !target_out_of_reach && !is_cloaked(ctx.players, target_id)
+    });
+
+    if ctx.info.mode_timer > 0.0 && target_valid {
+        // Keep the other sentries informed while the target is in sight
+        if let Some((target_id, target_pos)) = visible_target(ctx) {
+            report_sighting(ctx, target_id, target_pos);
+        }
+        return Status::Success;
+    }
+
+    // Switch to pre-patrol with cooldown
+    ctx.info.mode = SentryMode::PrePatrol;
+    ctx.info.mode_timer = ctx.info.cooldown_duration;
+    ctx.info.follow_target = None;
+    Status::Failure
+}
+
+// Watch and listen for players. Succeeds when the sentry engages a suspect: right away if the suspect
+// has sentry hunt power-up (flee), otherwise once the patrol cooldown has expired and not too many
+// sentries attack the suspect already.
+fn investigate(ctx: &mut SentryContext) -> Status {
+    ctx.info.mode_timer -= ctx.delta;
+
+    let Some(target_id) = ctx.perception.update(
+        archetype_stats(ctx.info.archetype),
+        &ctx.pos,
+        ctx.face_dir,
+        ctx.player_data,
+        ctx.players,
+        ctx.map_layout,
+        ctx.now,
+        ctx.delta,
+    ) else {
+        return Status::Failure;
+    };
+
+    if !has_sentry_hunt(ctx.players, target_id)
+        && (ctx.info.mode_timer > 0.0 || ctx.blackboard.pursuer_count(target_id) >= SENTRY_MAX_PURSUERS)
+    {
+        return Status::Failure;
+    }
+
+    engage(ctx, target_id);
+    Status::Success
+}
+
+// Join the pursuit of a player another sentry reported nearby, once the patrol cooldown has expired.
+// Prefers the most recent sighting and skips players that enough sentries attack already.
+fn respond_to_sighting(ctx: &mut SentryContext) -> Status {
+    if ctx.info.mode_timer > 0.0 {
+        return Status::Failure;
+    }
+
+    let target_id = ctx
+        .blackboard
+        .sightings
+        .iter()
+        .filter(|(player_id, sighting)| {
+            sighting.reported_by != ctx.sentry_id
+                && (sighting.pos.x - ctx.pos.x).hypot(sighting.pos.z - ctx.pos.z) <= sighting.range
+                && ctx.blackboard.pursuer_count(**player_id) < SENTRY_MAX_PURSUERS
+                && !has_sentry_hunt(ctx.players, **player_id)
+                && ctx
+                    .player_data
+                    .iter()
+                    .any(|(id, pos, _)| id == *player_id && is_within_sentry_reach(pos.y))
+        })
+        .max_by(|a, b| a.1.time.total_cmp(&b.1.time))
+        .map(|(player_id, _)| *player_id);
+
+    let Some(target_id) = target_id else {
+        return Status::Failure;
+    };
+
+    engage(ctx, target_id);
+    Status::Success
+}
+
+// Switch from patrol to target mode on the given player
+fn engage(ctx: &mut SentryContext, target_id: PlayerId) {
+    ctx.info.mode = SentryMode::Target;
+    ctx.info.mode_timer = SENTRY_TARGET_DURATION;
+    ctx.info.follow_target = Some(target_id);
+    ctx.info.path = None;
+    ctx.perception.reset();
+
+    // Remove from field map when leaving patrol mode
+    // Must clear both current AND destination cells (sentry occupies two cells while patrolling)
+    ctx.sentry_grid.clear_patrol_cells(&ctx.pos, &ctx.vel, ctx.sentry_id);
+    ctx.blackboard.add_pursuer(target_id, ctx.sentry_id);
+}
+
+// Report the target's position so nearby patrolling sentries can join
+fn alert_others(ctx: &mut SentryContext) -> Status {
+    let target = ctx.info.follow_target.and_then(|target_id| {
+        ctx.player_data
+            .iter()
+            .find(|(id, _, _)| *id == target_id)
+            .map(|(_, pos, _)| (target_id, *pos))
+    });
+    if let Some((target_id, target_pos)) = target {
+        report_sighting(ctx, target_id, target_pos);
+    }
+    Status::Success
+}
+
+// The target player and its position, if the sentry can see it
+fn visible_target(ctx: &SentryContext) -> Option<(PlayerId, Position)> {
+    let target_id = ctx
+        .info
+        .follow_target
+        .filter(|target_id| !is_cloaked(ctx.players, *target_id))?;
+    let (_, target_pos, _) = ctx.player_data.iter().find(|(id, _, _)| *id == target_id)?;
+    let in_range =
+        (target_pos.x - ctx.pos.x).hypot(target_pos.z - ctx.pos.z) <= archetype_stats(ctx.info.archetype).vision_range;
+    (in_range && has_line_of_sight(&ctx.pos, target_pos, ctx.map_layout)).then_some((target_id, *target_pos))
+}
+
+fn report_sighting(ctx: &mut SentryContext, player_id: PlayerId, pos: Position) {
+    ctx.blackboard.report_sighting(
+        player_id,
+        PlayerSighting {
+            pos,
+            time: ctx.now,
+            reported_by: ctx.sentry_id,
+            range: archetype_stats(ctx.info.archetype).alert_range,
+        },
+    );
+}
+
+// Walk along the grid lines
+fn patrol(ctx: &mut SentryContext) -> Status {
+    patrol_movement(
+        &ctx.sentry_id,
+        &mut ctx.pos,
+        &mut ctx.vel,
+        &mut ctx.face_dir,
+        ctx.info,
+        ctx.grid_config,
+        ctx.map_layout,
+        ctx.players,
+        ctx.sentry_grid,
+        ctx.blackboard,
+        ctx.delta,
+        ctx.rng,
+    );
+    Status::Running
+}
+
+// Keep watching from the current cell
+const fn hold_position(_ctx: &mut SentryContext) -> Status {
+    Status::Running
+}
+
+// Walk back to the center of the current cell, then resume patrolling
+fn return_to_post(ctx: &mut SentryContext) -> Status {
+    pre_patrol_movement(
+        &ctx.sentry_id,
+        &mut ctx.pos,
+        &mut ctx.vel,
+        &mut ctx.face_dir,
+        ctx.info,
+        ctx.map_layout,
+        ctx.players,
+        ctx.sentry_grid,
+        ctx.delta,
+    );
+    Status::Running
+}
+
+// Turn toward the target and request a shot whenever the fire interval has passed
+fn shoot(ctx: &mut SentryContext) -> Status {
+    let Some(target_id) = ctx.info.follow_target else {
+        return Status::Failure;
+    };
+
+    ctx.fire_pitch = turret_aim(
+        &ctx.pos,
+        &mut ctx.face_dir,
+        ctx.info,
+        target_id,
+        ctx.player_data,
+        ctx.delta,
+    );
+    Status::Running
+}
+
+fn chase(ctx: &mut SentryContext) -> Status {
+    let lead = ctx
+        .info
+        .follow_target
+        .filter(|target_id| !ctx.blackboard.is_lead_pursuer(*target_id, ctx.sentry_id))
+        .map_or(0.0, |_| SENTRY_INTERCEPT_LOOKAHEAD);
+    move_relative_to_target(ctx, false, lead)
+}
+
+fn flee(ctx: &mut SentryContext) -> Status {
+    move_relative_to_target(ctx, true, 0.0)
+}
+
+fn move_relative_to_target(ctx: &mut SentryContext, fleeing: bool, lead: f32) -> Status {
+    let Some(target_id) = ctx.info.follow_target else {
+        return Status::Failure;
+    };
+
+    target_movement(
+        &ctx.sentry_id,
+        &mut ctx.pos,
+        &mut ctx.vel,
+        ctx.info,
+        target_id,
+        fleeing,
+        lead,
+        ctx.player_data,
+        ctx.grid_config,
+        ctx.sentry_grid,
+        ctx.blackboard,
+        ctx.map_layout,
+        ctx.players,
+        ctx.delta,
+    );
+    Status::Running
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::prelude::*;
+    use std::collections::HashSet;
+    use tokio::sync::mpsc::unbounded_channel;
+
+    use super::*;
+    use crate::{
+        map::cell_center,
+        resources::{GridCell, PlayerInfo},
+    };
+    use common::{constants::*, map::level_height, power_ups::PowerUpId, stats::PlayerStats, weapons::WeaponId};
+
+    const SENTRY: SentryId = SentryId(1);
+    const PLAYER: PlayerId = PlayerId(1);
+
+    // Owns everything a `SentryContext` borrows: an open grid without walls, one sentry standing on
+    // the center of cell (5, 5) and facing +Z, and one walking player in the next cell in front of it.
+    struct Fixture {
+        info: SentryInfo,
+        pos: Position,
+        face_dir: f32,
+        perception: SentryPerception,
+        player_data: Vec<(PlayerId, Position, Speed)>,
+        players: PlayerMap,
+        grid_config: GridConfig,
+        map_layout: MapLayout,
+        sentry_grid: SentryGrid,
+        blackboard: SentryBlackboard,
+        rng: ThreadRng,
+        delta: f32,
+    }
+
+    impl Fixture {
+        fn new(archetype: SentryArchetype, mode: SentryMode) -> Self {
+            let pos = cell_center(5, 5);
+            let mut sentry_grid = SentryGrid(vec![vec![None; GRID_COLS as usize]; GRID_ROWS as usize]);
+            sentry_grid.0[5][5] = Some(SENTRY);
+
+            let player = PlayerInfo {
+                entity: Entity::PLACEHOLDER,
+                logged_in: true,
+                spectator: false,
+                channel: unbounded_channel().0,
+                hits: 0,
+                name: String::new(),
+                effects: Vec::new(),
+                stun_timer: 0.0,
+                last_shot_time: f32::NEG_INFINITY,
+                weapon_shot_times: [f32::NEG_INFINITY; WeaponId::ALL.len()],
+                jump_requested: false,
+                revealed_to: HashSet::new(),
+                stats: PlayerStats::default(),
+                resume_token: String::new(),
+            };
+
+            Self {
+                info: SentryInfo {
+                    entity: Entity::PLACEHOLDER,
+                    mode,
+                    mode_timer: 0.0,
+                    follow_target: (mode == SentryMode::Target).then_some(PLAYER),
+                    at_intersection: true,
+                    path: None,
+                    archetype,
+                    fire_timer: 0.0,
+                    follow_speed_scale: 1.0,
+                    cooldown_duration: SENTRY_COOLDOWN_DURATION,
+                },
+                pos,
+                face_dir: 0.0,
+                perception: SentryPerception::default(),
+                player_data: vec![(
+                    PLAYER,
+                    Position {
+                        z: pos.z + GRID_SIZE,
+                        ..pos
+                    },
+                    Speed {
+                        speed_level: SpeedLevel::Walk,
+                        move_dir: 0.0,
+                    },
+                )],
+                players: PlayerMap([(PLAYER, player)].into()),
+                grid_config: GridConfig {
+                    grid: vec![vec![GridCell::default(); GRID_COLS as usize]; GRID_ROWS as usize],
+                },
+                map_layout: MapLayout {
+                    boundary_walls: Vec::new(),
+                    interior_walls: Vec::new(),
+                    lower_walls: Vec::new(),
+                    upper_walls: Vec::new(),
+                    roofs: Vec::new(),
+                    ramps: Vec::new(),
+                    wall_lights: Vec::new(),
+                    destructible_walls: Vec::new(),
+                    jump_pads: Vec::new(),
+                },
+                sentry_grid,
+                blackboard: SentryBlackboard::default(),
+                rng: rand::rng(),
+                delta: 0.1,
+            }
+        }
+
+        fn player_pos(&self) -> Position {
+            self.player_data[0].1
+        }
+
+        fn add_other_pursuers(&mut self, count: u32) {
+            for id in 0..count {
+                self.blackboard.add_pursuer(PLAYER, SentryId(100 + id));
+            }
+        }
+
+        fn report_player_by_other_sentry(&mut self) {
+            let pos = self.player_pos();
+            self.blackboard.report_sighting(
+                PLAYER,
+                PlayerSighting {
+                    pos,
+                    time: 99.0,
+                    reported_by: SentryId(2),
+                    range: SENTRY_SIGHTING_RESPONSE_RANGE,
+                },
+            );
+        }
+
+        fn give_player_power_up(&mut self, effect: EffectKind) {
+            let power_up = PowerUpId::all()
+                .find(|power_up| power_up.def().effect == effect)
+                .expect("power-up with the effect");
+            self.players
+                .0
+                .get_mut(&PLAYER)
+                .expect("player exists")
+                .grant_power_up(power_up);
+        }
+
+        fn ctx(&mut self) -> SentryContext<'_> {
+            SentryContext {
+                sentry_id: SENTRY,
+                pos: self.pos,
+                vel: Velocity::default(),
+                face_dir: self.face_dir,
+                perception: self.perception,
+                info: &mut self.info,
+                player_data: &self.player_data,
+                players: &self.players,
+                grid_config: &self.grid_config,
+                map_layout: &self.map_layout,
+                sentry_grid: &mut self.sentry_grid,
+                blackboard: &mut self.blackboard,
+                rng: &mut self.rng,
+                now: 100.0,
+                delta: self.delta,
+                fire_pitch: None,
+            }
+        }
+
+        // Put a ramp east of the sentry, from the ground at cell (6, 5) up to a roof on cells (8, 5)
+        // and (8, 6), and move the player onto the roof at (8, 6)
+        fn add_ramp_to_roof(&mut self) {
+            let grid = &mut self.grid_config.grid;
+            grid[5][6].has_ramp = true;
+            grid[5][6].ramp_base_west = true;
+            grid[5][7].has_ramp = true;
+            grid[5][7].ramp_top_east = true;
+            grid[5][8].has_roof = true;
+            grid[6][8].has_roof = true;
+
+            self.player_data[0].1 = Position {
+                y: level_height(1),
+                ..cell_center(8, 6)
+            };
+        }
+    }
+
+    fn distance(a: &Position, b: &Position) -> f32 {
+        (a.x - b.x).hypot(a.z - b.z)
+    }
+
+    #[test]
+    fn mode_conditions_match_the_sentry_mode() {
+        let mut fixture = Fixture::new(SentryArchetype::Standard, SentryMode::Patrol);
+        let ctx = fixture.ctx();
+        assert!(in_patrol_mode(&ctx));
+        assert!(!in_target_mode(&ctx));
+
+        let mut fixture = Fixture::new(SentryArchetype::Standard, SentryMode::Target);
+        let ctx = fixture.ctx();
+        assert!(in_target_mode(&ctx));
+        assert!(!in_patrol_mode(&ctx));
+    }
+
+    #[test]
+    fn is_stationary_only_for_turrets() {
+        assert!(is_stationary(
+            &Fixture::new(SentryArchetype::Turret, SentryMode::Patrol).ctx()
+        ));
+        assert!(!is_stationary(
+            &Fixture::new(SentryArchetype::Scout, SentryMode::Patrol).ctx()
+        ));
+    }
+
+    #[test]
+    fn target_has_sentry_hunt_follows_the_power_up() {
+        let mut fixture = Fixture::new(SentryArchetype::Standard, SentryMode::Target);
+        assert_eq!(target_has_sentry_hunt(&fixture.ctx()), ALWAYS_SENTRY_HUNT);
+
+        fixture.give_player_power_up(EffectKind::SentryHunt);
+        assert!(target_has_sentry_hunt(&fixture.ctx()));
+    }
+
+    #[test]
+    fn keep_target_counts_down_and_gives_up() {
+        let mut fixture = Fixture::new(SentryArchetype::Standard, SentryMode::Target);
+        fixture.info.mode_timer = 1.0;
+        assert_eq!(keep_target(&mut fixture.ctx()), Status::Success);
+        assert!(fixture.info.mode_timer < 1.0);
+
+        fixture.info.mode_timer = 0.05;
+        assert_eq!(keep_target(&mut fixture.ctx()), Status::Failure);
+        assert_eq!(fixture.info.mode, SentryMode::PrePatrol);
+        assert_eq!(fixture.info.follow_target, None);
+    }
+
+    #[test]
+    fn keep_target_gives_up_on_stunned_target() {
+        let mut fixture = Fixture::new(SentryArchetype::Standard, SentryMode::Target);
+        fixture.info.mode_timer = SENTRY_TARGET_DURATION;
+        fixture.players.0.get_mut(&PLAYER).expect("player exists").stun_timer = 1.0;
+        assert_eq!(keep_target(&mut fixture.ctx()), Status::Failure);
+        assert_eq!(fixture.info.mode, SentryMode::PrePatrol);
+    }
+
+    #[test]
+    fn keep_target_follows_targets_onto_roofs_but_not_upper_decks() {
+        let mut fixture = Fixture::new(SentryArchetype::Standard, SentryMode::Target);
+        fixture.info.mode_timer = SENTRY_TARGET_DURATION;
+        fixture.player_data[0].1.y = level_height(1);
+        assert_eq!(keep_target(&mut fixture.ctx()), Status::Success);
+
+        fixture.player_data[0].1.y = level_height(2);
+        assert_eq!(keep_target(&mut fixture.ctx()), Status::Failure);
+        assert_eq!(fixture.info.mode, SentryMode::PrePatrol);
+    }
+
+    #[test]
+    fn keep_target_gives_up_on_cloaked_target() {
+        let mut fixture = Fixture::new(SentryArchetype::Standard, SentryMode::Target);
+        fixture.info.mode_timer = SENTRY_TARGET_DURATION;
+        fixture.give_player_power_up(EffectKind::Cloak);
+        assert_eq!(keep_target(&mut fixture.ctx()), Status::Failure);
+        assert_eq!(fixture.info.follow_target, None);
+    }
+
+    #[test]
+    fn investigate_engages_a_visible_player() {
+        let mut fixture = Fixture::new(SentryArchetype::Standard, SentryMode::Patrol);
+        fixture.delta = 1.0;
+        assert_eq!(investigate(&mut fixture.ctx()), Status::Success);
+        assert_eq!(fixture.info.mode, SentryMode::Target);
+        assert_eq!(fixture.info.follow_target, Some(PLAYER));
+    }
+
+    #[test]
+    fn investigate_ignores_players_behind_during_cooldown() {
+        let mut fixture = Fixture::new(SentryArchetype::Standard, SentryMode::Patrol);
+        fixture.face_dir = std::f32::consts::PI;
+        assert_eq!(investigate(&mut fixture.ctx()), Status::Failure);
+
+        let mut fixture = Fixture::new(SentryArchetype::Standard, SentryMode::Patrol);
+        fixture.delta = 1.0;
+        fixture.info.mode_timer = SENTRY_COOLDOWN_DURATION;
+        assert_eq!(investigate(&mut fixture.ctx()), Status::Failure);
+        assert_eq!(fixture.info.mode, SentryMode::Patrol);
+    }
+
+    #[test]
+    fn investigate_only_hears_cloaked_players() {
+        let mut fixture = Fixture::new(SentryArchetype::Standard, SentryMode::Patrol);
+        fixture.delta = 1.0;
+        fixture.give_player_power_up(EffectKind::Cloak);
+        assert_eq!(investigate(&mut fixture.ctx()), Status::Failure);
+
+        // A gunshot gives the player away
+        fixture
+            .players
+            .0
+            .get_mut(&PLAYER)
+            .expect("player exists")
+            .last_shot_time = 100.0;
+        assert_eq!(investigate(&mut fixture.ctx()), Status::Success);
+        assert_eq!(fixture.info.follow_target, Some(PLAYER));
+    }
+
+    #[test]
+    fn investigate_respects_the_pursuer_limit() {
+        let mut fixture = Fixture::new(SentryArchetype::Standard, SentryMode::Patrol);
+        fixture.delta = 1.0;
+        fixture.add_other_pursuers(SENTRY_MAX_PURSUERS as u32);
+        assert_eq!(investigate(&mut fixture.ctx()), Status::Failure);
+        assert_eq!(fixture.info.mode, SentryMode::Patrol);
+    }
+
+    #[test]
+    fn alert_others_reports_the_target_with_the_archetype_range() {
+        let mut fixture = Fixture::new(SentryArchetype::Scout, SentryMode::Target);
+        assert_eq!(alert_others(&mut fixture.ctx()), Status::Success);
+
+        let sighting = fixture.blackboard.sightings[&PLAYER];
+        assert_eq!(sighting.reported_by, SENTRY);
+        assert!(distance(&sighting.pos, &fixture.player_pos()) < f32::EPSILON);
+        assert!(sighting.range > SENTRY_SIGHTING_RESPONSE_RANGE);
+    }
+
+    #[test]
+    fn respond_to_sighting_joins_a_nearby_pursuit() {
+        let mut fixture = Fixture::new(SentryArchetype::Standard, SentryMode::Patrol);
+        fixture.report_player_by_other_sentry();
+        assert_eq!(respond_to_sighting(&mut fixture.ctx()), Status::Success);
+        assert_eq!(fixture.info.mode, SentryMode::Target);
+        assert_eq!(fixture.info.follow_target, Some(PLAYER));
+        assert_eq!(fixture.blackboard.pursuer_count(PLAYER), 1);
+    }
+
+    #[test]
+    fn respond_to_sighting_ignores_crowded_or_far_sightings() {
+        let mut fixture = Fixture::new(SentryArchetype::Standard, SentryMode::Patrol);
+        fixture.report_player_by_other_sentry();
+        fixture.add_other_pursuers(SENTRY_MAX_PURSUERS as u32);
+        assert_eq!(respond_to_sighting(&mut fixture.ctx()), Status::Failure);
+
+        let mut fixture = Fixture::new(SentryArchetype::Standard, SentryMode::Patrol);
+        fixture.report_player_by_other_sentry();
+        fixture.pos = cell_center(0, 0);
+        assert_eq!(respond_to_sighting(&mut fixture.ctx()), Status::Failure);
+        assert_eq!(fixture.info.mode, SentryMode::Patrol);
+    }
+
+    #[test]
+    fn patrol_walks_along_the_grid() {
+        let mut fixture = Fixture::new(SentryArchetype::Standard, SentryMode::Patrol);
+        let start = fixture.pos;
+        let mut ctx = fixture.ctx();
+        ctx.vel = Velocity {
+            x: SENTRY_SPEED,
+            y: 0.0,
+            z: 0.0,
+        };
+        assert_eq!(patrol(&mut ctx), Status::Running);
+        assert!(ctx.pos.x > start.x);
+        assert!((ctx.pos.z - start.z).abs() < f32::EPSILON);
+    }
+
+    #[test]
+    fn hold_position_keeps_the_sentry_in_place() {
+        let mut fixture = Fixture::new(SentryArchetype::Turret, SentryMode::Patrol);
+        let start = fixture.pos;
+        let mut ctx = fixture.ctx();
+        assert_eq!(hold_position(&mut ctx), Status::Running);
+        assert!(distance(&ctx.pos, &start) < f32::EPSILON);
+    }
+
+    #[test]
+    fn return_to_post_resumes_patrol_at_the_cell_center() {
+        let mut fixture = Fixture::new(SentryArchetype::Standard, SentryMode::PrePatrol);
+        fixture.pos.x += 1.0;
+        let mut ctx = fixture.ctx();
+        assert_eq!(return_to_post(&mut ctx), Status::Running);
+        assert_eq!(ctx.info.mode, SentryMode::PrePatrol);
+
+        fixture.pos = cell_center(5, 5);
+        assert_eq!(return_to_post(&mut fixture.ctx()), Status::Running);
+        assert_eq!(fixture.info.mode, SentryMode::Patrol);
+    }
+
+    #[test]
+    fn shoot_fires_at_the_fire_interval() {
+        let mut fixture = Fixture::new(SentryArchetype::Turret, SentryMode::Target);
+        let mut ctx = fixture.ctx();
+        assert_eq!(shoot(&mut ctx), Status::Running);
+        assert!(ctx.fire_pitch.is_some());
+
+        let mut ctx = fixture.ctx();
+        assert_eq!(shoot(&mut ctx), Status::Running);
+        assert!(ctx.fire_pitch.is_none());
+    }
+
+    #[test]
+    fn shoot_aims_up_at_targets_on_roofs() {
+        let mut fixture = Fixture::new(SentryArchetype::Turret, SentryMode::Target);
+        fixture.player_data[0].1.y = level_height(1);
+        let mut ctx = fixture.ctx();
+        assert_eq!(shoot(&mut ctx), Status::Running);
+        assert!(ctx.fire_pitch.is_some_and(|pitch| pitch > 0.0));
+    }
+
+    #[test]
+    fn chase_moves_toward_the_target() {
+        let mut fixture = Fixture::new(SentryArchetype::Standard, SentryMode::Target);
+        let before = distance(&fixture.pos, &fixture.player_pos());
+        let player_pos = fixture.player_pos();
+        let mut ctx = fixture.ctx();
+        assert_eq!(chase(&mut ctx), Status::Running);
+        assert!(distance(&ctx.pos, &player_pos) < before);
+    }
+
+    #[test]
+    fn chase_cuts_off_when_not_the_lead_pursuer() {
+        let mut fixture = Fixture::new(SentryArchetype::Standard, SentryMode::Target);
+        let lead = SentryId(0);
+        fixture.blackboard.add_pursuer(PLAYER, lead);
+        fixture.blackboard.add_pursuer(PLAYER, SENTRY);
+        assert_eq!(chase(&mut fixture.ctx()), Status::Running);
+
+        // The player walks along +Z, so the path leads ahead of it
+        let path = fixture.info.path.as_ref().expect("path to the player");
+        assert!(path.target_cell.1 > 6);
+        let (grid_x, grid_z, _) = path.waypoints[0];
+        assert!(fixture.blackboard.is_reserved_by_other((grid_x, grid_z), lead));
+    }
+
+    #[test]
+    fn flee_moves_away_from_the_target() {
+        let mut fixture = Fixture::new(SentryArchetype::Standard, SentryMode::Target);
+        fixture.give_player_power_up(EffectKind::SentryHunt);
+        let before = distance(&fixture.pos, &fixture.player_pos());
+        let player_pos = fixture.player_pos();
+        let mut ctx = fixture.ctx();
+        assert_eq!(flee(&mut ctx), Status::Running);
+        assert!(distance(&ctx.pos, &player_pos) > before);
+    }
+
+    #[test]
+    fn tree_chases_right_after_engaging() {
+        let mut fixture = Fixture::new(SentryArchetype::Standard, SentryMode::Patrol);
+        fixture.delta = 1.0;
+        let before = distance(&fixture.pos, &fixture.player_pos());
+        let player_pos = fixture.player_pos();
+        let mut ctx = fixture.ctx();
+        assert_eq!(SENTRY_BEHAVIOR_TREE.tick(&mut ctx), Status::Running);
+        assert_eq!(ctx.info.mode, SentryMode::Target);
+        assert!(distance(&ctx.pos, &player_pos) < before);
+    }
+
+    #[test]
+    fn chase_plans_up_the_ramp_onto_the_roof() {
+        let mut fixture = Fixture::new(SentryArchetype::Standard, SentryMode::Target);
+        fixture.add_ramp_to_roof();
+        assert_eq!(chase(&mut fixture.ctx()), Status::Running);
+
+        let path = fixture.info.path.as_ref().expect("path to the player");
+        assert_eq!(
+            Vec::from(path.waypoints.clone()),
+            vec![(6, 5, 0), (7, 5, 0), (8, 5, 1), (8, 6, 1)]
+        );
+    }
+
+    #[test]
+    fn chase_enters_ramps_only_at_their_low_end() {
+        let mut fixture = Fixture::new(SentryArchetype::Standard, SentryMode::Target);
+        fixture.add_ramp_to_roof();
+        fixture.pos = cell_center(7, 4);
+        assert_eq!(chase(&mut fixture.ctx()), Status::Running);
+
+        // Around the ramp to its base instead of stepping onto its side
+        let path = fixture.info.path.as_ref().expect("path to the player");
+        assert!(path.waypoints.iter().take(2).all(|&(_, grid_z, _)| grid_z == 4));
+        assert!(path.waypoints.contains(&(5, 5, 0)));
+    }
+}
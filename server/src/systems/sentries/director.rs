@@ -0,0 +1,135 @@
+use bevy::prelude::*;
+
+use super::spawn::spawn_sentry;
+use crate::{
+    constants::*,
+    resources::{GridConfig, PlayerMap, SentryDirector, SentryGrid, SentryMap, SentryMode, SentrySpawnConfig},
+    systems::network::broadcast_to_all,
+};
+use common::{markers::PlayerMarker, protocol::*};
+
+// ============================================================================
+// Sentry Director System
+// ============================================================================
+
+// Adapt the sentries to the players at regular intervals:
+// - Add or remove one sentry at a time toward the number for the connected players
+// - Tune follow speed and cooldown of all sentries to the leader's lead and the recent stun rate
+pub fn sentries_director_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    spawn_config: Res<SentrySpawnConfig>,
+    grid_config: Res<GridConfig>,
+    players: Res<PlayerMap>,
+    mut director: ResMut<SentryDirector>,
+    mut sentries: ResMut<SentryMap>,
+    mut sentry_grid: ResMut<SentryGrid>,
+    player_query: Query<&Position, With<PlayerMarker>>,
+) {
+    director.timer -= time.delta_secs();
+    if director.timer > 0.0 {
+        return;
+    }
+    director.timer = SENTRY_DIRECTOR_INTERVAL;
+
+    let now = time.elapsed_secs();
+    director
+        .recent_stuns
+        .retain(|stun_time| now - stun_time < SENTRY_DIRECTOR_STUN_WINDOW);
+
+    let scores: Vec<i32> = players
+        .0
+        .values()
+        .filter(|info| info.is_playing())
+        .map(|info| info.hits)
+        .collect();
+
+    // Tune the sentries
+    let difficulty = sentry_difficulty(&scores, director.recent_stuns.len());
+    if (difficulty - director.difficulty).abs() > f32::EPSILON {
+        debug!("sentry difficulty changed to {difficulty:.2}");
+    }
+    director.difficulty = difficulty;
+    for sentry_info in sentries.0.values_mut() {
+        sentry_info.follow_speed_scale = difficulty;
+        sentry_info.cooldown_duration = SENTRY_COOLDOWN_DURATION / difficulty;
+    }
+
+    // Adjust the number of sentries
+    let desired_count = desired_sentry_count(spawn_config.num_sentries, scores.len());
+    if sentries.0.len() < desired_count {
+        let sentry_id = SentryId(sentries.0.keys().map(|id| id.0 + 1).max().unwrap_or(0));
+        let player_positions: Vec<Position> = player_query.iter().copied().collect();
+        let Some(sentry) = spawn_sentry(
+            &mut commands,
+            &mut sentries,
+            &mut sentry_grid,
+            &grid_config,
+            sentry_id,
+            &player_positions,
+            &mut rand::rng(),
+        ) else {
+            return;
+        };
+
+        if let Some(sentry_info) = sentries.0.get_mut(&sentry_id) {
+            sentry_info.follow_speed_scale = difficulty;
+            sentry_info.cooldown_duration = SENTRY_COOLDOWN_DURATION / difficulty;
+        }
+
+        debug!("director spawned {sentry_id:?} ({:?})", sentry.archetype);
+        broadcast_to_all(&players, ServerMessage::Sentry(SSentry { id: sentry_id, sentry }));
+    } else if sentries.0.len() > desired_count {
+        // Only remove sentries that are not busy with a player, the newest first
+        let Some(sentry_id) = sentries
+            .0
+            .iter()
+            .filter(|(_, info)| info.mode != SentryMode::Target)
+            .map(|(id, _)| *id)
+            .max_by_key(|id| id.0)
+        else {
+            return;
+        };
+
+        if let Some(sentry_info) = sentries.0.remove(&sentry_id) {
+            commands.entity(sentry_info.entity).despawn();
+        }
+        sentry_grid.remove_sentry(sentry_id);
+
+        debug!("director removed {sentry_id:?}");
+        broadcast_to_all(&players, ServerMessage::SentryDespawn(SSentryDespawn { id: sentry_id }));
+    }
+}
+
+// ============================================================================
+// Helper Functions
+// ============================================================================
+
+// Number of sentries for the number of connected players. Starts at the configured number and never
+// adds sentries beyond the limit (but keeps a configured number above it).
+#[must_use]
+fn desired_sentry_count(num_sentries: u32, player_count: usize) -> usize {
+    let base = num_sentries as usize;
+    let extra = player_count.saturating_sub(1) * SENTRY_DIRECTOR_SENTRIES_PER_PLAYER;
+    (base + extra).min(SENTRY_DIRECTOR_MAX_SENTRIES.max(base))
+}
+
+// Difficulty factor for the sentries: higher the further the leader is ahead of the runner-up, lower
+// the more often players got stunned recently.
+#[must_use]
+fn sentry_difficulty(scores: &[i32], recent_stuns: usize) -> f32 {
+    let mut sorted = scores.to_vec();
+    sorted.sort_unstable_by(|a, b| b.cmp(a));
+    let lead = match sorted.as_slice() {
+        [leader, runner_up, ..] => leader - runner_up,
+        _ => 0,
+    };
+
+    let stuns_per_player = recent_stuns as f32 / scores.len().max(1) as f32;
+    stuns_per_player
+        .mul_add(
+            -SENTRY_DIRECTOR_STUN_RELIEF,
+            (lead as f32).mul_add(SENTRY_DIRECTOR_LEAD_DIFFICULTY, 1.0),
+        )
+        .clamp(SENTRY_DIRECTOR_MIN_DIFFICULTY, SENTRY_DIRECTOR_MAX_DIFFICULTY)
+}
@@ -1,7 +1,14 @@
+pub mod archetypes;
+pub mod behavior_tree;
+pub mod behaviors;
+pub mod director;
 pub mod movement;
 pub mod navigation;
+pub mod pathfinding;
+pub mod perception;
 pub mod spawn;
 pub mod systems;
 
+pub use director::sentries_director_system;
 pub use spawn::sentries_spawn_system;
 pub use systems::{sentries_movement_system, sentry_player_collision_system};
@@ -1,66 +1,32 @@
-use super::navigation::{GridDirection, ahead_directions, direction_from_velocity, pick_direction, valid_directions};
+use bevy::prelude::*;
+
+use super::{
+    archetypes::archetype_stats,
+    navigation::{
+        GridDirection, ahead_directions, direction_from_velocity, is_within_sentry_reach, nav_level, pick_direction,
+        valid_directions,
+    },
+    pathfinding::{NavCell, find_flee_cell, find_path, nav_cell_at},
+};
 use crate::{
     constants::*,
     map::cell_center,
-    resources::{GridConfig, PlayerMap, SentryGrid, SentryInfo, SentryMode},
-    systems::network::broadcast_to_all,
+    resources::{
+        GridConfig, NextProjectileId, PlayerMap, SentryBlackboard, SentryGrid, SentryInfo, SentryMode, SentryPath,
+    },
+    systems::{network::broadcast_to_all, projectiles::spawn_projectiles},
 };
 use common::{
-    collision::{slide_sentry_along_obstacles, sweep_player_vs_wall},
+    collision::slide_sentry_along_obstacles,
     constants::*,
+    map::{floor_height_at, walls_at_height},
     protocol::*,
+    spawning::calculate_projectile_spawns,
+    weapons::WeaponId,
 };
 
-// ============================================================================
-// AI Helper Functions
-// ============================================================================
-
-// Find the first moving player visible from sentry's position using line-of-sight check
-#[must_use]
-pub fn find_visible_moving_player(
-    sentry_pos: &Position,
-    player_data: &[(PlayerId, Position, Speed)],
-    walls: &[Wall],
-) -> Option<PlayerId> {
-    for (player_id, player_pos, player_speed) in player_data {
-        // Ignore players that are not moving (Idle speed)
-        if player_speed.speed_level == SpeedLevel::Idle {
-            continue;
-        }
-
-        // Ignore players that are on or above the roof
-        if player_pos.y >= ROOF_HEIGHT {
-            continue;
-        }
-
-        let dx = player_pos.x - sentry_pos.x;
-        let dz = player_pos.z - sentry_pos.z;
-        let distance_sq = dx.mul_add(dx, dz * dz);
-
-        if distance_sq > SENTRY_VISION_RANGE * SENTRY_VISION_RANGE {
-            continue;
-        }
-
-        // Check line of sight - use player sweep to check if path is clear
-        if has_line_of_sight(sentry_pos, player_pos, walls) {
-            return Some(*player_id);
-        }
-    }
-    None
-}
-
-// Check if there's a clear line of sight between two positions
-fn has_line_of_sight(from: &Position, to: &Position, walls: &[Wall]) -> bool {
-    // Use swept collision check to see if any wall blocks the path
-    for wall in walls {
-        if sweep_player_vs_wall(from, to, wall) {
-            return false;
-        }
-    }
-    true
-}
-
 const SENTRY_CENTER_THRESHOLD: f32 = 0.2;
+const SENTRY_WAYPOINT_THRESHOLD: f32 = 0.5;
 
 // ============================================================================
 // Movement Modes
@@ -73,10 +39,12 @@ pub fn pre_patrol_movement(
     vel: &mut Velocity,
     face_dir: &mut f32,
     sentry_info: &mut SentryInfo,
+    map_layout: &MapLayout,
     players: &PlayerMap,
     sentry_grid: &mut SentryGrid,
     delta: f32,
 ) {
+    let stats = archetype_stats(sentry_info.archetype);
     let grid_x = (((pos.x + FIELD_WIDTH / 2.0) / GRID_SIZE).floor() as i32).clamp(0, GRID_COLS - 1);
     let grid_z = (((pos.z + FIELD_DEPTH / 2.0) / GRID_SIZE).floor() as i32).clamp(0, GRID_ROWS - 1);
     let center = cell_center(grid_x, grid_z);
@@ -100,14 +68,18 @@ pub fn pre_patrol_movement(
         *face_dir = 0.0;
 
         sentry_info.mode = SentryMode::Patrol;
-        sentry_info.mode_timer = SENTRY_COOLDOWN_DURATION;
+        sentry_info.mode_timer = sentry_info.cooldown_duration;
         sentry_info.at_intersection = true;
 
         broadcast_to_all(
             players,
             ServerMessage::Sentry(SSentry {
                 id: *sentry_id,
-                sentry: Sentry { pos: *pos, vel: *vel },
+                sentry: Sentry {
+                    pos: *pos,
+                    vel: *vel,
+                    archetype: sentry_info.archetype,
+                },
             }),
         );
     } else {
@@ -120,9 +92,9 @@ pub fn pre_patrol_movement(
         let dir_x = dx / distance;
         let dir_z = dz / distance;
         let new_vel = Velocity {
-            x: dir_x * SENTRY_SPEED,
+            x: dir_x * stats.patrol_speed,
             y: 0.0,
-            z: dir_z * SENTRY_SPEED,
+            z: dir_z * stats.patrol_speed,
         };
 
         // Only broadcast if velocity changed
@@ -136,13 +108,18 @@ pub fn pre_patrol_movement(
                 players,
                 ServerMessage::Sentry(SSentry {
                     id: *sentry_id,
-                    sentry: Sentry { pos: *pos, vel: *vel },
+                    sentry: Sentry {
+                        pos: *pos,
+                        vel: *vel,
+                        archetype: sentry_info.archetype,
+                    },
                 }),
             );
         }
 
         pos.x += vel.x * delta;
         pos.z += vel.z * delta;
+        pos.y = floor_height_at(map_layout, pos.x, pos.z, pos.y);
     }
 }
 
@@ -154,13 +131,17 @@ pub fn patrol_movement(
     face_dir: &mut f32,
     sentry_info: &mut SentryInfo,
     grid_config: &GridConfig,
+    map_layout: &MapLayout,
     players: &PlayerMap,
     sentry_grid: &mut SentryGrid,
+    blackboard: &SentryBlackboard,
     delta: f32,
     rng: &mut impl rand::Rng,
 ) {
+    let stats = archetype_stats(sentry_info.archetype);
     let grid_x = (((pos.x + FIELD_WIDTH / 2.0) / GRID_SIZE).floor() as i32).clamp(0, GRID_COLS - 1);
     let grid_z = (((pos.z + FIELD_DEPTH / 2.0) / GRID_SIZE).floor() as i32).clamp(0, GRID_ROWS - 1);
+    let level = nav_level(grid_config, grid_x, grid_z, pos.y);
 
     let field = &mut sentry_grid.0[grid_z as usize][grid_x as usize];
     assert!(field.is_some());
@@ -193,7 +174,15 @@ pub fn patrol_movement(
             *field = None;
         }
 
-        let valid_directions = valid_directions(grid_config, grid_x, grid_z, &sentry_grid.0, *sentry_id);
+        let valid_directions = valid_directions(
+            grid_config,
+            grid_x,
+            grid_z,
+            level,
+            &sentry_grid.0,
+            blackboard,
+            *sentry_id,
+        );
         let mut direction_changed = false;
 
         if valid_directions.is_empty() {
@@ -202,14 +191,22 @@ pub fn patrol_movement(
                 direction_changed = true;
             }
         } else {
-            if current_direction.is_blocked(grid_config, grid_x, grid_z, &sentry_grid.0, *sentry_id) {
+            if current_direction.is_blocked(
+                grid_config,
+                grid_x,
+                grid_z,
+                level,
+                &sentry_grid.0,
+                blackboard,
+                *sentry_id,
+            ) {
                 let ahead_directions = ahead_directions(&valid_directions, current_direction);
                 if ahead_directions.is_empty() {
                     let new_direction = valid_directions.first().copied().expect("no valid direction");
-                    *vel = new_direction.to_velocity();
+                    *vel = new_direction.to_velocity(stats.patrol_speed);
                     direction_changed = true;
                 } else if let Some(new_direction) = pick_direction(rng, &ahead_directions) {
-                    *vel = new_direction.to_velocity();
+                    *vel = new_direction.to_velocity(stats.patrol_speed);
                     direction_changed = true;
                 }
             }
@@ -218,7 +215,7 @@ pub fn patrol_movement(
                 && !valid_directions.is_empty()
                 && let Some(new_direction) = pick_direction(rng, &valid_directions)
             {
-                *vel = new_direction.to_velocity();
+                *vel = new_direction.to_velocity(stats.patrol_speed);
                 direction_changed = true;
             }
         }
@@ -230,7 +227,11 @@ pub fn patrol_movement(
                 players,
                 ServerMessage::Sentry(SSentry {
                     id: *sentry_id,
-                    sentry: Sentry { pos: *pos, vel: *vel },
+                    sentry: Sentry {
+                        pos: *pos,
+                        vel: *vel,
+                        archetype: sentry_info.archetype,
+                    },
                 }),
             );
         }
@@ -254,6 +255,7 @@ pub fn patrol_movement(
 
     pos.x += vel.x * delta;
     pos.z += vel.z * delta;
+    pos.y = floor_height_at(map_layout, pos.x, pos.z, pos.y);
 
     // Incrementally adjust position toward grid line based on current direction
     match current_direction {
@@ -262,77 +264,137 @@ pub fn patrol_movement(
         }
         GridDirection::East | GridDirection::West => {
             let diff = center.z - pos.z;
-            pos.z += diff.signum() * (diff.abs().min(SENTRY_SPEED * delta * 0.5));
+            pos.z += diff.signum() * (diff.abs().min(stats.patrol_speed * delta * 0.5));
         }
         GridDirection::North | GridDirection::South => {
             let diff = center.x - pos.x;
-            pos.x += diff.signum() * (diff.abs().min(SENTRY_SPEED * delta * 0.5));
+            pos.x += diff.signum() * (diff.abs().min(stats.patrol_speed * delta * 0.5));
         }
     }
 }
 
-// Target mode movement - moves toward target player with wall sliding
-// If the target player has sentry hunt power-up, reverses direction to flee
+// Target mode movement - follows an A* path through the grid toward the target player, then moves
+// straight at it once in the same cell. With a `lead` (seconds), the path leads to where the player
+// will be if it keeps moving, to cut it off. When fleeing (the target player has sentry hunt
+// power-up), the path leads to the nearby cell farthest from the player instead. Paths avoid cells
+// of other sentries and the route is reserved on the blackboard. Paths lead up and down ramps, so
+// players on roofs are followed as well.
 pub fn target_movement(
     sentry_id: &SentryId,
     pos: &mut Position,
     vel: &mut Velocity,
+    sentry_info: &mut SentryInfo,
     target_id: PlayerId,
+    fleeing: bool,
+    lead: f32,
     player_data: &[(PlayerId, Position, Speed)],
-    walls: &[Wall],
-    ramps: &[Ramp],
+    grid_config: &GridConfig,
+    sentry_grid: &SentryGrid,
+    blackboard: &mut SentryBlackboard,
+    map_layout: &MapLayout,
     players: &PlayerMap,
     delta: f32,
 ) {
     // Find target player position
-    let target_pos = player_data
+    let target = player_data
         .iter()
-        .find(|(id, pos, _)| *id == target_id && pos.y < ROOF_HEIGHT)
-        .map(|(_, pos, _)| pos);
+        .find(|(id, pos, _)| *id == target_id && is_within_sentry_reach(pos.y))
+        .map(|(_, pos, speed)| (pos, speed));
 
-    let Some(target_pos) = target_pos else {
+    let Some((target_pos, target_speed)) = target else {
         return;
     };
 
-    // Check if target has sentry hunt power-up active
-    let target_has_sentry_hunt = ALWAYS_SENTRY_HUNT
-        || players
-            .0
-            .get(&target_id)
-            .is_some_and(|info| info.sentry_hunt_power_up_timer > 0.0);
+    let stats = archetype_stats(sentry_info.archetype);
+    let follow_speed = stats.follow_speed * sentry_info.follow_speed_scale;
+    let path = &mut sentry_info.path;
 
-    // Calculate direction to target
-    let dx = target_pos.x - pos.x;
-    let dz = target_pos.z - pos.z;
-    let distance = dx.hypot(dz);
+    // Position the path leads to (or away from)
+    let target_vel = target_speed.to_velocity();
+    let aim_pos = Position {
+        x: target_vel.x.mul_add(lead, target_pos.x),
+        y: target_pos.y,
+        z: target_vel.z.mul_add(lead, target_pos.z),
+    };
 
-    if distance < 0.01 {
-        // Already at target
-        vel.x = 0.0;
-        vel.z = 0.0;
-        return;
+    let sentry_cell = nav_cell_at(grid_config, pos);
+    let target_cell = nav_cell_at(grid_config, &aim_pos);
+
+    // Replan when the target changes cells or the sentry switches between chasing and fleeing.
+    // Unreachable targets leave no path and are replanned every tick (the grid is small).
+    let needs_path = path
+        .as_ref()
+        .is_none_or(|path| path.target_cell != target_cell || path.fleeing != fleeing);
+    if needs_path {
+        let goal = if fleeing {
+            find_flee_cell(grid_config, sentry_cell, target_cell, SENTRY_FLEE_SEARCH_CELLS)
+        } else {
+            target_cell
+        };
+        let extra_cost = |(grid_x, grid_z, _): NavCell| {
+            let occupied =
+                sentry_grid.0[grid_z as usize][grid_x as usize].is_some_and(|occupant| occupant != *sentry_id);
+            if occupied || blackboard.is_reserved_by_other((grid_x, grid_z), *sentry_id) {
+                SENTRY_RESERVED_CELL_COST
+            } else {
+                0
+            }
+        };
+        *path = find_path(grid_config, sentry_cell, goal, extra_cost).map(|waypoints| SentryPath {
+            waypoints,
+            target_cell,
+            fleeing,
+        });
     }
 
-    // Normalize direction
-    let dir_x = dx / distance;
-    let dir_z = dz / distance;
+    // Drop waypoints the sentry has reached
+    if let Some(path) = path.as_mut() {
+        while let Some(&(grid_x, grid_z, _)) = path.waypoints.front() {
+            let center = cell_center(grid_x, grid_z);
+            if (center.x - pos.x).hypot(center.z - pos.z) >= SENTRY_WAYPOINT_THRESHOLD {
+                break;
+            }
+            path.waypoints.pop_front();
+        }
+        blackboard.reserve_route(*sentry_id, &path.waypoints);
+    }
 
-    // If target has sentry hunt power-up, reverse direction (flee instead of follow)
-    let (final_dir_x, final_dir_z) = if target_has_sentry_hunt {
-        (-dir_x, -dir_z)
+    // Steer toward the next waypoint. Without one, a fleeing sentry has reached its cell and waits,
+    // and a chasing sentry shares the target's cell (or has no path) and moves straight at it.
+    let waypoint = path.as_ref().and_then(|path| path.waypoints.front().copied());
+    let (dx, dz) = if let Some((grid_x, grid_z, _)) = waypoint {
+        let center = cell_center(grid_x, grid_z);
+        (center.x - pos.x, center.z - pos.z)
+    } else if fleeing && path.is_some() {
+        (0.0, 0.0)
+    } else if fleeing {
+        (pos.x - target_pos.x, pos.z - target_pos.z)
     } else {
-        (dir_x, dir_z)
+        (target_pos.x - pos.x, target_pos.z - pos.z)
     };
+    let distance = dx.hypot(dz);
 
-    // Apply follow speed
-    let desired_vel = Velocity {
-        x: final_dir_x * SENTRY_FOLLOW_SPEED,
-        y: 0.0,
-        z: final_dir_z * SENTRY_FOLLOW_SPEED,
+    // Apply follow speed, or stop when already there
+    let desired_vel = if distance < 0.01 {
+        Velocity { x: 0.0, y: 0.0, z: 0.0 }
+    } else {
+        Velocity {
+            x: dx / distance * follow_speed,
+            y: 0.0,
+            z: dz / distance * follow_speed,
+        }
     };
 
-    // Apply sliding movement
-    let final_pos = slide_sentry_along_obstacles(walls, ramps, pos, desired_vel.x, desired_vel.z, delta);
+    // Apply sliding movement against the walls on the sentry's level, then follow the floor
+    let walls = walls_at_height(map_layout, pos.y, false);
+    let mut final_pos =
+        slide_sentry_along_obstacles(&walls, &map_layout.ramps, pos, desired_vel.x, desired_vel.z, delta);
+    final_pos.y = floor_height_at(map_layout, final_pos.x, final_pos.z, pos.y);
+
+    // Sentries never drop off roof edges
+    if pos.y - final_pos.y > LEVEL_HEIGHT_TOLERANCE {
+        final_pos = *pos;
+    }
 
     let actual_dx = final_pos.x - pos.x;
     let actual_dz = final_pos.z - pos.z;
@@ -353,8 +415,72 @@ pub fn target_movement(
             players,
             ServerMessage::Sentry(SSentry {
                 id: *sentry_id,
-                sentry: Sentry { pos: *pos, vel: *vel },
+                sentry: Sentry {
+                    pos: *pos,
+                    vel: *vel,
+                    archetype: sentry_info.archetype,
+                },
             }),
         );
     }
 }
+
+// Target mode for stationary sentries - turns toward the target player. Returns the pitch to shoot
+// at (e.g. up onto a roof) when it is time to shoot at it.
+pub fn turret_aim(
+    pos: &Position,
+    face_dir: &mut f32,
+    sentry_info: &mut SentryInfo,
+    target_id: PlayerId,
+    player_data: &[(PlayerId, Position, Speed)],
+    delta: f32,
+) -> Option<f32> {
+    let (_, target_pos, _) = player_data.iter().find(|(id, _, _)| *id == target_id)?;
+
+    let dx = target_pos.x - pos.x;
+    let dz = target_pos.z - pos.z;
+    *face_dir = dx.atan2(dz);
+
+    sentry_info.fire_timer -= delta;
+    if sentry_info.fire_timer > 0.0 {
+        return None;
+    }
+    sentry_info.fire_timer = archetype_stats(sentry_info.archetype).fire_interval;
+    Some((target_pos.y - pos.y).atan2(dx.hypot(dz)))
+}
+
+// Shoot a projectile from a sentry in its facing direction
+pub fn fire_sentry_shot(
+    commands: &mut Commands,
+    sentry_id: &SentryId,
+    pos: &Position,
+    face_dir: f32,
+    face_pitch: f32,
+    map_layout: &MapLayout,
+    players: &PlayerMap,
+    next_projectile_id: &mut NextProjectileId,
+) {
+    // Spawn projectile(s), tagged with the sentry's ID instead of a player's
+    let walls: Vec<_> = map_layout.visible_walls().copied().collect();
+    let spawns = calculate_projectile_spawns(
+        pos,
+        face_dir,
+        face_pitch,
+        WeaponId::Ball,
+        false,
+        &walls,
+        &map_layout.ramps,
+        &map_layout.roofs,
+    );
+    let projectiles = spawn_projectiles(commands, next_projectile_id, &spawns, WeaponId::Ball, *sentry_id);
+
+    broadcast_to_all(
+        players,
+        ServerMessage::SentryShot(SSentryShot {
+            id: *sentry_id,
+            face_dir,
+            face_pitch,
+            projectiles,
+        }),
+    );
+}
@@ -1,6 +1,10 @@
-use crate::{constants::*, resources::GridConfig};
+use crate::{
+    constants::SENTRY_MAX_LEVEL,
+    resources::{GridCell, GridConfig, SentryBlackboard},
+};
 use common::{
     constants::*,
+    map::level_height,
     protocol::{SentryId, Velocity},
 };
 
@@ -17,28 +21,28 @@ impl GridDirection {
     pub const ALL: [Self; 4] = [Self::East, Self::North, Self::West, Self::South];
 
     #[must_use]
-    pub fn to_velocity(self) -> Velocity {
+    pub fn to_velocity(self, speed: f32) -> Velocity {
         match self {
             Self::None => Velocity { x: 0.0, y: 0.0, z: 0.0 },
             Self::East => Velocity {
-                x: SENTRY_SPEED,
+                x: speed,
                 y: 0.0,
                 z: 0.0,
             },
             Self::North => Velocity {
                 x: 0.0,
                 y: 0.0,
-                z: -SENTRY_SPEED,
+                z: -speed,
             },
             Self::West => Velocity {
-                x: -SENTRY_SPEED,
+                x: -speed,
                 y: 0.0,
                 z: 0.0,
             },
             Self::South => Velocity {
                 x: 0.0,
                 y: 0.0,
-                z: SENTRY_SPEED,
+                z: speed,
             },
         }
     }
@@ -54,61 +58,143 @@ impl GridDirection {
         }
     }
 
+    // Grid coordinates of the neighboring cell in this direction
+    #[must_use]
+    pub const fn neighbor(self, grid_x: i32, grid_z: i32) -> (i32, i32) {
+        match self {
+            Self::North => (grid_x, grid_z - 1),
+            Self::South => (grid_x, grid_z + 1),
+            Self::East => (grid_x + 1, grid_z),
+            Self::West => (grid_x - 1, grid_z),
+            Self::None => (grid_x, grid_z),
+        }
+    }
+
     #[must_use]
     pub fn is_blocked(
         self,
         grid_config: &GridConfig,
         grid_x: i32,
         grid_z: i32,
+        level: u8,
         sentry_grid: &[Vec<Option<SentryId>>],
+        blackboard: &SentryBlackboard,
         sentry_id: SentryId,
     ) -> bool {
         if self == Self::None {
             return false;
         }
 
+        if self.is_obstructed(grid_config, grid_x, grid_z, level) {
+            return true;
+        }
+
+        // Check if target cell is occupied by another sentry
+        let (next_x, next_z) = self.neighbor(grid_x, grid_z);
+        let cell_occupant = sentry_grid[next_z as usize][next_x as usize];
+        if let Some(occupant) = cell_occupant
+            && occupant != sentry_id
+        {
+            return true; // Blocked by another sentry
+        }
+
+        // Keep out of the routes of targeting sentries
+        blackboard.is_reserved_by_other((next_x, next_z), sentry_id)
+    }
+
+    // Check if the map itself blocks this direction on the given level. Ignores other sentries.
+    #[must_use]
+    pub fn is_obstructed(self, grid_config: &GridConfig, grid_x: i32, grid_z: i32, level: u8) -> bool {
+        self.step(grid_config, grid_x, grid_z, level).is_none()
+    }
+
+    // Level a sentry ends up on when walking from the cell on the given level to the neighboring
+    // cell in this direction, or `None` if the map blocks the way:
+    // - Ground: walls on the cell edge block, ramps can only be entered and left at their ends.
+    //   Ramp cells count as ground, walking off the high end leads onto the roof.
+    // - Roof: only walls around upper decks block, but the roof ends at its edges (sentries never
+    //   jump down). Walking onto the high end of a ramp leads down to the ground.
+    #[must_use]
+    pub fn step(self, grid_config: &GridConfig, grid_x: i32, grid_z: i32, level: u8) -> Option<u8> {
+        if self == Self::None {
+            return Some(level);
+        }
+
+        let (next_x, next_z) = self.neighbor(grid_x, grid_z);
+        if !(0..GRID_COLS).contains(&next_x) || !(0..GRID_ROWS).contains(&next_z) {
+            return None; // out-of-bounds neighbor is considered blocked
+        }
+
         let cell = grid_config.grid[grid_z as usize][grid_x as usize];
+        let next = grid_config.grid[next_z as usize][next_x as usize];
+        let wall_blocked = self.has_wall(cell);
+        let is_roof_floor = next.has_roof && !next.has_upper_ramp;
+
+        if level == 0 {
+            if wall_blocked {
+                return None;
+            }
+            match (cell.has_ramp, next.has_ramp) {
+                (false, false) | (true, true) => Some(0),
+                (false, true) => self.opposite().is_ramp_base(next).then_some(0),
+                (true, false) if self.is_ramp_base(cell) => Some(0),
+                (true, false) => (self.is_ramp_top(cell) && is_roof_floor).then_some(1),
+            }
+        } else if wall_blocked && (cell.has_upper_roof || next.has_upper_roof) {
+            None
+        } else if next.has_ramp {
+            self.opposite().is_ramp_top(next).then_some(0)
+        } else {
+            is_roof_floor.then_some(1)
+        }
+    }
 
-        // Check walls
-        let wall_blocked = match self {
+    // Check if the cell has a wall on its edge in this direction
+    const fn has_wall(self, cell: GridCell) -> bool {
+        match self {
             Self::None => false,
             Self::East => cell.has_east_wall,
             Self::North => cell.has_north_wall,
             Self::West => cell.has_west_wall,
             Self::South => cell.has_south_wall,
-        };
-
-        if wall_blocked {
-            return true;
         }
+    }
 
-        // Check if leads to ramp
-        let (next_x, next_z) = match self {
-            Self::North => (grid_x, grid_z - 1),
-            Self::South => (grid_x, grid_z + 1),
-            Self::East => (grid_x + 1, grid_z),
-            Self::West => (grid_x - 1, grid_z),
-            Self::None => (grid_x, grid_z),
-        };
-
-        if !(0..GRID_COLS).contains(&next_x) || !(0..GRID_ROWS).contains(&next_z) {
-            return true; // out-of-bounds neighbor is considered blocked
+    // Check if the low end of a ramp is on the cell's edge in this direction
+    const fn is_ramp_base(self, cell: GridCell) -> bool {
+        match self {
+            Self::None => false,
+            Self::East => cell.ramp_base_east,
+            Self::North => cell.ramp_base_north,
+            Self::West => cell.ramp_base_west,
+            Self::South => cell.ramp_base_south,
         }
+    }
 
-        if grid_config.grid[next_z as usize][next_x as usize].has_ramp {
-            return true;
+    // Check if the high end of a ramp is on the cell's edge in this direction
+    const fn is_ramp_top(self, cell: GridCell) -> bool {
+        match self {
+            Self::None => false,
+            Self::East => cell.ramp_top_east,
+            Self::North => cell.ramp_top_north,
+            Self::West => cell.ramp_top_west,
+            Self::South => cell.ramp_top_south,
         }
+    }
+}
 
-        // Check if target cell is occupied by another sentry
-        let cell_occupant = sentry_grid[next_z as usize][next_x as usize];
-        if let Some(occupant) = cell_occupant
-            && occupant != sentry_id
-        {
-            return true; // Blocked by another sentry
-        }
+// Level a sentry or player at the position is on in the navigation grid. Ramp cells count as
+// ground, anything higher than half a storey above the ground as roof.
+#[must_use]
+pub fn nav_level(grid_config: &GridConfig, grid_x: i32, grid_z: i32, y: f32) -> u8 {
+    let on_ramp = grid_config.grid[grid_z as usize][grid_x as usize].has_ramp;
+    u8::from(!on_ramp && y >= LEVEL_HEIGHT / 2.0)
+}
 
-        false
-    }
+// Check if sentries can get at a player at this height, i.e. the player is not above the roof
+#[must_use]
+pub fn is_within_sentry_reach(y: f32) -> bool {
+    y < level_height(SENTRY_MAX_LEVEL) + LEVEL_HEIGHT / 2.0
 }
 
 #[must_use]
@@ -131,7 +217,9 @@ pub fn valid_directions(
     grid_config: &GridConfig,
     grid_x: i32,
     grid_z: i32,
+    level: u8,
     sentry_grid: &[Vec<Option<SentryId>>],
+    blackboard: &SentryBlackboard,
     sentry_id: SentryId,
 ) -> Vec<GridDirection> {
     assert!(
@@ -143,7 +231,7 @@ pub fn valid_directions(
     let valid: Vec<_> = GridDirection::ALL
         .iter()
         .copied()
-        .filter(|dir| !dir.is_blocked(grid_config, grid_x, grid_z, sentry_grid, sentry_id))
+        .filter(|dir| !dir.is_blocked(grid_config, grid_x, grid_z, level, sentry_grid, blackboard, sentry_id))
         .collect();
 
     valid
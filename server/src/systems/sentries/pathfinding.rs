@@ -0,0 +1,217 @@
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, HashMap, VecDeque, hash_map::Entry},
+};
+
+use super::navigation::{GridDirection, nav_level};
+use crate::{map::grid_coords_from_position, resources::GridConfig};
+use common::{
+    constants::{GRID_COLS, GRID_ROWS},
+    protocol::Position,
+};
+
+// Grid coordinates and level of a cell (grid_x, grid_z, level), 0 = ground (including ramps), 1 = roof
+pub type NavCell = (i32, i32, u8);
+
+// Navigation cell of a position, clamped to the field
+#[must_use]
+pub fn nav_cell_at(grid_config: &GridConfig, pos: &Position) -> NavCell {
+    let (grid_x, grid_z) = grid_coords_from_position(pos);
+    let (grid_x, grid_z) = (grid_x.clamp(0, GRID_COLS - 1), grid_z.clamp(0, GRID_ROWS - 1));
+    (grid_x, grid_z, nav_level(grid_config, grid_x, grid_z, pos.y))
+}
+
+// ============================================================================
+// Grid Search
+// ============================================================================
+
+// Cells a sentry can walk to from the given cell, on the ground, up and down ramps and across roofs
+fn walkable_neighbors(grid_config: &GridConfig, (grid_x, grid_z, level): NavCell) -> impl Iterator<Item = NavCell> {
+    GridDirection::ALL.into_iter().filter_map(move |dir| {
+        let next_level = dir.step(grid_config, grid_x, grid_z, level)?;
+        let (next_x, next_z) = dir.neighbor(grid_x, grid_z);
+        Some((next_x, next_z, next_level))
+    })
+}
+
+// Manhattan distance on the grid. Levels are ignored, every step between levels also moves to a
+// neighboring cell.
+#[must_use]
+const fn manhattan_distance(a: NavCell, b: NavCell) -> i32 {
+    (a.0 - b.0).abs() + (a.1 - b.1).abs()
+}
+
+// Find the cheapest path between two cells with A*, using the Manhattan distance as heuristic.
+// Entering a cell costs 1 plus `extra_cost` of the cell, e.g. to steer around other sentries.
+// Returns the cells to walk through after `start` (ending with `goal`), or `None` if the goal
+// cannot be reached.
+#[must_use]
+pub fn find_path(
+    grid_config: &GridConfig,
+    start: NavCell,
+    goal: NavCell,
+    extra_cost: impl Fn(NavCell) -> i32,
+) -> Option<VecDeque<NavCell>> {
+    let mut open = BinaryHeap::from([Reverse((manhattan_distance(start, goal), 0, start))]);
+    let mut came_from: HashMap<NavCell, NavCell> = HashMap::new();
+    let mut cost_so_far: HashMap<NavCell, i32> = HashMap::from([(start, 0)]);
+
+    while let Some(Reverse((_, cost, cell))) = open.pop() {
+        if cell == goal {
+            let mut path = VecDeque::new();
+            let mut current = goal;
+            while current != start {
+                path.push_front(current);
+                current = came_from[&current];
+            }
+            return Some(path);
+        }
+
+        // Skip stale entries that were already reached more cheaply
+        if cost_so_far.get(&cell).is_some_and(|&best| cost > best) {
+            continue;
+        }
+
+        for next in walkable_neighbors(grid_config, cell) {
+            let next_cost = cost + 1 + extra_cost(next);
+            if cost_so_far.get(&next).is_none_or(|&best| next_cost < best) {
+                cost_so_far.insert(next, next_cost);
+                came_from.insert(next, cell);
+                open.push(Reverse((next_cost + manhattan_distance(next, goal), next_cost, next)));
+            }
+        }
+    }
+
+    None
+}
+
+// Walking distance in cells from the given cell to every reachable cell
+fn walking_distances(grid_config: &GridConfig, from: NavCell) -> HashMap<NavCell, i32> {
+    let mut distances = HashMap::from([(from, 0)]);
+    let mut queue = VecDeque::from([from]);
+
+    while let Some(cell) = queue.pop_front() {
+        let distance = distances[&cell];
+        for next in walkable_neighbors(grid_config, cell) {
+            if let Entry::Vacant(entry) = distances.entry(next) {
+                entry.insert(distance + 1);
+                queue.push_back(next);
+            }
+        }
+    }
+
+    distances
+}
+
+// Find the cell within `max_steps` of `start` that is the longest walk away from `threat`.
+// Cells the threat cannot reach count as the farthest. Prefers closer cells on ties.
+#[must_use]
+pub fn find_flee_cell(grid_config: &GridConfig, start: NavCell, threat: NavCell, max_steps: i32) -> NavCell {
+    let threat_distances = walking_distances(grid_config, threat);
+    let safety = |cell: &NavCell| threat_distances.get(cell).copied().unwrap_or(i32::MAX);
+
+    let mut best = start;
+    let mut best_safety = safety(&start);
+
+    // Breadth-first, so closer cells are visited first
+    let mut visited = HashMap::from([(start, 0)]);
+    let mut queue = VecDeque::from([start]);
+    while let Some(cell) = queue.pop_front() {
+        let steps = visited[&cell];
+        if safety(&cell) > best_safety {
+            best = cell;
+            best_safety = safety(&cell);
+        }
+
+        if steps >= max_steps {
+            continue;
+        }
+
+        for next in walkable_neighbors(grid_config, cell) {
+            if let Entry::Vacant(entry) = visited.entry(next) {
+                entry.insert(steps + 1);
+                queue.push_back(next);
+            }
+        }
+    }
+
+    best
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::resources::GridCell;
+
+    // An open grid without walls, ramps or roofs
+    fn open_grid() -> GridConfig {
+        GridConfig {
+            grid: vec![vec![GridCell::default(); GRID_COLS as usize]; GRID_ROWS as usize],
+        }
+    }
+
+    // Wall between cell (grid_x, grid_z) and its east neighbor
+    fn add_east_wall(grid_config: &mut GridConfig, grid_x: i32, grid_z: i32) {
+        let (col, row) = (grid_x as usize, grid_z as usize);
+        grid_config.grid[row][col].has_east_wall = true;
+        grid_config.grid[row][col + 1].has_west_wall = true;
+    }
+
+    // Wall between cell (grid_x, grid_z) and its south neighbor
+    fn add_south_wall(grid_config: &mut GridConfig, grid_x: i32, grid_z: i32) {
+        let (col, row) = (grid_x as usize, grid_z as usize);
+        grid_config.grid[row][col].has_south_wall = true;
+        grid_config.grid[row + 1][col].has_north_wall = true;
+    }
+
+    #[test]
+    fn find_path_walks_around_a_wall() {
+        // Wall between columns 4 and 5, open only in the last row
+        let mut grid_config = open_grid();
+        for grid_z in 0..GRID_ROWS - 1 {
+            add_east_wall(&mut grid_config, 4, grid_z);
+        }
+
+        let start = (2, 2, 0);
+        let goal = (7, 2, 0);
+        let path = find_path(&grid_config, start, goal, |_| 0).expect("goal is reachable");
+
+        // Every step moves to a walkable neighbor, and the only gap is the one in the last row
+        let mut previous = start;
+        for &cell in &path {
+            assert!(walkable_neighbors(&grid_config, previous).any(|next| next == cell));
+            previous = cell;
+        }
+        assert_eq!(path.back(), Some(&goal));
+        assert!(path.contains(&(4, GRID_ROWS - 1, 0)));
+        assert!(path.contains(&(5, GRID_ROWS - 1, 0)));
+
+        // Shortest detour: down to the last row, through the gap and back up
+        let detour = 2 * (GRID_ROWS - 1 - start.1) + manhattan_distance(start, goal);
+        assert_eq!(path.len(), detour as usize);
+    }
+
+    #[test]
+    fn find_path_returns_none_for_an_unreachable_goal() {
+        // Cell (0, 0) is closed off by the field boundary and two walls
+        let mut grid_config = open_grid();
+        add_east_wall(&mut grid_config, 0, 0);
+        add_south_wall(&mut grid_config, 0, 0);
+
+        assert!(find_path(&grid_config, (5, 5, 0), (0, 0, 0), |_| 0).is_none());
+    }
+
+    #[test]
+    fn find_flee_cell_moves_away_from_the_threat() {
+        let grid_config = open_grid();
+        let start = (5, 5, 0);
+        let threat = (4, 5, 0);
+        let max_steps = 3;
+
+        let flee = find_flee_cell(&grid_config, start, threat, max_steps);
+
+        // On an open grid the walking distance is the Manhattan distance
+        assert!(manhattan_distance(flee, threat) > manhattan_distance(start, threat));
+        assert!(manhattan_distance(flee, start) <= max_steps);
+    }
+}
@@ -0,0 +1,173 @@
+use bevy::prelude::*;
+
+use super::{archetypes::SentryArchetypeStats, navigation::is_within_sentry_reach};
+use crate::{constants::*, resources::PlayerMap};
+use common::{collision::sweep_player_vs_wall, constants::*, map::walls_at_height, power_ups::EffectKind, protocol::*};
+
+// ============================================================================
+// Perception Component
+// ============================================================================
+
+// What a patrolling sentry noticed so far. Suspicion builds up while a player is seen or heard and
+// decays otherwise; the sentry commits to the suspect once it reaches `SENTRY_SUSPICION_THRESHOLD`.
+#[derive(Component, Debug, Clone, Copy, Default)]
+pub struct SentryPerception {
+    pub suspicion: f32,
+    pub suspect: Option<PlayerId>,
+}
+
+impl SentryPerception {
+    // Update suspicion from the strongest stimulus among all players. Returns the suspect once the
+    // sentry is suspicious enough to act.
+    pub fn update(
+        &mut self,
+        stats: &SentryArchetypeStats,
+        sentry_pos: &Position,
+        sentry_face_dir: f32,
+        player_data: &[(PlayerId, Position, Speed)],
+        players: &PlayerMap,
+        map_layout: &MapLayout,
+        now: f32,
+        delta: f32,
+    ) -> Option<PlayerId> {
+        // Prefer the most threatening player (strongest stimulus), then the closest one
+        let strongest = player_data
+            .iter()
+            .filter_map(|(player_id, player_pos, player_speed)| {
+                let info = players.0.get(player_id);
+                let shot_heard = info.is_some_and(|info| now - info.last_shot_time < SENTRY_SHOT_HEARING_DURATION);
+                let cloaked = info.is_some_and(|info| info.has_effect(EffectKind::Cloak));
+                let stimulus = player_stimulus(
+                    stats,
+                    sentry_pos,
+                    sentry_face_dir,
+                    player_pos,
+                    player_speed,
+                    shot_heard,
+                    cloaked,
+                    map_layout,
+                );
+                let distance = (player_pos.x - sentry_pos.x).hypot(player_pos.z - sentry_pos.z);
+                (stimulus > 0.0).then_some((*player_id, stimulus, distance))
+            })
+            .max_by(|a, b| a.1.total_cmp(&b.1).then(b.2.total_cmp(&a.2)));
+
+        if let Some((player_id, stimulus, _)) = strongest {
+            self.suspicion = stimulus.mul_add(delta, self.suspicion).min(SENTRY_SUSPICION_THRESHOLD);
+            self.suspect = Some(player_id);
+        } else {
+            self.suspicion = SENTRY_SUSPICION_DECAY.mul_add(-delta, self.suspicion).max(0.0);
+            if self.suspicion <= 0.0 {
+                self.suspect = None;
+            }
+        }
+
+        self.suspect.filter(|_| self.suspicion >= SENTRY_SUSPICION_THRESHOLD)
+    }
+
+    // Forget everything, e.g. after committing to a target
+    pub const fn reset(&mut self) {
+        self.suspicion = 0.0;
+        self.suspect = None;
+    }
+}
+
+// ============================================================================
+// Stimuli
+// ============================================================================
+
+// Suspicion per second a player causes for a sentry, 0.0 if the player is not perceived.
+// - Vision: players inside the forward cone with a clear line of sight, fading with distance.
+//   Moving players stand out more than idle ones.
+// - Hearing: gunshots and running players nearby, also through walls and roofs.
+// Players out of reach of the sentries (on upper decks) are ignored. Cloaked players can only be heard.
+#[must_use]
+pub fn player_stimulus(
+    stats: &SentryArchetypeStats,
+    sentry_pos: &Position,
+    sentry_face_dir: f32,
+    player_pos: &Position,
+    player_speed: &Speed,
+    shot_heard: bool,
+    cloaked: bool,
+    map_layout: &MapLayout,
+) -> f32 {
+    if !is_within_sentry_reach(player_pos.y) {
+        return 0.0;
+    }
+
+    let dx = player_pos.x - sentry_pos.x;
+    let dz = player_pos.z - sentry_pos.z;
+    let distance = dx.hypot(dz);
+
+    let mut stimulus = 0.0;
+
+    // Vision
+    if !cloaked
+        && distance <= stats.vision_range
+        && in_vision_cone(sentry_face_dir, stats.vision_half_angle_degrees, dx, dz, distance)
+    {
+        let visibility = if player_speed.speed_level == SpeedLevel::Idle {
+            SENTRY_IDLE_VISIBILITY
+        } else {
+            1.0
+        };
+        if has_line_of_sight(sentry_pos, player_pos, map_layout) {
+            stimulus += SENTRY_VISION_SUSPICION_RATE * visibility * (1.0 - distance / stats.vision_range);
+        }
+    }
+
+    // Hearing
+    if shot_heard && distance <= SENTRY_SHOT_HEARING_RANGE {
+        stimulus += SENTRY_SHOT_SUSPICION_RATE * (1.0 - distance / SENTRY_SHOT_HEARING_RANGE);
+    }
+    if player_speed.speed_level == SpeedLevel::Run && distance <= SENTRY_STEP_HEARING_RANGE {
+        stimulus += SENTRY_STEP_SUSPICION_RATE * (1.0 - distance / SENTRY_STEP_HEARING_RANGE);
+    }
+
+    stimulus
+}
+
+// Check if the direction (dx, dz) lies within the vision cone around the sentry's facing direction
+fn in_vision_cone(face_dir: f32, half_angle_degrees: f32, dx: f32, dz: f32, distance: f32) -> bool {
+    if distance < f32::EPSILON {
+        return true;
+    }
+
+    // Face direction 0 points along +Z (matches `vel.x.atan2(vel.z)`)
+    let (sin_face, cos_face) = face_dir.sin_cos();
+    let cos_angle = dx.mul_add(sin_face, dz * cos_face) / distance;
+    cos_angle >= half_angle_degrees.to_radians().cos()
+}
+
+// Check if there's a clear line of sight from a sentry to a player. Walls on the levels of both
+// block, and so do roofs between them (e.g. a player on a roof is hidden from a sentry standing
+// below it).
+#[must_use]
+pub fn has_line_of_sight(from: &Position, to: &Position, map_layout: &MapLayout) -> bool {
+    let mut walls = walls_at_height(map_layout, from.y, false);
+    if (to.y - from.y).abs() > LEVEL_HEIGHT_TOLERANCE {
+        walls.extend(walls_at_height(map_layout, to.y, false));
+    }
+
+    // Use swept collision check to see if any wall blocks the path
+    !walls.iter().any(|wall| sweep_player_vs_wall(from, to, wall))
+        && !map_layout.roofs.iter().any(|roof| roof_blocks_sight(from, to, roof))
+}
+
+// Check if the sight line from the top of a sentry to the top of a player passes through a roof
+fn roof_blocks_sight(from: &Position, to: &Position, roof: &Roof) -> bool {
+    let from_y = from.y + SENTRY_HEIGHT;
+    let to_y = to.y + PLAYER_HEIGHT;
+
+    // Both ends on the same side of the roof
+    if (roof.y - from_y) * (roof.y - to_y) >= 0.0 {
+        return false;
+    }
+
+    let t = (roof.y - from_y) / (to_y - from_y);
+    let x = t.mul_add(to.x - from.x, from.x);
+    let z = t.mul_add(to.z - from.z, from.z);
+    let (min_x, max_x, min_z, max_z) = roof.bounds_xz();
+    (min_x..=max_x).contains(&x) && (min_z..=max_z).contains(&z)
+}
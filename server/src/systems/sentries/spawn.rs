@@ -1,7 +1,9 @@
 use bevy::prelude::*;
-use rand::Rng as _;
+use rand::{Rng, seq::IndexedRandom as _};
 
+use super::{archetypes::random_archetype, perception::SentryPerception};
 use crate::{
+    constants::{SENTRY_COOLDOWN_DURATION, SENTRY_DIRECTOR_SPAWN_CLEARANCE},
     map::cell_center,
     resources::{GridConfig, SentryGrid, SentryInfo, SentryMap, SentryMode, SentrySpawnConfig},
 };
@@ -24,46 +26,80 @@ pub fn sentries_spawn_system(
     let mut rng = rand::rng();
 
     for i in 0..spawn_config.num_sentries {
-        // Pick a random grid cell that doesn't have a sentry or a ramp
-        let (grid_x, grid_z) = loop {
-            let grid_x = rng.random_range(0..GRID_COLS);
-            let grid_z = rng.random_range(0..GRID_ROWS);
-
-            if sentry_grid.0[grid_z as usize][grid_x as usize].is_some() {
-                continue;
-            }
+        spawn_sentry(
+            &mut commands,
+            &mut sentries,
+            &mut sentry_grid,
+            &grid_config,
+            SentryId(i),
+            &[],
+            &mut rng,
+        );
+    }
+}
 
-            if grid_config.grid[grid_z as usize][grid_x as usize].has_ramp {
-                continue;
-            }
+// Spawn a sentry with a random archetype at the center of a random grid cell that has no sentry or
+// ramp and is at least `SENTRY_DIRECTOR_SPAWN_CLEARANCE` away from the positions to avoid. Returns
+// the new sentry, or `None` if there is no such cell.
+pub fn spawn_sentry(
+    commands: &mut Commands,
+    sentries: &mut SentryMap,
+    sentry_grid: &mut SentryGrid,
+    grid_config: &GridConfig,
+    sentry_id: SentryId,
+    avoid: &[Position],
+    rng: &mut impl Rng,
+) -> Option<Sentry> {
+    let free_cells: Vec<(i32, i32)> = (0..GRID_ROWS)
+        .flat_map(|grid_z| (0..GRID_COLS).map(move |grid_x| (grid_x, grid_z)))
+        .filter(|&(grid_x, grid_z)| {
+            let center = cell_center(grid_x, grid_z);
+            sentry_grid.0[grid_z as usize][grid_x as usize].is_none()
+                && !grid_config.grid[grid_z as usize][grid_x as usize].has_ramp
+                && avoid
+                    .iter()
+                    .all(|pos| (pos.x - center.x).hypot(pos.z - center.z) >= SENTRY_DIRECTOR_SPAWN_CLEARANCE)
+        })
+        .collect();
+    let &(grid_x, grid_z) = free_cells.choose(rng)?;
 
-            break (grid_x, grid_z);
-        };
+    // Spawn at grid center
+    let pos = cell_center(grid_x, grid_z);
 
-        // Spawn at grid center
-        let pos = cell_center(grid_x, grid_z);
+    // Start with zero velocity - patrol movement will pick initial direction
+    let vel = Velocity { x: 0.0, y: 0.0, z: 0.0 };
+    let face_dir = 0.0;
 
-        // Start with zero velocity - patrol movement will pick initial direction
-        let vel = Velocity { x: 0.0, y: 0.0, z: 0.0 };
-        let face_dir = 0.0;
+    let archetype = random_archetype(rng);
+    let entity = commands
+        .spawn((
+            SentryMarker,
+            sentry_id,
+            pos,
+            vel,
+            FaceDirection(face_dir),
+            SentryPerception::default(),
+        ))
+        .id();
 
-        let sentry_id = SentryId(i);
-        let entity = commands
-            .spawn((SentryMarker, sentry_id, pos, vel, FaceDirection(face_dir)))
-            .id();
+    sentries.0.insert(
+        sentry_id,
+        SentryInfo {
+            entity,
+            mode: SentryMode::Patrol,
+            mode_timer: 0.0,
+            follow_target: None,
+            at_intersection: true,
+            path: None,
+            archetype,
+            fire_timer: 0.0,
+            follow_speed_scale: 1.0,
+            cooldown_duration: SENTRY_COOLDOWN_DURATION,
+        },
+    );
 
-        sentries.0.insert(
-            sentry_id,
-            SentryInfo {
-                entity,
-                mode: SentryMode::Patrol,
-                mode_timer: 0.0,
-                follow_target: None,
-                at_intersection: true,
-            },
-        );
+    // Add to field map (only current cell, no heading yet since velocity is zero)
+    sentry_grid.0[grid_z as usize][grid_x as usize] = Some(sentry_id);
 
-        // Add to field map (only current cell, no heading yet since velocity is zero)
-        sentry_grid.0[grid_z as usize][grid_x as usize] = Some(sentry_id);
-    }
+    Some(Sentry { pos, vel, archetype })
 }
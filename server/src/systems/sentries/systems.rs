@@ -1,16 +1,22 @@
 use bevy::prelude::*;
 
-use super::movement::{find_visible_moving_player, patrol_movement, pre_patrol_movement, target_movement};
+use super::{
+    archetypes::archetype_stats,
+    behaviors::{SENTRY_BEHAVIOR_TREE, SentryContext},
+    movement::fire_sentry_shot,
+    perception::SentryPerception,
+};
 use crate::{
-    constants::*,
     net::ServerToClient,
-    resources::{GridConfig, PlayerMap, SentryGrid, SentryMap, SentryMode},
+    resources::{
+        GridConfig, NextProjectileId, PlayerMap, SentryBlackboard, SentryDirector, SentryGrid, SentryMap, SentryMode,
+    },
     systems::network::broadcast_to_all,
 };
 use common::{
     collision::overlap_sentry_vs_player,
-    constants::*,
     markers::{PlayerMarker, SentryMarker},
+    power_ups::{EffectKind, server_tick},
     protocol::*,
 };
 
@@ -19,23 +25,33 @@ use common::{
 // ============================================================================
 
 pub fn sentries_movement_system(
+    mut commands: Commands,
     time: Res<Time>,
     map_layout: Res<MapLayout>,
     grid_config: Res<GridConfig>,
     players: Res<PlayerMap>,
     mut sentries: ResMut<SentryMap>,
     mut sentry_grid_map: ResMut<SentryGrid>,
+    mut blackboard: ResMut<SentryBlackboard>,
+    mut next_projectile_id: ResMut<NextProjectileId>,
     mut param_set: ParamSet<(
-        Query<(&SentryId, &mut Position, &mut Velocity, &mut FaceDirection), With<SentryMarker>>,
+        Query<
+            (
+                &SentryId,
+                &mut Position,
+                &mut Velocity,
+                &mut FaceDirection,
+                &mut SentryPerception,
+            ),
+            With<SentryMarker>,
+        >,
         Query<(&PlayerId, &Position, &Speed), With<PlayerMarker>>,
     )>,
 ) {
     let delta = time.delta_secs();
+    let now = time.elapsed_secs();
     let mut rng = rand::rng();
 
-    // Use all_walls for sentry collision (sentries never go on roofs)
-    let sentry_walls = &map_layout.lower_walls;
-
     // Collect player positions and speeds (excluding stunned players)
     let player_data: Vec<(PlayerId, Position, Speed)> = param_set
         .p1()
@@ -49,137 +65,59 @@ pub fn sentries_movement_system(
 
     // First, collect all sentry data and player data we need
     let mut sentry_updates = Vec::new();
-    for (sentry_id, sentry_pos, sentry_vel, face_dir) in param_set.p0().iter() {
-        sentry_updates.push((*sentry_id, *sentry_pos, *sentry_vel, face_dir.0));
+    for (sentry_id, sentry_pos, sentry_vel, face_dir, perception) in param_set.p0().iter() {
+        sentry_updates.push((*sentry_id, *sentry_pos, *sentry_vel, face_dir.0, *perception));
     }
 
+    // Share who is targeting whom and where before the sentries decide
+    blackboard.refresh(&sentries, now);
+
     // Now process sentry updates
-    for (sentry_id, mut sentry_pos, mut sentry_vel, mut face_dir) in sentry_updates {
+    for (sentry_id, sentry_pos, sentry_vel, face_dir, perception) in sentry_updates {
         let Some(sentry_info) = sentries.0.get_mut(&sentry_id) else {
             continue;
         };
-
-        // Handle mode transitions
-        match sentry_info.mode {
-            SentryMode::Patrol => {
-                // Decrement cooldown timer
-                sentry_info.mode_timer -= delta;
-
-                // Always check for visible players
-                if let Some(target_player_id) = find_visible_moving_player(&sentry_pos, &player_data, sentry_walls) {
-                    let player_has_sentry_hunt = ALWAYS_SENTRY_HUNT
-                        || players
-                            .0
-                            .get(&target_player_id)
-                            .is_some_and(|info| info.sentry_hunt_power_up_timer > 0.0);
-
-                    // Enter target mode if: player has sentry hunt (flee) OR cooldown expired (attack)
-                    if player_has_sentry_hunt || sentry_info.mode_timer <= 0.0 {
-                        sentry_info.mode = SentryMode::Target;
-                        sentry_info.mode_timer = SENTRY_TARGET_DURATION;
-                        sentry_info.follow_target = Some(target_player_id);
-                        // Remove from field map when leaving patrol mode
-                        // Must clear both current AND destination cells (sentry occupies two cells while patrolling)
-                        sentry_grid_map.clear_patrol_cells(&sentry_pos, &sentry_vel, sentry_id);
-                    }
-                }
-            }
-            SentryMode::Target => {
-                // Check if we're fleeing from a player with sentry hunt power-up
-                let is_fleeing = sentry_info
-                    .follow_target
-                    .and_then(|target_id| players.0.get(&target_id))
-                    .is_some_and(|info| ALWAYS_SENTRY_HUNT || info.sentry_hunt_power_up_timer > 0.0);
-
-                // Update target timer: only decrement when not fleeing
-                if is_fleeing {
-                    // If a sentry was attacking and is now fleeing, the timer has been decremented
-                    // previously, so we reset it every time we are fleeing
-                    sentry_info.mode_timer = SENTRY_TARGET_DURATION;
-                } else {
-                    sentry_info.mode_timer -= delta;
-                }
-
-                if sentry_info.mode_timer <= 0.0 {
-                    // Target timer expired, switch to pre-patrol with cooldown
-                    sentry_info.mode = SentryMode::PrePatrol;
-                    sentry_info.mode_timer = SENTRY_COOLDOWN_DURATION;
-                    sentry_info.follow_target = None;
-                } else {
-                    // Check if target player still exists and is not stunned
-                    if let Some(target_id) = sentry_info.follow_target {
-                        let target_info = players.0.get(&target_id);
-                        let target_valid = target_info.is_some_and(|info| info.logged_in && info.stun_timer <= 0.0);
-                        let target_on_roof = player_data
-                            .iter()
-                            .find(|(id, _, _)| *id == target_id)
-                            .is_some_and(|(_, pos, _)| pos.y >= ROOF_HEIGHT);
-
-                        if !target_valid || target_on_roof {
-                            // Target disconnected, stunned, or on a roof, switch to pre-patrol
-                            sentry_info.mode = SentryMode::PrePatrol;
-                            sentry_info.mode_timer = SENTRY_COOLDOWN_DURATION;
-                            sentry_info.follow_target = None;
-                        }
-                    }
-                }
-            }
-            SentryMode::PrePatrol => {
-                // PrePatrol doesn't have a timer - it transitions when reaching grid center
-                // The transition is handled in pre_patrol_movement
-            }
-        }
-
-        // Execute movement based on current mode
-        match sentry_info.mode {
-            SentryMode::PrePatrol => {
-                pre_patrol_movement(
-                    &sentry_id,
-                    &mut sentry_pos,
-                    &mut sentry_vel,
-                    &mut face_dir,
-                    sentry_info,
-                    &players,
-                    &mut sentry_grid_map,
-                    delta,
-                );
-            }
-            SentryMode::Patrol => {
-                patrol_movement(
-                    &sentry_id,
-                    &mut sentry_pos,
-                    &mut sentry_vel,
-                    &mut face_dir,
-                    sentry_info,
-                    &grid_config,
-                    &players,
-                    &mut sentry_grid_map,
-                    delta,
-                    &mut rng,
-                );
-            }
-            SentryMode::Target => {
-                if let Some(target_id) = sentry_info.follow_target {
-                    target_movement(
-                        &sentry_id,
-                        &mut sentry_pos,
-                        &mut sentry_vel,
-                        target_id,
-                        &player_data,
-                        sentry_walls,
-                        &map_layout.ramps,
-                        &players,
-                        delta,
-                    );
-                }
-            }
+        let entity = sentry_info.entity;
+
+        let mut ctx = SentryContext {
+            sentry_id,
+            pos: sentry_pos,
+            vel: sentry_vel,
+            face_dir,
+            perception,
+            info: sentry_info,
+            player_data: &player_data,
+            players: &players,
+            grid_config: &grid_config,
+            map_layout: &map_layout,
+            sentry_grid: &mut sentry_grid_map,
+            blackboard: &mut blackboard,
+            rng: &mut rng,
+            now,
+            delta,
+            fire_pitch: None,
+        };
+        SENTRY_BEHAVIOR_TREE.tick(&mut ctx);
+
+        if let Some(face_pitch) = ctx.fire_pitch {
+            fire_sentry_shot(
+                &mut commands,
+                &sentry_id,
+                &ctx.pos,
+                ctx.face_dir,
+                face_pitch,
+                &map_layout,
+                &players,
+                &mut next_projectile_id,
+            );
         }
 
         // Write back the updated position, velocity, and face direction
-        if let Ok((_, mut pos, mut vel, mut fd)) = param_set.p0().get_mut(sentry_info.entity) {
-            *pos = sentry_pos;
-            *vel = sentry_vel;
-            fd.0 = face_dir;
+        if let Ok((_, mut pos, mut vel, mut fd, mut perc)) = param_set.p0().get_mut(entity) {
+            *pos = ctx.pos;
+            *vel = ctx.vel;
+            fd.0 = ctx.face_dir;
+            *perc = ctx.perception;
         }
     }
 }
@@ -190,6 +128,8 @@ pub fn sentries_movement_system(
 
 // Check for sentry-player collisions and apply stun
 pub fn sentry_player_collision_system(
+    time: Res<Time>,
+    mut director: ResMut<SentryDirector>,
     mut sentries: ResMut<SentryMap>,
     mut players: ResMut<PlayerMap>,
     sentry_query: Query<(&SentryId, &Position), With<SentryMarker>>,
@@ -212,7 +152,7 @@ pub fn sentry_player_collision_system(
         }
 
         // Skip if player has hunt power-up
-        if ALWAYS_SENTRY_HUNT || player_info.sentry_hunt_power_up_timer > 0.0 {
+        if player_info.has_effect(EffectKind::SentryHunt) {
             continue;
         }
 
@@ -237,18 +177,32 @@ pub fn sentry_player_collision_system(
         }
     }
 
-    // Apply stun and broadcast
+    // Apply stun (or let a shield absorb it) and broadcast
+    let tick = server_tick(time.elapsed());
     for (player_id, sentry_id) in player_hits {
-        let status_msg = if let Some(player_info) = players.0.get_mut(&player_id) {
-            player_info.stun_timer = SENTRY_STUN_DURATION;
-            player_info.hits -= SENTRY_HIT_PENALTY;
+        let status_msg = if let Some(player_info) = players.0.get_mut(&player_id)
+            && player_info.absorb_stun()
+        {
+            // Notify all clients so they can play the absorb effect
+            let status = player_info.status(player_id, tick);
+            broadcast_to_all(&players, ServerMessage::ShieldAbsorb(SShieldAbsorb { id: player_id }));
+            Some(status)
+        } else if let Some(player_info) = players.0.get_mut(&player_id) {
+            let stats = sentries.0.get(&sentry_id).map_or_else(
+                || archetype_stats(SentryArchetype::Standard),
+                |info| archetype_stats(info.archetype),
+            );
+            player_info.stun_timer = stats.stun_duration;
+            director.record_stun(time.elapsed_secs());
+            player_info.hits -= stats.hit_penalty;
+            player_info.stats.sentry_stuns += 1;
 
             // Send sentry hit message only to the hit player for sound effect
             let _ = player_info
                 .channel
                 .send(ServerToClient::Send(ServerMessage::SentryHit(SSentryHit {})));
 
-            Some(player_info.status(player_id))
+            Some(player_info.status(player_id, tick))
         } else {
             None
         };